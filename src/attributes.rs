@@ -0,0 +1,131 @@
+//! Per-descriptor metadata for executors: the SPI transaction attributes of a transfer, e.g. a
+//! longer NSS setup for the wake-up from sleep, a lower clock on marginal layouts, the delay
+//! before the next transfer or the cache maintenance of its buffers.
+//!
+//! An [`AttributedDescriptor`] pairs a descriptor with its metadata, by default
+//! [`TransferAttributes`]. Other metadata uses the same wrapper, e.g. the transfer times recorded
+//! by a [`TimestampHook`](super::timestamp::TimestampHook).
+//!
+//! The attributes are hints: an executor honors the ones its SPI/DMA supports and uses its
+//! defaults for the others. [`TransferAttributes::new`] keeps all defaults.
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::{
+//!     attributes::AttributedDescriptor, cache::CacheFlags, sequences::SleepCycle,
+//! };
+//!
+//! static SLEEP_CYCLE: SleepCycle = SleepCycle::new(true);
+//! let [sleep, wake] = SLEEP_CYCLE.steps();
+//!
+//! // Keep NSS low before clocking the wake-up command, then wait for the device to start
+//! let wake = AttributedDescriptor::new(
+//!     wake.descriptor,
+//!     wake.attributes.with_cs_setup_ns(100_000),
+//! );
+//! assert_eq!(wake.attributes.post_delay_us, SleepCycle::WAKE_WARM_US);
+//! assert!(!wake.attributes.is_default());
+//!
+//! let sleep = AttributedDescriptor::new(
+//!     sleep.descriptor,
+//!     sleep.attributes.with_cache(CacheFlags::new().with_clean_tx(true)),
+//! );
+//! assert_eq!(sleep.attributes.clock_divider, None);
+//! ```
+
+use super::cache::CacheFlags;
+use super::commands::SpiDescriptor;
+
+/// NSS timing, delay, SPI clock and cache maintenance of a transfer. A zero time, `None` or
+/// empty [`CacheFlags`] keeps the executor's default.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransferAttributes {
+    /// Time from NSS falling to the first SCK edge in nanoseconds.
+    pub cs_setup_ns: u32,
+    /// Time from the last SCK edge to NSS rising in nanoseconds.
+    pub cs_hold_ns: u32,
+    /// Time after NSS rising before the next transfer in microseconds.
+    pub post_delay_us: u32,
+    /// Divider of the SPI kernel clock, e.g. 8 for `BR` = `Div8` on STM32.
+    pub clock_divider: Option<u8>,
+    /// Cache maintenance of the buffers, see [`cache`](super::cache).
+    pub cache: CacheFlags,
+}
+impl TransferAttributes {
+    /// Executor defaults.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            cs_setup_ns: 0,
+            cs_hold_ns: 0,
+            post_delay_us: 0,
+            clock_divider: None,
+            cache: CacheFlags::new(),
+        }
+    }
+    #[inline]
+    pub const fn with_cs_setup_ns(mut self, ns: u32) -> Self {
+        self.cs_setup_ns = ns;
+        self
+    }
+    #[inline]
+    pub const fn with_cs_hold_ns(mut self, ns: u32) -> Self {
+        self.cs_hold_ns = ns;
+        self
+    }
+    #[inline]
+    pub const fn with_post_delay_us(mut self, us: u32) -> Self {
+        self.post_delay_us = us;
+        self
+    }
+    #[inline]
+    pub const fn with_clock_divider(mut self, divider: u8) -> Self {
+        self.clock_divider = Some(divider);
+        self
+    }
+    #[inline]
+    pub const fn with_cache(mut self, flags: CacheFlags) -> Self {
+        self.cache = flags;
+        self
+    }
+    /// Whether all attributes keep the executor's defaults, so the transfer needs no special
+    /// handling.
+    #[inline]
+    pub const fn is_default(&self) -> bool {
+        self.cs_setup_ns == 0
+            && self.cs_hold_ns == 0
+            && self.post_delay_us == 0
+            && self.clock_divider.is_none()
+            && self.cache.into_bits() == 0
+    }
+}
+impl Default for TransferAttributes {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A descriptor with the metadata `A` of its transfer, by default its [`TransferAttributes`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AttributedDescriptor<A = TransferAttributes> {
+    pub descriptor: SpiDescriptor,
+    pub attributes: A,
+}
+impl<A> AttributedDescriptor<A> {
+    #[inline]
+    pub const fn new(descriptor: SpiDescriptor, attributes: A) -> Self {
+        Self {
+            descriptor,
+            attributes,
+        }
+    }
+}
+impl<A: Default> From<SpiDescriptor> for AttributedDescriptor<A> {
+    /// The descriptor with the default metadata, the executor defaults for
+    /// [`TransferAttributes`].
+    #[inline]
+    fn from(descriptor: SpiDescriptor) -> Self {
+        Self::new(descriptor, A::default())
+    }
+}
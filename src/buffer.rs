@@ -0,0 +1,377 @@
+//! Allocation of the 256 byte radio data buffer.
+#![allow(clippy::new_without_default)]
+
+use super::commands::{
+    CapacityExceeded, SetBufferBaseAddress, SpiDescriptor, WriteBuffer, transfer_length,
+};
+
+/// A contiguous region of the data buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BufferRegion {
+    pub offset: u8,
+    pub len: u16,
+}
+
+/// A region overlapping one that is already allocated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BufferOverlap {
+    pub region: BufferRegion,
+}
+
+/// A region that can't be reserved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReserveError {
+    Overlap(BufferOverlap),
+    /// The region extends past the end of the data buffer.
+    OutOfBounds(BufferRegion),
+}
+
+/// # BufferAllocator
+/// Hands out non-overlapping regions of the data buffer for TX payloads and RX base addresses.
+/// Regions don't wrap around the end of the buffer.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::buffer::{BufferAllocator, BufferOverlap, BufferRegion, ReserveError};
+///
+/// let mut allocator: BufferAllocator = BufferAllocator::new();
+/// let tx = allocator.alloc(64).unwrap();
+/// let rx = allocator.alloc(128).unwrap();
+/// assert_eq!(tx, BufferRegion { offset: 0, len: 64 });
+/// assert_eq!(rx, BufferRegion { offset: 64, len: 128 });
+/// assert_eq!(allocator.alloc(65), None);
+///
+/// let beacon = BufferRegion { offset: 160, len: 16 };
+/// assert_eq!(
+///     allocator.reserve(beacon),
+///     Err(ReserveError::Overlap(BufferOverlap { region: beacon }))
+/// );
+///
+/// assert_eq!(BufferAllocator::set_buffer_base_address(tx, rx).tx_buf, [0x8F, 0, 64]);
+///
+/// allocator.free(tx);
+/// assert_eq!(allocator.alloc(32), Some(BufferRegion { offset: 0, len: 32 }));
+/// ```
+pub struct BufferAllocator {
+    used: [u32; 8],
+}
+impl BufferAllocator {
+    /// Size of the data buffer in bytes.
+    pub const SIZE: u16 = 256;
+
+    #[inline]
+    pub const fn new() -> Self {
+        Self { used: [0; 8] }
+    }
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    const fn is_used(&self, address: usize) -> bool {
+        self.used[address / 32] & (1 << (address % 32)) != 0
+    }
+    /// End of `region`, or `None` if it extends past the end of the buffer.
+    #[inline]
+    const fn end(region: BufferRegion) -> Option<usize> {
+        match (region.offset as u16).checked_add(region.len) {
+            Some(end) if end <= Self::SIZE => Some(end as usize),
+            _ => None,
+        }
+    }
+    const fn check(&self, region: BufferRegion) -> Result<(), ReserveError> {
+        let Some(end) = Self::end(region) else {
+            return Err(ReserveError::OutOfBounds(region));
+        };
+        let mut address = region.offset as usize;
+        while address < end {
+            if self.is_used(address) {
+                return Err(ReserveError::Overlap(BufferOverlap { region }));
+            }
+            address += 1;
+        }
+        Ok(())
+    }
+    /// Marks the part of `region` inside the buffer.
+    #[allow(clippy::indexing_slicing)]
+    const fn mark(&mut self, region: BufferRegion, used: bool) {
+        let end = match (region.offset as usize).checked_add(region.len as usize) {
+            Some(end) if end <= Self::SIZE as usize => end,
+            _ => Self::SIZE as usize,
+        };
+        let mut address = region.offset as usize;
+        while address < end {
+            if used {
+                self.used[address / 32] |= 1 << (address % 32);
+            } else {
+                self.used[address / 32] &= !(1 << (address % 32));
+            }
+            address += 1;
+        }
+    }
+    /// Allocates the first free region of `len` bytes.
+    pub const fn alloc(&mut self, len: u16) -> Option<BufferRegion> {
+        let mut offset: u16 = 0;
+        while offset + len <= Self::SIZE {
+            let region = BufferRegion {
+                offset: offset as u8,
+                len,
+            };
+            if self.check(region).is_ok() {
+                self.mark(region, true);
+                return Some(region);
+            }
+            offset += 1;
+        }
+        None
+    }
+    /// Allocates a specific region, failing if it overlaps an allocated region or exceeds the
+    /// buffer.
+    pub const fn reserve(&mut self, region: BufferRegion) -> Result<(), ReserveError> {
+        match self.check(region) {
+            Ok(()) => {
+                self.mark(region, true);
+                Ok(())
+            }
+            Err(error) => Err(error),
+        }
+    }
+    pub const fn free(&mut self, region: BufferRegion) {
+        self.mark(region, false);
+    }
+    /// `SetBufferBaseAddress` for a TX and an RX region.
+    #[inline]
+    pub const fn set_buffer_base_address(
+        tx: BufferRegion,
+        rx: BufferRegion,
+    ) -> SetBufferBaseAddress {
+        SetBufferBaseAddress::new(tx.offset, rx.offset)
+    }
+}
+
+/// Payload rejected by [`WriteBufferPool::push`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PushError {
+    /// All slots are queued.
+    Full,
+    /// The payload is longer than a slot.
+    CapacityExceeded(CapacityExceeded),
+}
+
+/// A payload queued in a [`WriteBufferPool`] of `SLOTS` slots. Returned to the pool with
+/// [`WriteBufferPool::release`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct PayloadSlot<const SLOTS: usize> {
+    /// Address of the pool.
+    pool: usize,
+    index: usize,
+}
+impl<const SLOTS: usize> PayloadSlot<SLOTS> {
+    /// Index of the slot in the pool.
+    #[inline]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+}
+
+/// # WriteBufferPool
+/// `SLOTS` write buffers of `SIZE - 2` bytes of payload, each writing to its own region of the
+/// data buffer, so payloads can be queued while earlier ones are transmitted. Slot `i` starts at
+/// offset `i * (SIZE - 2)`.
+///
+/// For each payload, `push()` it, transfer its `write_descriptor()` and, before `SetTx`, the
+/// `set_buffer_base_address()` and the payload length in the packet parameters. `release()` the
+/// [`PayloadSlot`] once `TxDone` is set.
+///
+/// The pool identifies its slots by its address, so it must not move while payloads are
+/// queued, e.g. by being a `static`. Slots of other pools are rejected.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::buffer::{BufferRegion, PushError, WriteBufferPool};
+///
+/// let mut pool: WriteBufferPool<2, 66> = WriteBufferPool::new();
+/// let first = pool.push(b"hello").unwrap();
+/// let second = pool.push(b"world").unwrap();
+/// assert_eq!(pool.push(b"!"), Err(PushError::Full));
+///
+/// assert_eq!(pool.write_descriptor(&second).unwrap().transfer_length, 7);
+/// assert_eq!(pool.region(&second), BufferRegion { offset: 64, len: 64 });
+/// assert_eq!(pool.set_buffer_base_address(&second, 128).tx_buf, [0x8F, 64, 128]);
+/// assert_eq!(pool.payload_length(&second), Some(5));
+///
+/// // TxDone of the first payload
+/// pool.release(first).unwrap();
+/// assert_eq!(pool.push(b"again").unwrap().index(), 0);
+/// ```
+pub struct WriteBufferPool<const SLOTS: usize, const SIZE: usize> {
+    slots: [WriteBuffer<SIZE>; SLOTS],
+    payload_lengths: [u8; SLOTS],
+    queued: [bool; SLOTS],
+}
+impl<const SLOTS: usize, const SIZE: usize> WriteBufferPool<SLOTS, SIZE> {
+    const FITS: () = assert!(
+        SIZE > 2 && SIZE <= 257 && SLOTS * (SIZE - 2) <= BufferAllocator::SIZE as usize,
+        "the slots must fit in the data buffer, with at most 255 bytes each"
+    );
+
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn new() -> Self {
+        let () = Self::FITS;
+        let mut slots = [const { WriteBuffer::with_offset(0) }; SLOTS];
+        let mut index: usize = 0;
+        while index < SLOTS {
+            slots[index] = WriteBuffer::with_offset((index * (SIZE - 2)) as u8);
+            index += 1;
+        }
+        Self {
+            slots,
+            payload_lengths: [0; SLOTS],
+            queued: [false; SLOTS],
+        }
+    }
+    /// Offset of `slot` in the data buffer.
+    #[inline]
+    pub const fn offset(slot: &PayloadSlot<SLOTS>) -> u8 {
+        (slot.index * (SIZE - 2)) as u8
+    }
+    /// Region of the data buffer written by `slot`.
+    #[inline]
+    pub const fn region(&self, slot: &PayloadSlot<SLOTS>) -> BufferRegion {
+        BufferRegion {
+            offset: Self::offset(slot),
+            len: (SIZE - 2) as u16,
+        }
+    }
+    #[inline]
+    fn address(&self) -> usize {
+        core::ptr::from_ref(self).addr()
+    }
+    /// Copies `payload` into the first free slot, returning the slot.
+    #[allow(clippy::indexing_slicing)]
+    pub fn push(&mut self, payload: &[u8]) -> Result<PayloadSlot<SLOTS>, PushError> {
+        if payload.len() + 2 > SIZE {
+            return Err(PushError::CapacityExceeded(CapacityExceeded {
+                len: payload.len(),
+                capacity: SIZE - 2,
+            }));
+        }
+        let mut index: usize = 0;
+        while index < SLOTS && self.queued[index] {
+            index += 1;
+        }
+        if index == SLOTS {
+            return Err(PushError::Full);
+        }
+        let mut i: usize = 0;
+        while i < payload.len() {
+            self.slots[index].tx_buf[i + 2] = payload[i];
+            i += 1;
+        }
+        self.payload_lengths[index] = payload.len() as u8;
+        self.queued[index] = true;
+        Ok(PayloadSlot {
+            pool: self.address(),
+            index,
+        })
+    }
+    /// Frees `slot` once its payload is transmitted, or gives `slot` back if it is not queued in
+    /// this pool.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub fn release(&mut self, slot: PayloadSlot<SLOTS>) -> Result<(), PayloadSlot<SLOTS>> {
+        if !self.is_queued(&slot) {
+            return Err(slot);
+        }
+        self.queued[slot.index] = false;
+        Ok(())
+    }
+    /// Whether `slot` is queued in this pool.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub fn is_queued(&self, slot: &PayloadSlot<SLOTS>) -> bool {
+        slot.pool == self.address() && self.queued[slot.index]
+    }
+    /// Payload length of `slot`, for `SetPacketParams`, or `None` if `slot` is not queued in
+    /// this pool.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub fn payload_length(&self, slot: &PayloadSlot<SLOTS>) -> Option<u8> {
+        self.is_queued(slot)
+            .then(|| self.payload_lengths[slot.index])
+    }
+    /// Descriptor writing only the payload of `slot`, or `None` if `slot` is not queued in this
+    /// pool.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub fn write_descriptor(&self, slot: &PayloadSlot<SLOTS>) -> Option<SpiDescriptor> {
+        let payload_length = self.payload_length(slot)?;
+        Some(SpiDescriptor {
+            transfer_length: transfer_length(payload_length as usize + 2),
+            ..self.slots[slot.index].descriptor()
+        })
+    }
+    /// `SetBufferBaseAddress` transmitting from `slot`, with the RX base address `rx_base`.
+    #[inline]
+    pub const fn set_buffer_base_address(
+        &self,
+        slot: &PayloadSlot<SLOTS>,
+        rx_base: u8,
+    ) -> SetBufferBaseAddress {
+        SetBufferBaseAddress::new(Self::offset(slot), rx_base)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffer_bounds() {
+        let mut allocator: BufferAllocator = BufferAllocator::new();
+        assert_eq!(
+            allocator.alloc(256),
+            Some(BufferRegion {
+                offset: 0,
+                len: 256
+            })
+        );
+        allocator.free(BufferRegion {
+            offset: 0,
+            len: 256,
+        });
+        let end = BufferRegion {
+            offset: 250,
+            len: 7,
+        };
+        assert_eq!(allocator.reserve(end), Err(ReserveError::OutOfBounds(end)));
+        let overflow = BufferRegion {
+            offset: 255,
+            len: u16::MAX,
+        };
+        assert_eq!(
+            allocator.reserve(overflow),
+            Err(ReserveError::OutOfBounds(overflow))
+        );
+        allocator.free(overflow);
+        assert_eq!(allocator.alloc(257), None);
+    }
+
+    #[test]
+    fn test_write_buffer_pool_rejects_foreign_slot() {
+        let mut pool: WriteBufferPool<2, 66> = WriteBufferPool::new();
+        let mut other: WriteBufferPool<2, 66> = WriteBufferPool::new();
+        let _ = pool.push(b"first").unwrap();
+        let _ = other.push(b"first").unwrap();
+        let second = pool.push(b"second").unwrap();
+        let other_second = other.push(b"other").unwrap();
+        assert_eq!(second.index(), other_second.index());
+
+        assert!(!other.is_queued(&second));
+        assert_eq!(other.payload_length(&second), None);
+        assert!(other.write_descriptor(&second).is_none());
+        let second = other.release(second).unwrap_err();
+        assert_eq!(other.payload_length(&other_second), Some(5));
+
+        pool.release(second).unwrap();
+        other.release(other_second).unwrap();
+    }
+}
@@ -0,0 +1,146 @@
+//! Data cache maintenance of the command buffers, for executors on cores with a D-Cache such as
+//! the Cortex-M7 and Cortex-M55, where the DMA does not see the cached buffers.
+//!
+//! The [`cache`](TransferAttributes::cache) attribute of an [`AttributedDescriptor`] carries
+//! [`CacheFlags`] telling which buffer of the descriptor needs maintenance: the TX buffer is cleaned before the transfer so the DMA reads the bytes written
+//! by the CPU, and the RX buffer is invalidated after the transfer so the CPU reads the bytes
+//! written by the DMA. The RX buffer is also cleaned and invalidated before the transfer: a dirty
+//! line of it evicted during the transfer would overwrite the bytes written by the DMA, and a
+//! line fetched during the transfer, e.g. by speculative reads, would hide them until the second
+//! invalidation. The cache operations themselves are done by a [`CacheMaintenance`]
+//! implementation, e.g. with `cortex_m::peripheral::SCB::clean_dcache_by_address`.
+//!
+//! Invalidating a buffer discards the whole cache lines holding it: RX buffers must not share
+//! a cache line with other data, e.g. by aligning the commands to the cache line size.
+//!
+//! [`CacheHook`] does the maintenance of both buffers of every descriptor passed to an executor
+//! through [`TransferHook`].
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::{
+//!     attributes::{AttributedDescriptor, TransferAttributes},
+//!     cache::{CacheFlags, CacheMaintenance},
+//!     commands::GetIrqStatus,
+//! };
+//!
+//! #[derive(Default)]
+//! struct Log {
+//!     cleaned: usize,
+//!     invalidated: usize,
+//! }
+//! impl CacheMaintenance for Log {
+//!     fn clean(&mut self, _addr: *const u8, len: usize) {
+//!         self.cleaned += len;
+//!     }
+//!     fn invalidate(&mut self, _addr: *const u8, len: usize) {
+//!         self.invalidated += len;
+//!     }
+//! }
+//!
+//! let get_irq_status = GetIrqStatus::new();
+//! // The TX buffer is constant, only the response buffer needs maintenance
+//! let descriptor = AttributedDescriptor::new(
+//!     get_irq_status.descriptor(),
+//!     TransferAttributes::new().with_cache(CacheFlags::new().with_invalidate_rx(true)),
+//! );
+//!
+//! let mut cache = Log::default();
+//! descriptor.before_transfer(&mut cache);
+//! descriptor.after_transfer(&mut cache);
+//! assert_eq!((cache.cleaned, cache.invalidated), (4, 8));
+//! ```
+
+use super::attributes::{AttributedDescriptor, TransferAttributes};
+use super::commands::SpiDescriptor;
+use super::timestamp::TransferHook;
+use bitfield_struct::bitfield;
+
+/// Cache maintenance of the buffers of a descriptor.
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+pub struct CacheFlags {
+    /// Clean the TX buffer before the transfer.
+    #[bits(1)]
+    pub clean_tx: bool,
+    /// Clean and invalidate the RX buffer before the transfer and invalidate it after.
+    #[bits(1)]
+    pub invalidate_rx: bool,
+    #[bits(6)]
+    __: u8,
+}
+impl CacheFlags {
+    /// Both buffers need maintenance.
+    pub const ALL: Self = Self::new().with_clean_tx(true).with_invalidate_rx(true);
+}
+
+/// # CacheMaintenance
+/// Data cache operations by address, provided by the user for the target core.
+pub trait CacheMaintenance {
+    /// Writes the cached bytes of `len` bytes at `addr` back to memory.
+    fn clean(&mut self, addr: *const u8, len: usize);
+    /// Discards the cached bytes of `len` bytes at `addr`, so they are read again from memory.
+    fn invalidate(&mut self, addr: *const u8, len: usize);
+    /// Cleans then invalidates `len` bytes at `addr`, e.g. with
+    /// `SCB::clean_invalidate_dcache_by_address`.
+    #[inline]
+    fn clean_invalidate(&mut self, addr: *const u8, len: usize) {
+        self.clean(addr, len);
+        self.invalidate(addr, len);
+    }
+}
+
+impl AttributedDescriptor {
+    /// Cleans the TX buffer if `cache.clean_tx()`, then cleans and invalidates the RX buffer if
+    /// `cache.invalidate_rx()`, so no line of it is written back or stays cached during the
+    /// transfer. Call before starting the DMA.
+    ///
+    /// The RX buffer is cleaned too as it may share cache lines with the TX buffer, e.g. the
+    /// constant opcode of a command whose TX buffer is not cleaned.
+    #[inline]
+    pub fn before_transfer(&self, cache: &mut impl CacheMaintenance) {
+        if self.descriptor.is_empty() {
+            return;
+        }
+        let flags = self.attributes.cache;
+        if flags.clean_tx() {
+            cache.clean(self.descriptor.tx_buf_ptr, self.descriptor.len());
+        }
+        if flags.invalidate_rx() {
+            cache.clean_invalidate(self.descriptor.rx_buf_ptr, self.descriptor.len());
+        }
+    }
+    /// Invalidates the RX buffer if `cache.invalidate_rx()`. Call once the transfer has
+    /// completed, before reading the response.
+    #[inline]
+    pub fn after_transfer(&self, cache: &mut impl CacheMaintenance) {
+        if self.attributes.cache.invalidate_rx() && !self.descriptor.is_empty() {
+            cache.invalidate(self.descriptor.rx_buf_ptr, self.descriptor.len());
+        }
+    }
+}
+
+/// # CacheHook
+/// [`TransferHook`] doing the maintenance of [`CacheFlags::ALL`] for every descriptor: cleaning
+/// the TX buffer and cleaning and invalidating the RX buffer at submission, invalidating the RX
+/// buffer again at completion.
+pub struct CacheHook<C>(pub C);
+impl<C> CacheHook<C> {
+    #[inline]
+    const fn attributed(descriptor: &SpiDescriptor) -> AttributedDescriptor {
+        AttributedDescriptor::new(
+            *descriptor,
+            TransferAttributes::new().with_cache(CacheFlags::ALL),
+        )
+    }
+}
+impl<C: CacheMaintenance> TransferHook for CacheHook<C> {
+    #[inline]
+    fn submitted(&mut self, descriptor: &SpiDescriptor) {
+        Self::attributed(descriptor).before_transfer(&mut self.0);
+    }
+    #[inline]
+    fn completed(&mut self, descriptor: &SpiDescriptor) {
+        Self::attributed(descriptor).after_transfer(&mut self.0);
+    }
+}
@@ -0,0 +1,241 @@
+//! Channel tables for frequency hopping and multi-channel operation.
+
+use super::commands::{
+    ClearIrqStatus, GetIrqStatus, GetRssiInst, Irq, SetCad, SetRfFrequency, SetRx, SetStandby,
+    SpiDescriptor, StdbyConfig,
+};
+
+/// # ChannelPlan
+/// `SetRfFrequency` commands for a list of channels, computed at compile time.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::channels::ChannelPlan;
+///
+/// static EU868: ChannelPlan<3> = ChannelPlan::new([868_100_000, 868_300_000, 868_500_000]);
+/// assert_eq!(EU868.len(), 3);
+/// assert_eq!(EU868.channels[0].tx_buf, [0x86, 0x36, 0x41, 0x99, 0x99]);
+/// assert_eq!(EU868.descriptor(2).transfer_length, 5);
+/// assert!(EU868.try_descriptor(3).is_none());
+/// ```
+pub struct ChannelPlan<const N: usize> {
+    pub channels: [SetRfFrequency; N],
+}
+impl<const N: usize> ChannelPlan<N> {
+    /// Channel plan from RF frequencies in Hz.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn new(freqs_hz: [u32; N]) -> Self {
+        let mut channels = [const { SetRfFrequency::new(0) }; N];
+        let mut i: usize = 0;
+        while i < N {
+            channels[i] = SetRfFrequency::from_hz(freqs_hz[i]);
+            i += 1;
+        }
+        Self { channels }
+    }
+    /// Number of channels.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        N
+    }
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+    /// Descriptor of the `SetRfFrequency` command for `channel`.
+    ///
+    /// ## Panics
+    /// If `channel` is not less than `N`.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn descriptor(&self, channel: usize) -> SpiDescriptor {
+        self.channels[channel].descriptor()
+    }
+    /// Descriptor of the `SetRfFrequency` command for `channel`, or `None` if `channel` is not
+    /// less than `N`.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn try_descriptor(&self, channel: usize) -> Option<SpiDescriptor> {
+        if channel < N {
+            Some(self.channels[channel].descriptor())
+        } else {
+            None
+        }
+    }
+}
+
+/// # CadScan
+/// Runs CAD on each channel of a [`ChannelPlan`] and records which channels are busy.
+/// The CAD parameters must be set and `CadDone` enabled beforehand. For each channel:
+/// 1. Transfer `start_descriptors()` from standby - tunes to the channel and starts CAD.
+/// 2. On `CadDone`, transfer `result_descriptors()` and call `update()`.
+///
+/// Once all channels have been scanned, `start_descriptors()` returns `None` until `reset()`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::channels::{CadScan, ChannelPlan};
+///
+/// static PLAN: ChannelPlan<2> = ChannelPlan::new([868_100_000, 868_300_000]);
+/// let mut activity = [false; 2];
+/// let mut scan = CadScan::new(&PLAN);
+/// assert_eq!(scan.start_descriptors().unwrap()[1].transfer_length, 1);
+///
+/// scan.get_irq_status.rx_buf[3] = 0x80;
+/// assert!(scan.update(&mut activity));
+/// assert_eq!(scan.channel(), 1);
+/// scan.get_irq_status.rx_buf[2..4].copy_from_slice(&[0x01, 0x80]);
+/// assert!(!scan.update(&mut activity));
+/// assert_eq!(activity, [false, true]);
+/// assert!(scan.start_descriptors().is_none());
+///
+/// scan.reset();
+/// assert_eq!(scan.channel(), 0);
+/// ```
+pub struct CadScan<'a, const N: usize> {
+    plan: &'a ChannelPlan<N>,
+    pub set_cad: SetCad,
+    pub get_irq_status: GetIrqStatus,
+    pub clear_irq_status: ClearIrqStatus,
+    channel: usize,
+}
+impl<'a, const N: usize> CadScan<'a, N> {
+    #[inline]
+    pub const fn new(plan: &'a ChannelPlan<N>) -> Self {
+        Self {
+            plan,
+            set_cad: SetCad::new(),
+            get_irq_status: GetIrqStatus::new(),
+            clear_irq_status: ClearIrqStatus::new(
+                Irq::new().with_cad_done(true).with_cad_detected(true),
+            ),
+            channel: 0,
+        }
+    }
+    /// Channel currently being scanned.
+    #[inline]
+    pub const fn channel(&self) -> usize {
+        self.channel
+    }
+    /// Restarts the scan from the first channel.
+    #[inline]
+    pub const fn reset(&mut self) {
+        self.channel = 0;
+    }
+    /// Descriptors for `SetRfFrequency` and `SetCad`, or `None` once all channels have been
+    /// scanned.
+    #[inline]
+    pub const fn start_descriptors(&self) -> Option<[SpiDescriptor; 2]> {
+        match self.plan.try_descriptor(self.channel) {
+            Some(set_rf_frequency) => Some([set_rf_frequency, self.set_cad.descriptor()]),
+            None => None,
+        }
+    }
+    /// Descriptors for `GetIrqStatus` and `ClearIrqStatus`.
+    #[inline]
+    pub const fn result_descriptors(&self) -> [SpiDescriptor; 2] {
+        [
+            self.get_irq_status.descriptor(),
+            self.clear_irq_status.descriptor(),
+        ]
+    }
+    /// Records whether activity was detected on the current channel and moves to the next one.
+    /// Returns `false` once all channels have been scanned, without recording further results.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn update(&mut self, activity: &mut [bool; N]) -> bool {
+        if self.channel >= N {
+            return false;
+        }
+        activity[self.channel] = self.get_irq_status.irq_status().cad_detected();
+        self.channel += 1;
+        self.channel < N
+    }
+}
+
+/// # RssiScan
+/// Measures the instantaneous RSSI on each channel of a [`ChannelPlan`]. For each channel:
+/// 1. Transfer `start_descriptors()` from standby - tunes to the channel and enters continuous
+///    RX.
+/// 2. After the RSSI has settled, transfer `result_descriptors()` and call `update()`.
+///
+/// Once all channels have been scanned, `start_descriptors()` returns `None` until `reset()`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::channels::{ChannelPlan, RssiScan};
+///
+/// static PLAN: ChannelPlan<2> = ChannelPlan::new([868_100_000, 868_300_000]);
+/// let mut rssi = [0; 2];
+/// let mut scan = RssiScan::new(&PLAN);
+/// assert_eq!(scan.set_rx.tx_buf, [0x82, 0xFF, 0xFF, 0xFF]);
+///
+/// scan.get_rssi_inst.rx_buf[2] = 240;
+/// assert!(scan.update(&mut rssi));
+/// scan.get_rssi_inst.rx_buf[2] = 180;
+/// assert!(!scan.update(&mut rssi));
+/// assert_eq!(rssi, [-120, -90]);
+/// assert!(scan.start_descriptors().is_none());
+///
+/// scan.reset();
+/// assert!(scan.start_descriptors().is_some());
+/// ```
+pub struct RssiScan<'a, const N: usize> {
+    plan: &'a ChannelPlan<N>,
+    pub set_rx: SetRx,
+    pub get_rssi_inst: GetRssiInst,
+    pub set_standby: SetStandby,
+    channel: usize,
+}
+impl<'a, const N: usize> RssiScan<'a, N> {
+    #[inline]
+    pub const fn new(plan: &'a ChannelPlan<N>) -> Self {
+        Self {
+            plan,
+            set_rx: SetRx::new(0xFF_FFFF),
+            get_rssi_inst: GetRssiInst::new(),
+            set_standby: SetStandby::new(StdbyConfig::StdbyRc),
+            channel: 0,
+        }
+    }
+    /// Channel currently being scanned.
+    #[inline]
+    pub const fn channel(&self) -> usize {
+        self.channel
+    }
+    /// Restarts the scan from the first channel.
+    #[inline]
+    pub const fn reset(&mut self) {
+        self.channel = 0;
+    }
+    /// Descriptors for `SetRfFrequency` and `SetRx`, or `None` once all channels have been
+    /// scanned.
+    #[inline]
+    pub const fn start_descriptors(&self) -> Option<[SpiDescriptor; 2]> {
+        match self.plan.try_descriptor(self.channel) {
+            Some(set_rf_frequency) => Some([set_rf_frequency, self.set_rx.descriptor()]),
+            None => None,
+        }
+    }
+    /// Descriptors for `GetRssiInst` and `SetStandby`.
+    #[inline]
+    pub const fn result_descriptors(&self) -> [SpiDescriptor; 2] {
+        [
+            self.get_rssi_inst.descriptor(),
+            self.set_standby.descriptor(),
+        ]
+    }
+    /// Records the RSSI of the current channel in dBm and moves to the next one.
+    /// Returns `false` once all channels have been scanned, without recording further results.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn update(&mut self, rssi: &mut [i8; N]) -> bool {
+        if self.channel >= N {
+            return false;
+        }
+        rssi[self.channel] = self.get_rssi_inst.rssi_inst();
+        self.channel += 1;
+        self.channel < N
+    }
+}
@@ -1,16 +1,240 @@
 //! Generate SPI buffers for Semtech SX126x SPI commands.
 #![allow(clippy::new_without_default)]
 
-use super::registers::Register;
+use super::DEFAULT_DEVICE;
+use super::registers::{
+    FreqErrorIndicator0, RandomNumberGen0, Register, RegisterBlock, RegisterValue,
+};
+use super::timing;
 use bitfield_struct::bitfield;
 use core::marker::PhantomData;
 
+#[cfg(all(feature = "transfer-length-u8", feature = "transfer-length-usize"))]
+compile_error!(
+    "only one of the `transfer-length-u8` and `transfer-length-usize` features can be enabled"
+);
+/// Type of [`SpiDescriptor::transfer_length`], matching the width of the DMA transfer count.
+/// `u16` by default, `usize` with the `transfer-length-usize` feature and `u8` with the
+/// `transfer-length-u8` feature.
+///
+/// The features change a public type, so they are not additive: only the final binary should
+/// enable one, and enabling both fails to compile.
+#[cfg(not(any(feature = "transfer-length-u8", feature = "transfer-length-usize")))]
+pub type TransferLength = u16;
+/// Type of [`SpiDescriptor::transfer_length`], `u8` with the `transfer-length-u8` feature.
+#[cfg(all(feature = "transfer-length-u8", not(feature = "transfer-length-usize")))]
+pub type TransferLength = u8;
+/// Type of [`SpiDescriptor::transfer_length`], `usize` with the `transfer-length-usize` feature.
+#[cfg(feature = "transfer-length-usize")]
+pub type TransferLength = usize;
+
+/// `len` as a [`TransferLength`].
+///
+/// ## Panics
+/// If `len` does not fit, e.g. a `ReadBuffer<258>` with `transfer-length-u8`.
+#[inline]
+pub(crate) const fn transfer_length(len: usize) -> TransferLength {
+    assert!(
+        len as u64 <= TransferLength::MAX as u64,
+        "transfer length exceeds TransferLength"
+    );
+    len as TransferLength
+}
+
 /// A descriptor for an SPI transfer - contains TX and RX buffer pointers and transfer length.
+/// `#[repr(C)]` with the `ffi` feature.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "ffi", repr(C))]
 pub struct SpiDescriptor {
     pub tx_buf_ptr: *const u8,
     pub rx_buf_ptr: *const u8,
-    pub transfer_length: u16,
+    pub transfer_length: TransferLength,
+}
+// SAFETY: a descriptor only carries the buffer pointers, which are dereferenced by the DMA or in
+// unsafe code of the user, so it can be handed to an interrupt or another thread.
+unsafe impl Send for SpiDescriptor {}
+impl SpiDescriptor {
+    /// An empty transfer, used as a placeholder.
+    pub const EMPTY: Self = Self {
+        tx_buf_ptr: core::ptr::null(),
+        rx_buf_ptr: core::ptr::null(),
+        transfer_length: 0,
+    };
+
+    /// Transfer length in bytes as a `usize`, whatever the [`TransferLength`] type.
+    #[allow(clippy::unnecessary_cast)]
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.transfer_length as usize
+    }
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.transfer_length == 0
+    }
+    /// Buffer addresses and transfer length as plain integers, for programming DMA registers or
+    /// hardware linked-list items. Addresses are truncated to 32 bits, the address width of the
+    /// DMA controllers of 32-bit MCUs.
+    ///
+    /// ## Safety
+    /// Converting is safe, but the DMA writing to `dst` and reading from `src` bypasses the
+    /// borrow checker: until the transfer has completed, the buffers must stay at their address,
+    /// e.g. by being `static`, the RX buffer must not be accessed and the TX buffer not written.
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::commands::GetStatus;
+    ///
+    /// let get_status = GetStatus::new();
+    /// let raw = get_status.descriptor().raw();
+    /// assert_eq!(raw.src, get_status.tx_buf.as_ptr() as usize as u32);
+    /// assert_eq!(raw.dst, get_status.rx_buf.as_ptr() as usize as u32);
+    /// assert_eq!(raw.len, 2);
+    /// ```
+    #[inline]
+    pub fn raw(&self) -> RawDescriptor {
+        RawDescriptor {
+            src: self.tx_buf_ptr.expose_provenance() as u32,
+            dst: self.rx_buf_ptr.expose_provenance() as u32,
+            len: self.len() as u32,
+        }
+    }
+}
+
+/// [`SpiDescriptor`] as plain addresses, see [`SpiDescriptor::raw`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "ffi", repr(C))]
+pub struct RawDescriptor {
+    /// Address of the TX buffer, the source of the transfer.
+    pub src: u32,
+    /// Address of the RX buffer, the destination of the transfer.
+    pub dst: u32,
+    /// Transfer length in bytes.
+    pub len: u32,
+}
+
+/// A command with its TX and RX buffers, for code generic over commands, e.g.
+/// [`Sequence`](super::sequences::Sequence).
+pub trait Command {
+    /// Size of the TX and RX buffers in bytes, the longest transfer of the command.
+    const LEN: usize;
+    /// RAM used by the TX and RX buffers in bytes.
+    const FOOTPRINT: usize = 2 * Self::LEN;
+    fn descriptor(&self) -> SpiDescriptor;
+    /// Like `descriptor()`, with the buffer pointers derived from a mutable borrow of the
+    /// command, so that Rust code may write the RX buffer through them, e.g. the `mock`
+    /// executor. Writing through the pointers of `descriptor()` is undefined behavior, which
+    /// Miri reports.
+    fn descriptor_mut(&mut self) -> SpiDescriptor
+    where
+        Self: Sized,
+    {
+        let descriptor = self.descriptor();
+        let base = (self as *mut Self).cast::<u8>();
+        // The buffers are fields of the command, so the offsets stay within `*self`
+        let offset = |ptr: *const u8| ptr as usize - base as usize;
+        SpiDescriptor {
+            tx_buf_ptr: base.wrapping_add(offset(descriptor.tx_buf_ptr)),
+            rx_buf_ptr: base.wrapping_add(offset(descriptor.rx_buf_ptr)),
+            ..descriptor
+        }
+    }
+}
+macro_rules! impl_command {
+    ($($command:ident = $len:literal),+ $(,)?) => {$(
+        impl Command for $command {
+            const LEN: usize = $len;
+            #[inline]
+            fn descriptor(&self) -> SpiDescriptor {
+                Self::descriptor(self)
+            }
+        }
+    )+};
+}
+impl_command!(
+    SetSleep = 2,
+    SetStandby = 2,
+    SetFs = 1,
+    SetTx = 4,
+    SetRx = 4,
+    StopTimerOnPreamble = 2,
+    SetRxDutyCycle = 7,
+    SetCad = 1,
+    SetTxContinuousWave = 1,
+    SetTxInfinitePreamble = 1,
+    SetRegulatorMode = 2,
+    Calibrate = 2,
+    CalibrateImage = 3,
+    SetPaConfig = 5,
+    SetRxTxFallbackMode = 2,
+    WriteRegister = 4,
+    ReadRandomNumber = 8,
+    ReadFrequencyError = 7,
+    SetDioIrqParams = 9,
+    GetIrqStatus = 4,
+    ClearIrqStatus = 3,
+    SetDio2AsRfSwitchCtrl = 2,
+    SetDio3AsTcxoCtrl = 5,
+    SetRfFrequency = 5,
+    SetPacketType = 2,
+    GetPacketType = 3,
+    SetTxParams = 3,
+    SetModulationParamsLora = 5,
+    SetModulationParamsGfsk = 9,
+    SetPacketParams = 7,
+    SetPacketParamsGfsk = 10,
+    SetCadParams = 8,
+    SetBufferBaseAddress = 3,
+    SetLoraSymbNumTimeout = 2,
+    GetStatus = 2,
+    GetRssiInst = 3,
+    GetRxBufferStatus = 4,
+    GetPacketStatusLora = 5,
+    GetPacketStatusGfsk = 5,
+    ResetStats = 7,
+    GetDeviceErrors = 4,
+    ClearDeviceErrors = 3,
+);
+impl<const N: usize> Command for WriteRegisters<N> {
+    const LEN: usize = N;
+    #[inline]
+    fn descriptor(&self) -> SpiDescriptor {
+        Self::descriptor(self)
+    }
+}
+impl<R: Register> Command for ReadRegister<R> {
+    const LEN: usize = 5;
+    #[inline]
+    fn descriptor(&self) -> SpiDescriptor {
+        Self::descriptor(self)
+    }
+}
+impl<const N: usize> Command for ReadRegisters<N> {
+    const LEN: usize = N;
+    #[inline]
+    fn descriptor(&self) -> SpiDescriptor {
+        Self::descriptor(self)
+    }
+}
+impl<const N: usize> Command for WriteBuffer<N> {
+    const LEN: usize = N;
+    #[inline]
+    fn descriptor(&self) -> SpiDescriptor {
+        Self::descriptor(self)
+    }
+}
+impl<const N: usize> Command for ReadBuffer<N> {
+    const LEN: usize = N;
+    #[inline]
+    fn descriptor(&self) -> SpiDescriptor {
+        Self::descriptor(self)
+    }
+}
+impl<P> Command for GetStats<P> {
+    const LEN: usize = 8;
+    #[inline]
+    fn descriptor(&self) -> SpiDescriptor {
+        Self::descriptor(self)
+    }
 }
 
 /// # SetSleep command
@@ -519,12 +743,20 @@ pub struct CalibrationSetting {
 ///
 /// ## Example
 /// ```
-/// use sx126x_spi_buffers::commands::CalibrateImage;
+/// use sx126x_spi_buffers::commands::{CalibrateImage, ImageCalibrationBand};
 ///
 /// const CALIBRATE_IMAGE: CalibrateImage = CalibrateImage::new(0xC4, 0x85);
 /// assert_eq!(CALIBRATE_IMAGE.tx_buf, [0x98, 0xC4, 0x85]);
 /// assert_eq!(CALIBRATE_IMAGE.rx_buf, [0; 3]);
 /// assert_eq!(CALIBRATE_IMAGE.descriptor().transfer_length, 3);
+///
+/// let calibrate_image = CalibrateImage::for_band(ImageCalibrationBand::Mhz863_870);
+/// assert_eq!(calibrate_image.tx_buf, [0x98, 0xD7, 0xDB]);
+///
+/// let band = ImageCalibrationBand::from_hz(915_000_000).unwrap();
+/// assert_eq!(band.freqs(), [0xE1, 0xE9]);
+/// assert_eq!(ImageCalibrationBand::from_rf_freq(0x3640_0000), Some(ImageCalibrationBand::Mhz863_870));
+/// assert_eq!(ImageCalibrationBand::from_hz(2_400_000_000), None);
 /// ```
 pub struct CalibrateImage {
     pub tx_buf: [u8; 3],
@@ -540,6 +772,12 @@ impl CalibrateImage {
             rx_buf: [0; 3],
         }
     }
+    /// Calibrates the image rejection for `band`.
+    #[inline]
+    pub const fn for_band(band: ImageCalibrationBand) -> Self {
+        let [freq1, freq2] = band.freqs();
+        Self::new(freq1, freq2)
+    }
     #[inline]
     pub const fn descriptor(&self) -> SpiDescriptor {
         SpiDescriptor {
@@ -549,20 +787,76 @@ impl CalibrateImage {
         }
     }
 }
+/// Frequency bands of the `CalibrateImage` command (datasheet table 9-2).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageCalibrationBand {
+    Mhz430_440,
+    Mhz470_510,
+    Mhz779_787,
+    Mhz863_870,
+    Mhz902_928,
+}
+impl ImageCalibrationBand {
+    /// `freq1` and `freq2` parameters of `CalibrateImage`.
+    #[inline]
+    pub const fn freqs(self) -> [u8; 2] {
+        match self {
+            ImageCalibrationBand::Mhz430_440 => [0x6B, 0x6F],
+            ImageCalibrationBand::Mhz470_510 => [0x75, 0x81],
+            ImageCalibrationBand::Mhz779_787 => [0xC1, 0xC5],
+            ImageCalibrationBand::Mhz863_870 => [0xD7, 0xDB],
+            ImageCalibrationBand::Mhz902_928 => [0xE1, 0xE9],
+        }
+    }
+    /// Band containing the RF frequency `freq_hz`, or `None` outside the bands of the datasheet.
+    #[inline]
+    pub const fn from_hz(freq_hz: u32) -> Option<Self> {
+        match freq_hz {
+            430_000_000..=440_000_000 => Some(ImageCalibrationBand::Mhz430_440),
+            470_000_000..=510_000_000 => Some(ImageCalibrationBand::Mhz470_510),
+            779_000_000..=787_000_000 => Some(ImageCalibrationBand::Mhz779_787),
+            863_000_000..=870_000_000 => Some(ImageCalibrationBand::Mhz863_870),
+            902_000_000..=928_000_000 => Some(ImageCalibrationBand::Mhz902_928),
+            _ => None,
+        }
+    }
+    /// Band containing the RF frequency `rf_freq`, in the same units as [`SetRfFrequency`].
+    #[inline]
+    pub const fn from_rf_freq(rf_freq: u32) -> Option<Self> {
+        Self::from_hz(((rf_freq as u64 * 32_000_000) >> 25) as u32)
+    }
+}
 
 /// # SetPaConfig command
 /// Configures the power amplifier settings.
 ///
 /// - `device_sel = 1` only for SX1261.
 ///
+/// `try_new()` checks the parameters against the limits of the
+/// [`DEFAULT_DEVICE`](crate::DEFAULT_DEVICE), and `optimal()` gives the datasheet settings for
+/// the highest efficiency at a given output power. `try_new_for()` and `optimal_for()` do the
+/// same for another [`Device`].
+///
 /// ## Example
 /// ```
-/// use sx126x_spi_buffers::commands::SetPaConfig;
+/// use sx126x_spi_buffers::commands::{Device, InvalidPaConfig, SetPaConfig};
 ///
 /// const SET_PA_CONFIG: SetPaConfig = SetPaConfig::new(0x04, 0x07, 0);
 /// assert_eq!(SET_PA_CONFIG.tx_buf, [0x95, 0x04, 0x07, 0x00, 0x01]);
 /// assert_eq!(SET_PA_CONFIG.rx_buf, [0; 5]);
 /// assert_eq!(SET_PA_CONFIG.descriptor().transfer_length, 5);
+///
+/// let set_pa_config = SetPaConfig::try_new_for(Device::Sx1261, 0x06, 0x00).unwrap();
+/// assert_eq!(set_pa_config.tx_buf, [0x95, 0x06, 0x00, 0x01, 0x01]);
+/// assert_eq!(
+///     SetPaConfig::try_new_for(Device::Sx1262, 0x06, 0x07).err(),
+///     Some(InvalidPaConfig { device: Device::Sx1262, pa_duty_cycle: 0x06, hp_max: 0x07 })
+/// );
+///
+/// let (set_pa_config, tx_power) = SetPaConfig::optimal_for(Device::Sx1262, 17).unwrap();
+/// assert_eq!(set_pa_config.tx_buf, [0x95, 0x02, 0x03, 0x00, 0x01]);
+/// assert_eq!(tx_power, 22);
+/// assert!(SetPaConfig::try_new(0x04, 0x07).is_ok());
 /// ```
 pub struct SetPaConfig {
     pub tx_buf: [u8; 5],
@@ -578,6 +872,57 @@ impl SetPaConfig {
             rx_buf: [0; 5],
         }
     }
+    /// PA config for the [`DEFAULT_DEVICE`](crate::DEFAULT_DEVICE), rejecting a
+    /// `pa_duty_cycle` or `hp_max` that may damage it.
+    #[inline]
+    pub const fn try_new(pa_duty_cycle: u8, hp_max: u8) -> Result<Self, InvalidPaConfig> {
+        Self::try_new_for(DEFAULT_DEVICE, pa_duty_cycle, hp_max)
+    }
+    /// PA config for `device`, rejecting a `pa_duty_cycle` or `hp_max` that may damage it.
+    #[inline]
+    pub const fn try_new_for(
+        device: Device,
+        pa_duty_cycle: u8,
+        hp_max: u8,
+    ) -> Result<Self, InvalidPaConfig> {
+        if pa_duty_cycle > device.max_pa_duty_cycle() || hp_max > 0x07 {
+            Err(InvalidPaConfig {
+                device,
+                pa_duty_cycle,
+                hp_max,
+            })
+        } else {
+            Ok(Self::new(pa_duty_cycle, hp_max, device.device_sel()))
+        }
+    }
+    /// Optimal PA config for an output power of `power` dBm on the
+    /// [`DEFAULT_DEVICE`](crate::DEFAULT_DEVICE), with the power to set
+    /// with `SetTxParams`, per the datasheet. Other output powers are reached by lowering the
+    /// `SetTxParams` power from the next higher setting.
+    ///
+    /// Returns `None` for output powers without an optimal setting.
+    #[inline]
+    pub const fn optimal(power: i8) -> Option<(Self, i8)> {
+        Self::optimal_for(DEFAULT_DEVICE, power)
+    }
+    /// Optimal PA config for an output power of `power` dBm on `device`, see `optimal()`.
+    pub const fn optimal_for(device: Device, power: i8) -> Option<(Self, i8)> {
+        let (pa_duty_cycle, hp_max, tx_power) = match (device, power) {
+            (Device::Sx1261, 15) => (0x06, 0x00, 14),
+            (Device::Sx1261, 14) => (0x04, 0x00, 14),
+            (Device::Sx1261, 10) => (0x01, 0x00, 13),
+            (Device::Sx1262 | Device::Sx1268 | Device::Llcc68, 22) => (0x04, 0x07, 22),
+            (Device::Sx1262 | Device::Sx1268 | Device::Llcc68, 20) => (0x03, 0x05, 22),
+            (Device::Sx1262 | Device::Sx1268 | Device::Llcc68, 17) => (0x02, 0x03, 22),
+            (Device::Sx1268, 14) => (0x04, 0x06, 22),
+            (Device::Sx1262 | Device::Llcc68, 14) => (0x02, 0x02, 22),
+            _ => return None,
+        };
+        Some((
+            Self::new(pa_duty_cycle, hp_max, device.device_sel()),
+            tx_power,
+        ))
+    }
     #[inline]
     pub const fn descriptor(&self) -> SpiDescriptor {
         SpiDescriptor {
@@ -637,10 +982,17 @@ pub enum FallbackMode {
 /// ```
 /// use sx126x_spi_buffers::{registers, commands::{WriteRegister}};
 ///
+/// # #[cfg(feature = "nightly")] {
 /// const WRITE_REGISTER: WriteRegister = WriteRegister::new(registers::LoraSyncWordMsb(0x48));
 /// assert_eq!(WRITE_REGISTER.tx_buf, [0x0D, 0x07, 0x40, 0x48]);
 /// assert_eq!(WRITE_REGISTER.rx_buf, [0; 4]);
 /// assert_eq!(WRITE_REGISTER.descriptor().transfer_length, 4);
+/// # }
+///
+/// // Without the `nightly` feature, typed registers are written at runtime
+/// let write_register = WriteRegister::new(registers::LoraSyncWordMsb(0x48));
+/// const WRITE_AT_ADDRESS: WriteRegister = WriteRegister::at_address(0x0740, 0x48);
+/// assert_eq!(WRITE_AT_ADDRESS.tx_buf, write_register.tx_buf);
 /// ```
 pub struct WriteRegister {
     pub tx_buf: [u8; 4],
@@ -649,14 +1001,36 @@ pub struct WriteRegister {
 impl WriteRegister {
     const OPCODE: u8 = 0x0D;
 
+    maybe_const! {
+        #[inline]
+        pub [const] fn new<R: [const] Register>(register: R) -> Self {
+            Self {
+                tx_buf: [
+                    Self::OPCODE,
+                    (R::ADDRESS >> 8) as u8,
+                    R::ADDRESS as u8,
+                    register.bits(),
+                ],
+                rx_buf: [0; 4],
+            }
+        }
+    }
+    /// Writes `value` to the register at a runtime address.
+    #[inline]
+    pub const fn at_address(address: u16, value: u8) -> Self {
+        Self {
+            tx_buf: [Self::OPCODE, (address >> 8) as u8, address as u8, value],
+            rx_buf: [0; 4],
+        }
+    }
     #[inline]
-    pub const fn new<R: const Register>(register: R) -> Self {
+    pub const fn from_value(value: RegisterValue) -> Self {
         Self {
             tx_buf: [
                 Self::OPCODE,
-                (R::ADDRESS >> 8) as u8,
-                R::ADDRESS as u8,
-                register.bits(),
+                (value.address >> 8) as u8,
+                value.address as u8,
+                value.value,
             ],
             rx_buf: [0; 4],
         }
@@ -682,10 +1056,36 @@ impl WriteRegister {
 /// ```
 /// use sx126x_spi_buffers::{registers, commands::{WriteRegisters}};
 ///
+/// # #[cfg(feature = "nightly")] {
 /// const WRITE_REGISTERS: WriteRegisters<5> = WriteRegisters::<5>::new::<registers::LoraSyncWordMsb>([0x67, 0x98]);
 /// assert_eq!(WRITE_REGISTERS.tx_buf, [0x0D, 0x07, 0x40, 0x67, 0x98]);
 /// assert_eq!(WRITE_REGISTERS.rx_buf, [0; 5]);
 /// assert_eq!(WRITE_REGISTERS.descriptor().transfer_length, 5);
+///
+/// const WRITE_SYNC_WORD: WriteRegisters<5> = WriteRegisters::<5>::from_block(registers::LoraSyncWord(0x3444));
+/// assert_eq!(WRITE_SYNC_WORD.tx_buf, [0x0D, 0x07, 0x40, 0x34, 0x44]);
+///
+/// const WRITE_RETENTION: WriteRegisters<5> = WriteRegisters::<5>::from_block((
+///     registers::RxGainRetention1(0x08),
+///     registers::RxGainRetention2(0xAC),
+/// ));
+/// assert_eq!(WRITE_RETENTION.tx_buf, [0x0D, 0x02, 0xA0, 0x08, 0xAC]);
+/// # }
+///
+/// // Without the `nightly` feature
+/// let write_registers: WriteRegisters<5> =
+///     WriteRegisters::<5>::from_slice::<registers::LoraSyncWordMsb>(&[0x67, 0x98]);
+/// assert_eq!(write_registers.tx_buf, [0x0D, 0x07, 0x40, 0x67, 0x98]);
+/// ```
+///
+/// Registers written as a tuple must be at contiguous addresses:
+/// ```compile_fail
+/// use sx126x_spi_buffers::{registers, commands::{WriteRegisters}};
+///
+/// const WRITE_REGISTERS: WriteRegisters<5> = WriteRegisters::<5>::from_block((
+///     registers::RxGainRetention0(0x01),
+///     registers::RxGainRetention2(0xAC),
+/// ));
 /// ```
 pub struct WriteRegisters<const N: usize> {
     pub tx_buf: [u8; N],
@@ -694,12 +1094,65 @@ pub struct WriteRegisters<const N: usize> {
 impl<const N: usize> WriteRegisters<N> {
     const OPCODE: u8 = 0x0D;
 
+    #[cfg(feature = "nightly")]
+    #[inline]
+    pub const fn new<R: Register>(data: [u8; N - 3]) -> Self {
+        Self::at_address(R::ADDRESS, &data)
+    }
+    /// Writes `data` to the registers starting at `R`, without `generic_const_exprs`.
+    ///
+    /// ## Panics
+    /// If `data` is not `N - 3` bytes long.
+    #[inline]
+    pub const fn from_slice<R: Register>(data: &[u8]) -> Self {
+        Self::at_address(R::ADDRESS, data)
+    }
+    maybe_const! {
+        /// Writes a multi-byte register block.
+        #[cfg(feature = "nightly")]
+        #[inline]
+        pub [const] fn from_block<B: [const] RegisterBlock<{ N - 3 }>>(block: B) -> Self {
+            Self::at_address_unchecked(B::ADDRESS, &block.bytes())
+        }
+    }
+    /// Writes a multi-byte register block, without `generic_const_exprs`.
+    ///
+    /// ## Panics
+    /// If the block is not `N - 3` bytes long.
+    #[cfg(not(feature = "nightly"))]
+    #[inline]
+    pub fn from_block<const LEN: usize, B: RegisterBlock<LEN>>(block: B) -> Self {
+        Self::at_address(B::ADDRESS, &block.bytes())
+    }
+    /// Writes `data` to the registers starting at a runtime address.
+    ///
+    /// ## Panics
+    /// If `data` is not `N - 3` bytes long.
+    #[inline]
+    pub const fn at_address(address: u16, data: &[u8]) -> Self {
+        assert!(data.len() + 3 == N, "data length must be N - 3");
+        Self::at_address_unchecked(address, data)
+    }
+    /// Writes `data` to the registers starting at a runtime address, rejecting `data` that is
+    /// not `N - 3` bytes long.
+    #[inline]
+    pub const fn try_at_address(address: u16, data: &[u8]) -> Result<Self, InvalidLength> {
+        if data.len() + 3 != N {
+            return Err(InvalidLength {
+                len: data.len(),
+                expected: N.saturating_sub(3),
+            });
+        }
+        Ok(Self::at_address_unchecked(address, data))
+    }
+    /// `data` must be `N - 3` bytes long.
     #[inline]
-    pub const fn new<R: const Register>(data: [u8; N - 3]) -> Self {
+    #[allow(clippy::indexing_slicing)]
+    const fn at_address_unchecked(address: u16, data: &[u8]) -> Self {
         let mut tx_buf = [0; N];
         tx_buf[0] = Self::OPCODE;
-        tx_buf[1] = (R::ADDRESS >> 8) as u8;
-        tx_buf[2] = R::ADDRESS as u8;
+        tx_buf[1] = (address >> 8) as u8;
+        tx_buf[2] = address as u8;
         let mut i: usize = 0;
         while i < N - 3 {
             tx_buf[i + 3] = data[i];
@@ -715,7 +1168,7 @@ impl<const N: usize> WriteRegisters<N> {
         SpiDescriptor {
             tx_buf_ptr: self.tx_buf.as_ptr(),
             rx_buf_ptr: self.rx_buf.as_ptr(),
-            transfer_length: N as u16,
+            transfer_length: const { transfer_length(N) },
         }
     }
 }
@@ -739,7 +1192,7 @@ pub struct ReadRegister<R> {
     pub rx_buf: [u8; 5],
     register: PhantomData<R>,
 }
-impl<R: const Register> ReadRegister<R> {
+impl<R: Register> ReadRegister<R> {
     const OPCODE: u8 = 0x1D;
 
     #[inline]
@@ -764,9 +1217,82 @@ impl<R: const Register> ReadRegister<R> {
             transfer_length: 5,
         }
     }
+    maybe_const! {
+        #[inline]
+        pub [const] fn register(&self) -> R
+        where
+            R: [const] Register,
+        {
+            R::from_bits(self.rx_buf[4])
+        }
+    }
+}
+
+/// # Read-modify-write of a single register
+/// Pairs a `ReadRegister` with a `WriteRegister` for updating some bits of a register while
+/// preserving the others.
+///
+/// 1. Transfer `read_descriptor()`.
+/// 2. Call `update()` (or `update_with()`) once the read has completed.
+/// 3. Transfer `write_descriptor()`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers, commands::Rmw};
+///
+/// let mut rmw: Rmw<registers::EventMask> = Rmw::new(0x00, 0x02);
+/// assert_eq!(rmw.read.tx_buf, [0x1D, 0x09, 0x44, 0, 0]);
+/// assert_eq!(rmw.read_descriptor().transfer_length, 5);
+/// assert_eq!(rmw.write_descriptor().transfer_length, 4);
+///
+/// rmw.read.rx_buf[4] = 0x11;
+/// rmw.update();
+/// assert_eq!(rmw.write.tx_buf, [0x0D, 0x09, 0x44, 0x13]);
+///
+/// let mut rmw: Rmw<registers::LoraSyncWordMsb> = Rmw::new(0, 0);
+/// rmw.read.rx_buf[4] = 0x12;
+/// rmw.update_with(|r| registers::LoraSyncWordMsb(r.0 + 0x22));
+/// assert_eq!(rmw.write.tx_buf, [0x0D, 0x07, 0x40, 0x34]);
+/// ```
+pub struct Rmw<R> {
+    pub read: ReadRegister<R>,
+    pub write: WriteRegister,
+    clear_mask: u8,
+    set_mask: u8,
+}
+impl<R: Register> Rmw<R> {
+    /// Bits in `clear_mask` are cleared, then bits in `set_mask` are set.
+    #[inline]
+    pub const fn new(clear_mask: u8, set_mask: u8) -> Self {
+        Self {
+            read: ReadRegister::new(),
+            write: WriteRegister::at_address(R::ADDRESS, set_mask),
+            clear_mask,
+            set_mask,
+        }
+    }
+    #[inline]
+    pub const fn read_descriptor(&self) -> SpiDescriptor {
+        self.read.descriptor()
+    }
     #[inline]
-    pub const fn register(&self) -> R {
-        R::from_bits(self.rx_buf[4])
+    pub const fn write_descriptor(&self) -> SpiDescriptor {
+        self.write.descriptor()
+    }
+    /// Prepares `write` by applying the masks to the value read by `read`.
+    #[inline]
+    pub const fn update(&mut self) {
+        self.write.tx_buf[3] = (self.read.rx_buf[4] & !self.clear_mask) | self.set_mask;
+    }
+    maybe_const! {
+        /// Prepares `write` by applying `f` to the value read by `read`.
+        #[inline]
+        pub fn update_with(&mut self, f: impl FnOnce(R) -> R)
+        where
+            R: [const] Register,
+        {
+            self.write = WriteRegister::new(f(self.read.register()));
+        }
     }
 }
 
@@ -785,6 +1311,29 @@ impl<R: const Register> ReadRegister<R> {
 /// assert_eq!(read_registers.tx_buf, [0x1D, 0x07, 0x40, 0, 0, 0]);
 /// assert_eq!(read_registers.rx_buf, [0; 6]);
 /// assert_eq!(read_registers.descriptor().transfer_length, 6);
+///
+/// # #[cfg(feature = "nightly")] {
+/// let mut read_sync_word: ReadRegisters<6> = ReadRegisters::<6>::for_block::<registers::LoraSyncWord>();
+/// assert_eq!(read_sync_word.tx_buf, [0x1D, 0x07, 0x40, 0, 0, 0]);
+/// read_sync_word.rx_buf[4..6].copy_from_slice(&[0x14, 0x24]);
+/// assert_eq!(read_sync_word.block::<registers::LoraSyncWord>(), registers::LoraSyncWord(0x1424));
+/// # }
+/// ```
+///
+/// Contiguous registers can be decoded into a tuple of typed registers:
+/// ```
+/// use sx126x_spi_buffers::{registers::{RxGainRetention1, RxGainRetention2}, commands::ReadRegisters};
+///
+/// # #[cfg(feature = "nightly")] {
+/// let mut read_retention: ReadRegisters<6> =
+///     ReadRegisters::<6>::for_block::<(RxGainRetention1, RxGainRetention2)>();
+/// assert_eq!(read_retention.tx_buf, [0x1D, 0x02, 0xA0, 0, 0, 0]);
+///
+/// read_retention.rx_buf[4..6].copy_from_slice(&[0x08, 0xAC]);
+/// let (msb, lsb) = read_retention.block::<(RxGainRetention1, RxGainRetention2)>();
+/// assert_eq!(msb, RxGainRetention1(0x08));
+/// assert_eq!(lsb, RxGainRetention2(0xAC));
+/// # }
 /// ```
 pub struct ReadRegisters<const N: usize> {
     pub tx_buf: [u8; N],
@@ -792,13 +1341,26 @@ pub struct ReadRegisters<const N: usize> {
 }
 impl<const N: usize> ReadRegisters<N> {
     const OPCODE: u8 = 0x1D;
+    const MIN_SIZE: () = assert!(N >= 4, "N must be at least 4");
 
     #[inline]
-    pub const fn new<R: const Register>() -> Self {
+    pub const fn new<R: Register>() -> Self {
+        Self::at_address(R::ADDRESS)
+    }
+    /// Reads a multi-byte register block.
+    #[cfg(feature = "nightly")]
+    #[inline]
+    pub const fn for_block<B: RegisterBlock<{ N - 4 }>>() -> Self {
+        Self::at_address(B::ADDRESS)
+    }
+    /// Reads registers starting at a runtime address.
+    #[inline]
+    pub const fn at_address(address: u16) -> Self {
+        let () = Self::MIN_SIZE;
         let mut tx_buf = [0; N];
         tx_buf[0] = Self::OPCODE;
-        tx_buf[1] = (R::ADDRESS >> 8) as u8;
-        tx_buf[2] = R::ADDRESS as u8;
+        tx_buf[1] = (address >> 8) as u8;
+        tx_buf[2] = address as u8;
         Self {
             tx_buf,
             rx_buf: [0; N],
@@ -809,9 +1371,144 @@ impl<const N: usize> ReadRegisters<N> {
         SpiDescriptor {
             tx_buf_ptr: self.tx_buf.as_ptr(),
             rx_buf_ptr: self.rx_buf.as_ptr(),
-            transfer_length: N as u16,
+            transfer_length: const { transfer_length(N) },
+        }
+    }
+    maybe_const! {
+        /// Decodes a multi-byte register block from the received bytes.
+        #[cfg(feature = "nightly")]
+        #[inline]
+        pub [const] fn block<B: [const] RegisterBlock<{ N - 4 }>>(&self) -> B {
+            B::from_bytes(self.bytes())
+        }
+    }
+    /// Received register values, without `generic_const_exprs`. A `LEN` other than `N - 4` is
+    /// rejected at compile time.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn bytes<const LEN: usize>(&self) -> [u8; LEN] {
+        let () = BytesLen::<LEN, N>::READ_REGISTERS;
+        let mut bytes = [0; LEN];
+        let mut i: usize = 0;
+        while i < LEN {
+            bytes[i] = self.rx_buf[i + 4];
+            i += 1;
+        }
+        bytes
+    }
+}
+
+/// # ReadRandomNumber command
+/// Reads the 32-bit random number from `RandomNumberGen0` to `RandomNumberGen3` in a single
+/// transfer. The random number generator draws its entropy from RX noise, so the device should be
+/// in continuous RX mode while reading.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::ReadRandomNumber;
+///
+/// let mut read_random_number: ReadRandomNumber = ReadRandomNumber::new();
+/// assert_eq!(read_random_number.tx_buf, [0x1D, 0x08, 0x19, 0, 0, 0, 0, 0]);
+/// assert_eq!(read_random_number.rx_buf, [0; 8]);
+/// assert_eq!(read_random_number.descriptor().transfer_length, 8);
+///
+/// read_random_number.rx_buf[4..8].copy_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+/// assert_eq!(read_random_number.random_u32(), 0xDEADBEEF);
+/// ```
+pub struct ReadRandomNumber {
+    pub tx_buf: [u8; 8],
+    pub rx_buf: [u8; 8],
+}
+impl ReadRandomNumber {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            tx_buf: ReadRegisters::<8>::new::<RandomNumberGen0>().tx_buf,
+            rx_buf: [0; 8],
+        }
+    }
+    #[inline]
+    pub const fn descriptor(&self) -> SpiDescriptor {
+        SpiDescriptor {
+            tx_buf_ptr: self.tx_buf.as_ptr(),
+            rx_buf_ptr: self.rx_buf.as_ptr(),
+            transfer_length: 8,
+        }
+    }
+    #[inline]
+    pub const fn random_u32(&self) -> u32 {
+        (self.rx_buf[4] as u32) << 24
+            | (self.rx_buf[5] as u32) << 16
+            | (self.rx_buf[6] as u32) << 8
+            | self.rx_buf[7] as u32
+    }
+}
+
+/// # ReadFrequencyError command
+/// Reads the LoRa frequency error indicator from `FreqErrorIndicator0` to `FreqErrorIndicator2`
+/// in a single transfer. The indicator is a signed 20-bit value, valid after a packet has been
+/// received.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{ReadFrequencyError, Bw};
+///
+/// let mut read_frequency_error: ReadFrequencyError = ReadFrequencyError::new();
+/// assert_eq!(read_frequency_error.tx_buf, [0x1D, 0x07, 0x6B, 0, 0, 0, 0]);
+/// assert_eq!(read_frequency_error.rx_buf, [0; 7]);
+/// assert_eq!(read_frequency_error.descriptor().transfer_length, 7);
+///
+/// read_frequency_error.rx_buf[4..7].copy_from_slice(&[0x0F, 0xF8, 0x30]);
+/// assert_eq!(read_frequency_error.frequency_error(), -2000);
+/// assert_eq!(read_frequency_error.frequency_error_hz(Bw::Bw125), -242);
+/// assert_eq!(read_frequency_error.frequency_error_ppb(Bw::Bw125, 868_000_000), -278);
+/// ```
+pub struct ReadFrequencyError {
+    pub tx_buf: [u8; 7],
+    pub rx_buf: [u8; 7],
+}
+impl ReadFrequencyError {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            tx_buf: ReadRegisters::<7>::new::<FreqErrorIndicator0>().tx_buf,
+            rx_buf: [0; 7],
+        }
+    }
+    #[inline]
+    pub const fn descriptor(&self) -> SpiDescriptor {
+        SpiDescriptor {
+            tx_buf_ptr: self.tx_buf.as_ptr(),
+            rx_buf_ptr: self.rx_buf.as_ptr(),
+            transfer_length: 7,
         }
     }
+    /// Raw sign-extended 20-bit frequency error indicator.
+    #[inline]
+    pub const fn frequency_error(&self) -> i32 {
+        let raw = (self.rx_buf[4] as i32 & 0x0F) << 16
+            | (self.rx_buf[5] as i32) << 8
+            | self.rx_buf[6] as i32;
+        (raw << 12) >> 12
+    }
+    /// Frequency error in Hz for the configured LoRa bandwidth.
+    #[inline]
+    pub const fn frequency_error_hz(&self, bw: Bw) -> i32 {
+        // 1.55 * FEI * BW[kHz] / 1600
+        (self.frequency_error() as i64 * bw.hz() as i64 * 31 / 32_000_000) as i32
+    }
+    /// Frequency error in parts per billion (0.001 ppm) of the RF frequency in Hz.
+    #[inline]
+    pub const fn frequency_error_ppb(&self, bw: Bw, rf_freq_hz: u32) -> i32 {
+        (self.frequency_error_hz(bw) as i64 * 1_000_000_000 / rf_freq_hz as i64) as i32
+    }
+}
+
+/// Length checks between a const generic argument `LEN` of a method and the size `N` of its
+/// buffer, failing the build when the method is instantiated with mismatching sizes.
+struct BytesLen<const LEN: usize, const N: usize>;
+impl<const LEN: usize, const N: usize> BytesLen<LEN, N> {
+    const READ_REGISTERS: () = assert!(LEN + 4 == N, "LEN must be N - 4");
 }
 
 /// # WriteBuffer command
@@ -825,10 +1522,23 @@ impl<const N: usize> ReadRegisters<N> {
 /// ```
 /// use sx126x_spi_buffers::commands::WriteBuffer;
 ///
+/// # #[cfg(feature = "nightly")] {
 /// let mut write_buffer: WriteBuffer<7> = WriteBuffer::new(0x10, [b'h', b'e', b'l', b'l', b'o'].into());
 /// assert_eq!(write_buffer.tx_buf, [0x0E, 0x10, b'h', b'e', b'l', b'l', b'o']);
 /// assert_eq!(write_buffer.rx_buf, [0; 7]);
 /// assert_eq!(write_buffer.descriptor().transfer_length, 7);
+/// # }
+///
+/// let write_buffer: WriteBuffer<7> = WriteBuffer::from_slice(0x10, b"hi");
+/// assert_eq!(write_buffer.tx_buf, [0x0E, 0x10, b'h', b'i', 0, 0, 0]);
+///
+/// const BEACON: WriteBuffer<8> = WriteBuffer::from_str(0x80, "beacon");
+/// assert_eq!(BEACON.tx_buf, [0x0E, 0x80, b'b', b'e', b'a', b'c', b'o', b'n']);
+///
+/// let mut write_buffer: WriteBuffer<7> = WriteBuffer::from_slice(0x10, &[]);
+/// let descriptor = write_buffer.fill(b"hey");
+/// assert_eq!(write_buffer.tx_buf, [0x0E, 0x10, b'h', b'e', b'y', 0, 0]);
+/// assert_eq!(descriptor.transfer_length, 5);
 /// ```
 pub struct WriteBuffer<const N: usize> {
     pub tx_buf: [u8; N],
@@ -836,17 +1546,64 @@ pub struct WriteBuffer<const N: usize> {
 }
 impl<const N: usize> WriteBuffer<N> {
     const OPCODE: u8 = 0x0E;
+    const MIN_SIZE: () = assert!(N >= 2, "N must be at least 2");
 
+    #[cfg(feature = "nightly")]
     #[inline]
     pub const fn new(offset: u8, data: [u8; N - 2]) -> Self {
-        let mut tx_buf = [0; N];
-        tx_buf[0] = Self::OPCODE;
-        tx_buf[1] = offset;
+        Self::from_slice(offset, &data)
+    }
+    /// Writes `data` at `offset`, without `generic_const_exprs`. Data after `data` is zero.
+    ///
+    /// ## Panics
+    /// If `data` is longer than `N - 2` bytes.
+    #[inline]
+    #[allow(clippy::panic)]
+    pub const fn from_slice(offset: u8, data: &[u8]) -> Self {
+        match Self::try_from_slice(offset, data) {
+            Ok(write_buffer) => write_buffer,
+            Err(_) => panic!("data exceeds buffer capacity"),
+        }
+    }
+    /// Writes the UTF-8 bytes of `data` at `offset`, e.g. a beacon or test payload. In a
+    /// `const` or `static` item, `data` longer than `N - 2` bytes fails the build:
+    /// ```compile_fail
+    /// use sx126x_spi_buffers::commands::WriteBuffer;
+    ///
+    /// const BEACON: WriteBuffer<6> = WriteBuffer::from_str(0, "beacon");
+    /// ```
+    ///
+    /// ## Panics
+    /// If `data` is longer than `N - 2` bytes.
+    #[inline]
+    pub const fn from_str(offset: u8, data: &str) -> Self {
+        Self::from_slice(offset, data.as_bytes())
+    }
+    /// Writes `data` at `offset`, rejecting `data` longer than `N - 2` bytes.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn try_from_slice(offset: u8, data: &[u8]) -> Result<Self, CapacityExceeded> {
+        if data.len() + 2 > N {
+            return Err(CapacityExceeded {
+                len: data.len(),
+                capacity: N.saturating_sub(2),
+            });
+        }
+        let mut write_buffer = Self::with_offset(offset);
         let mut i: usize = 0;
-        while i < N - 2 {
-            tx_buf[i + 2] = data[i];
+        while i < data.len() {
+            write_buffer.tx_buf[i + 2] = data[i];
             i += 1;
         }
+        Ok(write_buffer)
+    }
+    /// Zero-filled data at `offset`.
+    #[inline]
+    pub(crate) const fn with_offset(offset: u8) -> Self {
+        let () = Self::MIN_SIZE;
+        let mut tx_buf = [0; N];
+        tx_buf[0] = Self::OPCODE;
+        tx_buf[1] = offset;
         Self {
             tx_buf,
             rx_buf: [0; N],
@@ -857,9 +1614,45 @@ impl<const N: usize> WriteBuffer<N> {
         SpiDescriptor {
             tx_buf_ptr: self.tx_buf.as_ptr(),
             rx_buf_ptr: self.rx_buf.as_ptr(),
-            transfer_length: N as u16,
+            transfer_length: const { transfer_length(N) },
         }
     }
+    /// Copies `payload` after the offset, returning the descriptor writing only `payload`.
+    /// Payload types longer than `N - 2` bytes fail to build:
+    /// ```compile_fail
+    /// use sx126x_spi_buffers::commands::WriteBuffer;
+    ///
+    /// let mut write_buffer: WriteBuffer<4> = WriteBuffer::from_slice(0, &[]);
+    /// write_buffer.fill(b"hey");
+    /// ```
+    #[inline]
+    pub fn fill<P: BoundedPayload>(&mut self, payload: &P) -> SpiDescriptor {
+        let () = PayloadFits::<P, N>::CHECK;
+        let payload = payload.as_ref();
+        self.tx_buf[2..2 + payload.len()].copy_from_slice(payload);
+        SpiDescriptor {
+            transfer_length: transfer_length(payload.len() + 2),
+            ..self.descriptor()
+        }
+    }
+}
+
+/// Payloads of at most `MAX_LEN` bytes, accepted by [`WriteBuffer::fill`], e.g. arrays and,
+/// with the `heapless` feature, `heapless::Vec`.
+pub trait BoundedPayload: AsRef<[u8]> {
+    const MAX_LEN: usize;
+}
+impl<const M: usize> BoundedPayload for [u8; M] {
+    const MAX_LEN: usize = M;
+}
+#[cfg(feature = "heapless")]
+impl<const M: usize> BoundedPayload for heapless::Vec<u8, M> {
+    const MAX_LEN: usize = M;
+}
+/// Length check of a [`BoundedPayload`] against the size `N` of a `WriteBuffer`.
+struct PayloadFits<P, const N: usize>(core::marker::PhantomData<P>);
+impl<P: BoundedPayload, const N: usize> PayloadFits<P, N> {
+    const CHECK: () = assert!(P::MAX_LEN + 2 <= N, "payload exceeds buffer capacity");
 }
 
 /// # ReadBuffer command
@@ -878,6 +1671,13 @@ impl<const N: usize> WriteBuffer<N> {
 /// assert_eq!(read_buffer.descriptor().transfer_length, 8);
 /// read_buffer.rx_buf[3..8].copy_from_slice(&[b'h', b'e', b'l', b'l', b'o']);
 /// assert_eq!(read_buffer.data(), &[b'h', b'e', b'l', b'l', b'o']);
+///
+/// let write_descriptor = read_buffer.write_descriptor(0x80, 5);
+/// assert_eq!(write_descriptor.transfer_length, 7);
+/// assert_eq!(read_buffer.rx_buf[1..], [0x0E, 0x80, b'h', b'e', b'l', b'l', b'o']);
+/// assert_eq!(write_descriptor.rx_buf_ptr, read_buffer.tx_buf[1..].as_ptr());
+/// read_buffer.set_offset(0x17);
+/// assert_eq!(read_buffer.tx_buf[..2], [0x1E, 0x17]);
 /// ```
 pub struct ReadBuffer<const N: usize> {
     pub tx_buf: [u8; N],
@@ -885,9 +1685,11 @@ pub struct ReadBuffer<const N: usize> {
 }
 impl<const N: usize> ReadBuffer<N> {
     const OPCODE: u8 = 0x1E;
+    const MIN_SIZE: () = assert!(N >= 3, "N must be at least 3");
 
     #[inline]
     pub const fn new(offset: u8) -> Self {
+        let () = Self::MIN_SIZE;
         let mut tx_buf = [0; N];
         tx_buf[0] = Self::OPCODE;
         tx_buf[1] = offset;
@@ -901,15 +1703,82 @@ impl<const N: usize> ReadBuffer<N> {
         SpiDescriptor {
             tx_buf_ptr: self.tx_buf.as_ptr(),
             rx_buf_ptr: self.rx_buf.as_ptr(),
-            transfer_length: N as u16,
+            transfer_length: const { transfer_length(N) },
         }
     }
+    /// Received data. `new()` rejects an `N` less than 3 at compile time.
     #[inline]
     pub fn data(&self) -> &[u8] {
         &self.rx_buf[3..N]
     }
+    /// Sets the offset of the next read.
+    #[inline]
+    pub const fn set_offset(&mut self, offset: u8) {
+        self.tx_buf[1] = offset;
+    }
+    /// Turns the first `len` bytes of data into a `WriteBuffer` transfer to `offset` in place,
+    /// overwriting the status bytes in `rx_buf` with the `WriteBuffer` header. The bytes clocked
+    /// out by the radio are received into `tx_buf` after the opcode, so the read offset is
+    /// overwritten: call `set_offset()` before reading again.
+    ///
+    /// ## Panics
+    /// If `len` is greater than `N - 3`.
+    #[inline]
+    #[allow(clippy::panic)]
+    pub const fn write_descriptor(&mut self, offset: u8, len: u8) -> SpiDescriptor {
+        match self.try_write_descriptor(offset, len) {
+            Ok(descriptor) => descriptor,
+            Err(_) => panic!("length exceeds buffer capacity"),
+        }
+    }
+    /// Like `write_descriptor()`, rejecting a `len` greater than `N - 3`.
+    #[inline]
+    pub const fn try_write_descriptor(
+        &mut self,
+        offset: u8,
+        len: u8,
+    ) -> Result<SpiDescriptor, CapacityExceeded> {
+        if len as usize + 3 > N {
+            return Err(CapacityExceeded {
+                len: len as usize,
+                capacity: N.saturating_sub(3),
+            });
+        }
+        self.rx_buf[1] = 0x0E;
+        self.rx_buf[2] = offset;
+        Ok(SpiDescriptor {
+            tx_buf_ptr: self.rx_buf.as_ptr().wrapping_add(1),
+            rx_buf_ptr: self.tx_buf.as_ptr().wrapping_add(1),
+            transfer_length: transfer_length(len as usize + 2),
+        })
+    }
 }
 
+/// `ReadBuffer` of a full 255 byte payload.
+pub type ReadBuffer255 = ReadBuffer<258>;
+/// `WriteBuffer` of a full 255 byte payload.
+pub type WriteBuffer255 = WriteBuffer<257>;
+
+#[cfg(all(feature = "max-payload-64", feature = "max-payload-128"))]
+compile_error!("only one of the `max-payload-64` and `max-payload-128` features can be enabled");
+/// Longest payload used by the application: 64 or 128 bytes with the `max-payload-64` or
+/// `max-payload-128` feature, 255 bytes by default. Sizes [`MaxReadBuffer`] and
+/// [`MaxWriteBuffer`], to save RAM on targets exchanging short packets only.
+///
+/// The features shrink public types, so they are not additive: only the final binary should
+/// enable one, and enabling both fails to compile.
+pub const MAX_PAYLOAD_LEN: usize = if cfg!(feature = "max-payload-64") {
+    64
+} else if cfg!(feature = "max-payload-128") {
+    128
+} else {
+    255
+};
+/// `ReadBuffer` of a [`MAX_PAYLOAD_LEN`] byte payload.
+pub type MaxReadBuffer = ReadBuffer<{ MAX_PAYLOAD_LEN + 3 }>;
+/// `WriteBuffer` of a [`MAX_PAYLOAD_LEN`] byte payload.
+pub type MaxWriteBuffer = WriteBuffer<{ MAX_PAYLOAD_LEN + 2 }>;
+
 /// # SetDioIrqParams command
 /// Sets the DIO IRQ parameters for the device.
 ///
@@ -990,32 +1859,244 @@ pub struct Irq {
     #[bits(1)]
     __: bool,
 }
-
-/// # GetIrqStatus command
-/// Retrieves the value of the IRQ register.
+/// # Irq constants and bit operations
+/// Single flag masks, composed with `|`, `&` and `!` or their const equivalents
+/// [`union`](Irq::union), [`intersection`](Irq::intersection) and
+/// [`complement`](Irq::complement).
 ///
 /// ## Example
 /// ```
-/// use sx126x_spi_buffers::commands::{GetIrqStatus, Irq};
+/// use sx126x_spi_buffers::commands::Irq;
 ///
-/// let mut get_irq_status: GetIrqStatus = GetIrqStatus::new();
-/// assert_eq!(get_irq_status.tx_buf, [0x12, 0, 0, 0]);
-/// assert_eq!(get_irq_status.rx_buf, [0; 4]);
-/// assert_eq!(get_irq_status.descriptor().transfer_length, 4);
-/// get_irq_status.rx_buf[3] = 0x03;
-/// assert_eq!(get_irq_status.irq_status(), Irq::new().with_tx_done(true).with_rx_done(true).with_timeout(false));
+/// const TX_IRQS: Irq = Irq::TX_DONE.union(Irq::TIMEOUT);
+/// assert_eq!(TX_IRQS, Irq::new().with_tx_done(true).with_timeout(true));
+/// assert_eq!(Irq::TX_DONE | Irq::TIMEOUT, TX_IRQS);
+/// assert_eq!(TX_IRQS & Irq::TIMEOUT, Irq::TIMEOUT);
+/// assert_eq!(!TX_IRQS & TX_IRQS, Irq::NONE);
+/// assert_eq!(!Irq::NONE, Irq::ALL);
+/// assert!(TX_IRQS.contains(Irq::TX_DONE));
 /// ```
-pub struct GetIrqStatus {
-    pub tx_buf: [u8; 4],
-    pub rx_buf: [u8; 4],
-}
-impl GetIrqStatus {
-    const OPCODE: u8 = 0x12;
+impl Irq {
+    pub const NONE: Self = Self::from_bits(0);
+    /// All flags, without the reserved bits.
+    pub const ALL: Self = Self::from_bits(0x43FF);
+    pub const TX_DONE: Self = Self::new().with_tx_done(true);
+    pub const RX_DONE: Self = Self::new().with_rx_done(true);
+    pub const PREAMBLE_DETECTED: Self = Self::new().with_preamble_detected(true);
+    pub const SYNC_WORD_VALID: Self = Self::new().with_sync_word_valid(true);
+    pub const HEADER_VALID: Self = Self::new().with_header_valid(true);
+    pub const HEADER_ERR: Self = Self::new().with_header_err(true);
+    pub const CRC_ERR: Self = Self::new().with_crc_err(true);
+    pub const CAD_DONE: Self = Self::new().with_cad_done(true);
+    pub const CAD_DETECTED: Self = Self::new().with_cad_detected(true);
+    pub const TIMEOUT: Self = Self::new().with_timeout(true);
+    pub const LR_FHSS_HOP: Self = Self::new().with_lr_fhss_hop(true);
 
+    /// Flags set in `self` or `other`.
     #[inline]
-    pub const fn new() -> Self {
-        Self {
-            tx_buf: [Self::OPCODE, 0, 0, 0],
+    pub const fn union(self, other: Self) -> Self {
+        Self::from_bits(self.into_bits() | other.into_bits())
+    }
+    /// Flags set in both `self` and `other`.
+    #[inline]
+    pub const fn intersection(self, other: Self) -> Self {
+        Self::from_bits(self.into_bits() & other.into_bits())
+    }
+    /// Flags not set in `self`, without the reserved bits.
+    #[inline]
+    pub const fn complement(self) -> Self {
+        Self::from_bits(!self.into_bits() & Self::ALL.into_bits())
+    }
+    /// Whether all flags of `other` are set in `self`.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.into_bits() & other.into_bits() == other.into_bits()
+    }
+    /// Whether no flag is set.
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.into_bits() == 0
+    }
+}
+impl core::ops::BitOr for Irq {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+impl core::ops::BitOrAssign for Irq {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = self.union(rhs);
+    }
+}
+impl core::ops::BitAnd for Irq {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self {
+        self.intersection(rhs)
+    }
+}
+impl core::ops::BitAndAssign for Irq {
+    #[inline]
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = self.intersection(rhs);
+    }
+}
+impl core::ops::Not for Irq {
+    type Output = Self;
+
+    #[inline]
+    fn not(self) -> Self {
+        self.complement()
+    }
+}
+impl Irq {
+    /// Iterator over the set flags, from the lowest bit, e.g. to handle each pending event.
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::commands::{Irq, IrqFlag};
+    ///
+    /// let irq = Irq::TX_DONE | Irq::TIMEOUT;
+    /// let mut flags = irq.iter();
+    /// assert_eq!(flags.next(), Some(IrqFlag::TxDone));
+    /// assert_eq!(flags.next(), Some(IrqFlag::Timeout));
+    /// assert_eq!(flags.next(), None);
+    /// assert_eq!(IrqFlag::Timeout.mask(), Irq::TIMEOUT);
+    /// ```
+    #[inline]
+    pub const fn iter(self) -> IrqIter {
+        IrqIter(self.intersection(Self::ALL).into_bits())
+    }
+}
+/// A single [`Irq`] flag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IrqFlag {
+    TxDone,
+    RxDone,
+    PreambleDetected,
+    SyncWordValid,
+    HeaderValid,
+    HeaderErr,
+    CrcErr,
+    CadDone,
+    CadDetected,
+    Timeout,
+    LrFhssHop,
+}
+impl IrqFlag {
+    /// All flags, from the lowest bit.
+    pub const ALL: [Self; 11] = [
+        Self::TxDone,
+        Self::RxDone,
+        Self::PreambleDetected,
+        Self::SyncWordValid,
+        Self::HeaderValid,
+        Self::HeaderErr,
+        Self::CrcErr,
+        Self::CadDone,
+        Self::CadDetected,
+        Self::Timeout,
+        Self::LrFhssHop,
+    ];
+
+    /// Bit of the flag in the IRQ register.
+    #[inline]
+    pub const fn bit(self) -> u8 {
+        match self {
+            Self::LrFhssHop => 14,
+            flag => flag as u8,
+        }
+    }
+    /// The flag as an [`Irq`] mask.
+    #[inline]
+    pub const fn mask(self) -> Irq {
+        Irq::from_bits(1 << self.bit())
+    }
+    /// Flag at `bit` of the IRQ register, `None` for reserved bits.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn from_bit(bit: u8) -> Option<Self> {
+        match bit {
+            0..=9 => Some(Self::ALL[bit as usize]),
+            14 => Some(Self::LrFhssHop),
+            _ => None,
+        }
+    }
+}
+/// Iterator over the flags set in a 16-bit flag register, from the lowest bit, returned by the
+/// `iter()` of the register, which also makes the register `IntoIterator`.
+macro_rules! flag_iter {
+    ($iter:ident: $register:ident => $flag:ident) => {
+        #[doc = concat!(
+            "Iterator over the flags set in an [`", stringify!($register), "`], returned by [`",
+            stringify!($register), "::iter`]."
+        )]
+        #[derive(Clone, Copy, Debug)]
+        pub struct $iter(u16);
+        impl Iterator for $iter {
+            type Item = $flag;
+
+            #[inline]
+            fn next(&mut self) -> Option<$flag> {
+                if self.0 == 0 {
+                    return None;
+                }
+                let bit = self.0.trailing_zeros() as u8;
+                // Clear the lowest set bit
+                self.0 &= self.0 - 1;
+                $flag::from_bit(bit)
+            }
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.0.count_ones() as usize;
+                (len, Some(len))
+            }
+        }
+        impl ExactSizeIterator for $iter {}
+        impl IntoIterator for $register {
+            type Item = $flag;
+            type IntoIter = $iter;
+
+            #[inline]
+            fn into_iter(self) -> $iter {
+                self.iter()
+            }
+        }
+    };
+}
+flag_iter!(IrqIter: Irq => IrqFlag);
+
+/// # GetIrqStatus command
+/// Retrieves the value of the IRQ register.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{GetIrqStatus, Irq};
+///
+/// let mut get_irq_status: GetIrqStatus = GetIrqStatus::new();
+/// assert_eq!(get_irq_status.tx_buf, [0x12, 0, 0, 0]);
+/// assert_eq!(get_irq_status.rx_buf, [0; 4]);
+/// assert_eq!(get_irq_status.descriptor().transfer_length, 4);
+/// get_irq_status.rx_buf[3] = 0x03;
+/// assert_eq!(get_irq_status.raw(), 0x0003);
+/// assert_eq!(get_irq_status.irq_status(), Irq::new().with_tx_done(true).with_rx_done(true).with_timeout(false));
+/// ```
+pub struct GetIrqStatus {
+    pub tx_buf: [u8; 4],
+    pub rx_buf: [u8; 4],
+}
+impl GetIrqStatus {
+    const OPCODE: u8 = 0x12;
+
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            tx_buf: [Self::OPCODE, 0, 0, 0],
             rx_buf: [0; 4],
         }
     }
@@ -1029,7 +2110,24 @@ impl GetIrqStatus {
     }
     #[inline]
     pub const fn irq_status(&self) -> Irq {
-        Irq::from_bits((self.rx_buf[2] as u16) << 8 | (self.rx_buf[3] as u16))
+        Irq::from_bits(self.raw())
+    }
+    /// Undecoded IRQ register, including the reserved bits.
+    #[inline]
+    pub const fn raw(&self) -> u16 {
+        (self.rx_buf[2] as u16) << 8 | (self.rx_buf[3] as u16)
+    }
+    /// IRQ status, rejecting reserved bits.
+    #[inline]
+    pub const fn try_irq_status(&self) -> Result<Irq, InvalidResponse> {
+        // Bits 10 to 13 and 15
+        if self.rx_buf[2] & 0xBC != 0 {
+            return Err(InvalidResponse::Reserved {
+                index: 2,
+                value: self.rx_buf[2],
+            });
+        }
+        Ok(self.irq_status())
     }
 }
 
@@ -1153,6 +2251,7 @@ impl SetDio3AsTcxoCtrl {
     }
 }
 #[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TcxoVoltage {
     V1_6 = 0x00,
     V1_7 = 0x01,
@@ -1163,18 +2262,34 @@ pub enum TcxoVoltage {
     V3_0 = 0x06,
     V3_3 = 0x07,
 }
+impl TcxoVoltage {
+    #[inline]
+    pub(crate) const fn from_bits(value: u8) -> Self {
+        unsafe { core::mem::transmute(value & 0x07) }
+    }
+}
 
 /// # SetRfFrequency command
 /// Sets the RF frequency for the device.
 ///
 /// ## Example
 /// ```
-/// use sx126x_spi_buffers::commands::SetRfFrequency;
+/// use sx126x_spi_buffers::commands::{Device, FrequencyOutOfRange, SetRfFrequency};
 ///
 /// const SET_RF_FREQUENCY: SetRfFrequency = SetRfFrequency::new(455_081_984);
 /// assert_eq!(SET_RF_FREQUENCY.tx_buf, [0x86, 0x1B, 0x20, 0, 0]);
 /// assert_eq!(SET_RF_FREQUENCY.rx_buf, [0; 5]);
 /// assert_eq!(SET_RF_FREQUENCY.descriptor().transfer_length, 5);
+///
+/// const SET_RF_FREQUENCY_868_1: SetRfFrequency = SetRfFrequency::from_hz(868_100_000);
+/// assert_eq!(SET_RF_FREQUENCY_868_1.tx_buf, [0x86, 0x36, 0x41, 0x99, 0x99]);
+///
+/// assert!(SetRfFrequency::try_from_hz_for(Device::Sx1262, 868_100_000).is_ok());
+/// assert_eq!(
+///     SetRfFrequency::try_from_hz_for(Device::Sx1268, 868_100_000).err(),
+///     Some(FrequencyOutOfRange { freq_hz: 868_100_000 })
+/// );
+/// assert!(SetRfFrequency::try_from_hz(433_175_000).is_ok());
 /// ```
 pub struct SetRfFrequency {
     pub tx_buf: [u8; 5],
@@ -1196,6 +2311,30 @@ impl SetRfFrequency {
             rx_buf: [0; 5],
         }
     }
+    /// RF frequency in Hz, converted with the 32 MHz crystal: `rf_freq = freq_hz * 2^25 / 32 MHz`.
+    #[inline]
+    pub const fn from_hz(freq_hz: u32) -> Self {
+        Self::new((((freq_hz as u64) << 25) / 32_000_000) as u32)
+    }
+    /// RF frequency in Hz, rejecting a frequency outside of the range of the
+    /// [`DEFAULT_DEVICE`](crate::DEFAULT_DEVICE).
+    #[inline]
+    pub const fn try_from_hz(freq_hz: u32) -> Result<Self, FrequencyOutOfRange> {
+        Self::try_from_hz_for(DEFAULT_DEVICE, freq_hz)
+    }
+    /// RF frequency in Hz, rejecting a frequency outside of the range of `device`.
+    #[inline]
+    pub const fn try_from_hz_for(
+        device: Device,
+        freq_hz: u32,
+    ) -> Result<Self, FrequencyOutOfRange> {
+        let (min_hz, max_hz) = device.freq_range_hz();
+        if freq_hz < min_hz || freq_hz > max_hz {
+            Err(FrequencyOutOfRange { freq_hz })
+        } else {
+            Ok(Self::from_hz(freq_hz))
+        }
+    }
     #[inline]
     pub const fn descriptor(&self) -> SpiDescriptor {
         SpiDescriptor {
@@ -1295,6 +2434,14 @@ impl GetPacketType {
     pub const fn packet_type(&self) -> PacketType {
         PacketType::from_bits(self.rx_buf[2])
     }
+    /// Packet type, rejecting reserved values.
+    #[inline]
+    pub const fn try_packet_type(&self) -> Result<PacketType, InvalidResponse> {
+        match self.rx_buf[2] {
+            0x00 | 0x01 | 0x03 => Ok(self.packet_type()),
+            value => Err(InvalidResponse::Reserved { index: 2, value }),
+        }
+    }
 }
 
 /// # SetTxParams command
@@ -1302,12 +2449,20 @@ impl GetPacketType {
 ///
 /// ## Example
 /// ```
-/// use sx126x_spi_buffers::commands::{SetTxParams, RampTime};
+/// use sx126x_spi_buffers::commands::{Device, RampTime, SetTxParams, TxPowerOutOfRange};
 ///
 /// const SET_TX_PARAMS: SetTxParams = SetTxParams::new(22, RampTime::Ramp200U);
 /// assert_eq!(SET_TX_PARAMS.tx_buf, [0x8E, 22, 4]);
 /// assert_eq!(SET_TX_PARAMS.rx_buf, [0; 3]);
 /// assert_eq!(SET_TX_PARAMS.descriptor().transfer_length, 3);
+///
+/// let set_tx_params = SetTxParams::try_new_for(Device::Sx1261, -17, RampTime::Ramp200U).unwrap();
+/// assert_eq!(set_tx_params.tx_buf, [0x8E, 0xEF, 4]);
+/// assert_eq!(
+///     SetTxParams::try_new_for(Device::Sx1261, 15, RampTime::Ramp200U).err(),
+///     Some(TxPowerOutOfRange { device: Device::Sx1261, power: 15 })
+/// );
+/// assert!(SetTxParams::try_new(14, RampTime::Ramp200U).is_ok());
 /// ```
 pub struct SetTxParams {
     pub tx_buf: [u8; 3],
@@ -1323,6 +2478,25 @@ impl SetTxParams {
             rx_buf: [0; 3],
         }
     }
+    /// TX params for the [`DEFAULT_DEVICE`](crate::DEFAULT_DEVICE), rejecting a `power` in dBm
+    /// outside of its range.
+    #[inline]
+    pub const fn try_new(power: i8, ramp_time: RampTime) -> Result<Self, TxPowerOutOfRange> {
+        Self::try_new_for(DEFAULT_DEVICE, power, ramp_time)
+    }
+    /// TX params for `device`, rejecting a `power` in dBm outside of its range.
+    #[inline]
+    pub const fn try_new_for(
+        device: Device,
+        power: i8,
+        ramp_time: RampTime,
+    ) -> Result<Self, TxPowerOutOfRange> {
+        if power < device.min_tx_power() || power > device.max_tx_power() {
+            Err(TxPowerOutOfRange { device, power })
+        } else {
+            Ok(Self::new(power as u8, ramp_time))
+        }
+    }
     #[inline]
     pub const fn descriptor(&self) -> SpiDescriptor {
         SpiDescriptor {
@@ -1333,7 +2507,7 @@ impl SetTxParams {
     }
 }
 #[repr(u8)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum RampTime {
     Ramp10U = 0x00,
     Ramp20U = 0x01,
@@ -1348,19 +2522,58 @@ pub enum RampTime {
 /// # SetModulationParamsLora command
 /// Configures the LoRa modulation parameters of the radio.
 ///
+/// The LLCC68 accepts the SF/BW combinations it does not support and fails silently: use
+/// `try_new()` to reject them for the device, in const context at compile time. With the
+/// `llcc68` feature, `new()` and `new_auto_ldro()` reject them for the LLCC68, so a `const`
+/// item with an unsupported combination fails to compile.
+///
 /// ## Example
 /// ```
-/// use sx126x_spi_buffers::commands::{SetModulationParamsLora, Sf, Bw, Cr};
+/// use sx126x_spi_buffers::commands::{
+///     Bw, Cr, Device, SetModulationParamsLora, Sf, UnsupportedModulation,
+/// };
 ///
 /// const SET_MODULATION_PARAMS_LORA: SetModulationParamsLora = SetModulationParamsLora::new(
-///    Sf::Sf10,
+///    Sf::Sf9,
 ///    Bw::Bw125,
 ///    Cr::Cr4_5,
 ///    false,
 /// );
-/// assert_eq!(SET_MODULATION_PARAMS_LORA.tx_buf, [0x8B, 0x0A, 0x04, 0x01, 0]);
+/// assert_eq!(SET_MODULATION_PARAMS_LORA.tx_buf, [0x8B, 0x09, 0x04, 0x01, 0]);
 /// assert_eq!(SET_MODULATION_PARAMS_LORA.rx_buf, [0; 5]);
 /// assert_eq!(SET_MODULATION_PARAMS_LORA.descriptor().transfer_length, 5);
+///
+/// const SET_MODULATION_PARAMS_SF11: SetModulationParamsLora =
+///     SetModulationParamsLora::new_auto_ldro(Sf::Sf11, Bw::Bw500, Cr::Cr4_5);
+/// assert_eq!(SET_MODULATION_PARAMS_SF11.tx_buf, [0x8B, 0x0B, 0x06, 0x01, 0]);
+///
+/// let sx1262 = SetModulationParamsLora::try_new(Device::Sx1262, Sf::Sf12, Bw::Bw125, Cr::Cr4_5, true);
+/// assert!(sx1262.is_ok());
+/// let llcc68 = SetModulationParamsLora::try_new(Device::Llcc68, Sf::Sf12, Bw::Bw125, Cr::Cr4_5, true);
+/// assert_eq!(
+///     llcc68.err(),
+///     Some(UnsupportedModulation { device: Device::Llcc68, sf: Sf::Sf12, bw: Bw::Bw125 })
+/// );
+/// ```
+///
+/// An unsupported combination in a `const` item fails to compile:
+/// ```compile_fail
+/// use sx126x_spi_buffers::commands::{Bw, Cr, Device, SetModulationParamsLora, Sf};
+///
+/// const SET_MODULATION_PARAMS_LORA: SetModulationParamsLora =
+///     match SetModulationParamsLora::try_new(Device::Llcc68, Sf::Sf10, Bw::Bw125, Cr::Cr4_5, false) {
+///         Ok(set_modulation_params) => set_modulation_params,
+///         Err(_) => panic!("unsupported by the LLCC68"),
+///     };
+/// ```
+///
+/// With the `llcc68` feature, so does an unsupported combination given to `new()`:
+#[cfg_attr(feature = "llcc68", doc = "```compile_fail")]
+#[cfg_attr(not(feature = "llcc68"), doc = "```ignore")]
+/// use sx126x_spi_buffers::commands::{Bw, Cr, SetModulationParamsLora, Sf};
+///
+/// const SET_MODULATION_PARAMS_LORA: SetModulationParamsLora =
+///     SetModulationParamsLora::new(Sf::Sf12, Bw::Bw125, Cr::Cr4_5, true);
 /// ```
 pub struct SetModulationParamsLora {
     pub tx_buf: [u8; 5],
@@ -1369,8 +2582,19 @@ pub struct SetModulationParamsLora {
 impl SetModulationParamsLora {
     const OPCODE: u8 = 0x8B;
 
+    /// ## Panics
+    /// With the `llcc68` feature, if the LLCC68 does not support `sf` at `bw`.
     #[inline]
     pub const fn new(sf: Sf, bw: Bw, cr: Cr, low_data_rate_optimize: bool) -> Self {
+        #[cfg(feature = "llcc68")]
+        assert!(
+            Device::Llcc68.supports(sf, bw),
+            "SF/BW combination unsupported by the LLCC68"
+        );
+        Self::new_unchecked(sf, bw, cr, low_data_rate_optimize)
+    }
+    #[inline]
+    const fn new_unchecked(sf: Sf, bw: Bw, cr: Cr, low_data_rate_optimize: bool) -> Self {
         Self {
             tx_buf: [
                 Self::OPCODE,
@@ -1382,6 +2606,34 @@ impl SetModulationParamsLora {
             rx_buf: [0; 5],
         }
     }
+    /// Enables low data rate optimization when it is required for `sf` and `bw`.
+    ///
+    /// ## Panics
+    /// With the `llcc68` feature, if the LLCC68 does not support `sf` at `bw`.
+    #[inline]
+    pub const fn new_auto_ldro(sf: Sf, bw: Bw, cr: Cr) -> Self {
+        Self::new(sf, bw, cr, Self::ldro_required(sf, bw))
+    }
+    /// Rejects an SF/BW combination that `device` does not support.
+    #[inline]
+    pub const fn try_new(
+        device: Device,
+        sf: Sf,
+        bw: Bw,
+        cr: Cr,
+        low_data_rate_optimize: bool,
+    ) -> Result<Self, UnsupportedModulation> {
+        if !device.supports(sf, bw) {
+            Err(UnsupportedModulation { device, sf, bw })
+        } else {
+            Ok(Self::new_unchecked(sf, bw, cr, low_data_rate_optimize))
+        }
+    }
+    /// Low data rate optimization is required for symbols of 16.38 ms or longer.
+    #[inline]
+    pub const fn ldro_required(sf: Sf, bw: Bw) -> bool {
+        timing::symbol_duration_us(sf, bw) >= 16_384
+    }
     #[inline]
     pub const fn descriptor(&self) -> SpiDescriptor {
         SpiDescriptor {
@@ -1392,6 +2644,7 @@ impl SetModulationParamsLora {
     }
 }
 #[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Sf {
     Sf5 = 0x05,
     Sf6 = 0x06,
@@ -1402,7 +2655,20 @@ pub enum Sf {
     Sf11 = 0x0B,
     Sf12 = 0x0C,
 }
+impl Sf {
+    /// Spreading factor, e.g. 7 for SF7.
+    #[inline]
+    pub const fn factor(self) -> u8 {
+        self as u8
+    }
+    /// Chips per symbol, `2^SF`.
+    #[inline]
+    pub const fn chips_per_symbol(self) -> u32 {
+        1 << self as u8
+    }
+}
 #[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Bw {
     Bw7_8 = 0x00,
     Bw10_42 = 0x08,
@@ -1415,7 +2681,26 @@ pub enum Bw {
     Bw250 = 0x05,
     Bw500 = 0x06,
 }
+impl Bw {
+    /// Bandwidth in Hz, rounded to the nearest Hz.
+    #[inline]
+    pub const fn hz(self) -> u32 {
+        match self {
+            Bw::Bw7_8 => 7_812,
+            Bw::Bw10_42 => 10_417,
+            Bw::Bw15_63 => 15_625,
+            Bw::Bw20_83 => 20_833,
+            Bw::Bw31_25 => 31_250,
+            Bw::Bw41_67 => 41_667,
+            Bw::Bw62_50 => 62_500,
+            Bw::Bw125 => 125_000,
+            Bw::Bw250 => 250_000,
+            Bw::Bw500 => 500_000,
+        }
+    }
+}
 #[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Cr {
     Cr4_5 = 0x01,
     Cr4_6 = 0x02,
@@ -1425,6 +2710,384 @@ pub enum Cr {
     Cr4_6Li = 0x06,
     Cr4_8Li = 0x07,
 }
+impl Cr {
+    /// Numerator of the coding rate, always 4.
+    #[inline]
+    pub const fn numerator(self) -> u8 {
+        4
+    }
+    /// Denominator of the coding rate, e.g. 5 for 4/5 with or without long interleaving.
+    #[inline]
+    pub const fn denominator(self) -> u8 {
+        match self {
+            Cr::Cr4_5 | Cr::Cr4_5Li => 5,
+            Cr::Cr4_6 | Cr::Cr4_6Li => 6,
+            Cr::Cr4_7 => 7,
+            Cr::Cr4_8 | Cr::Cr4_8Li => 8,
+        }
+    }
+}
+
+/// Device of the SX126x family, given to the functions that check parameters against its
+/// limits, e.g. `SetTxParams::try_new_for`. The functions without a `Device` parameter, e.g.
+/// `SetTxParams::try_new`, check against the [`DEFAULT_DEVICE`](crate::DEFAULT_DEVICE).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Device {
+    Sx1261,
+    Sx1262,
+    Sx1268,
+    Llcc68,
+}
+
+impl Device {
+    /// Minimum TX power in dBm.
+    #[inline]
+    pub const fn min_tx_power(self) -> i8 {
+        match self {
+            Device::Sx1261 => -17,
+            Device::Sx1262 | Device::Sx1268 | Device::Llcc68 => -9,
+        }
+    }
+    /// Maximum `SetTxParams` power in dBm: +14 dBm for the SX1261 low power PA, +22 dBm for
+    /// the others.
+    #[inline]
+    pub const fn max_tx_power(self) -> i8 {
+        match self {
+            Device::Sx1261 => 14,
+            Device::Sx1262 | Device::Sx1268 | Device::Llcc68 => 22,
+        }
+    }
+    /// Maximum output power in dBm, reached with the [`SetPaConfig::optimal`] PA config: +15 dBm
+    /// for the SX1261, +22 dBm for the others.
+    #[inline]
+    pub const fn max_output_power(self) -> i8 {
+        match self {
+            Device::Sx1261 => 15,
+            Device::Sx1262 | Device::Sx1268 | Device::Llcc68 => 22,
+        }
+    }
+    /// Minimum and maximum RF frequency in Hz.
+    #[inline]
+    pub const fn freq_range_hz(self) -> (u32, u32) {
+        match self {
+            Device::Sx1268 => (410_000_000, 810_000_000),
+            Device::Sx1261 | Device::Sx1262 | Device::Llcc68 => (150_000_000, 960_000_000),
+        }
+    }
+    /// `device_sel` of `SetPaConfig`, selecting the low power PA of the SX1261.
+    #[inline]
+    pub const fn device_sel(self) -> u8 {
+        match self {
+            Device::Sx1261 => 1,
+            Device::Sx1262 | Device::Sx1268 | Device::Llcc68 => 0,
+        }
+    }
+    /// Highest `pa_duty_cycle` of `SetPaConfig`. Higher values may damage the device.
+    #[inline]
+    pub const fn max_pa_duty_cycle(self) -> u8 {
+        match self {
+            Device::Sx1261 => 0x07,
+            Device::Sx1262 | Device::Sx1268 | Device::Llcc68 => 0x04,
+        }
+    }
+    /// Whether the device supports LoRa with `sf` and `bw`. The LLCC68 only supports 125, 250
+    /// and 500 kHz, with SF5-9, SF5-10 and SF5-11 respectively.
+    #[inline]
+    pub const fn supports(self, sf: Sf, bw: Bw) -> bool {
+        match self {
+            Device::Sx1261 | Device::Sx1262 | Device::Sx1268 => true,
+            Device::Llcc68 => match bw {
+                Bw::Bw125 => sf as u8 <= Sf::Sf9 as u8,
+                Bw::Bw250 => sf as u8 <= Sf::Sf10 as u8,
+                Bw::Bw500 => sf as u8 <= Sf::Sf11 as u8,
+                _ => false,
+            },
+        }
+    }
+}
+
+/// SF/BW combination not supported by the device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnsupportedModulation {
+    pub device: Device,
+    pub sf: Sf,
+    pub bw: Bw,
+}
+
+/// Data longer than the buffer of a command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CapacityExceeded {
+    pub len: usize,
+    pub capacity: usize,
+}
+
+/// Data length different from the fixed length of a command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidLength {
+    pub len: usize,
+    pub expected: usize,
+}
+
+/// Response of the radio failing validation, e.g. in a corrupted capture.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidResponse {
+    /// Response length different from the command length.
+    Length(InvalidLength),
+    /// Status byte with bit 0 set or a reserved chip mode or command status.
+    Status(u8),
+    /// Byte at `index` with reserved bits set or a reserved value.
+    Reserved { index: usize, value: u8 },
+}
+
+/// Copies a captured response of a command of `N` bytes, validating its length and status
+/// byte.
+#[allow(clippy::indexing_slicing)]
+const fn checked_rx_buf<const N: usize>(rx_buf: &[u8]) -> Result<[u8; N], InvalidResponse> {
+    if rx_buf.len() != N {
+        return Err(InvalidResponse::Length(InvalidLength {
+            len: rx_buf.len(),
+            expected: N,
+        }));
+    }
+    if let Err(err) = Status::try_from_bits(rx_buf[1]) {
+        return Err(err);
+    }
+    let mut buf = [0; N];
+    buf.copy_from_slice(rx_buf);
+    Ok(buf)
+}
+
+/// `try_from_rx_buf()` of the commands reading a response, building the command around a
+/// captured response checked by [`checked_rx_buf`].
+macro_rules! impl_try_from_rx_buf {
+    ($($command:ident $(<$param:ident>)?),+ $(,)?) => {$(
+        impl$(<$param>)? $command$(<$param>)? {
+            #[doc = concat!(
+                "`", stringify!($command), "` with `rx_buf` copied from a captured response, ",
+                "validating its length and status byte."
+            )]
+            #[inline]
+            pub const fn try_from_rx_buf(rx_buf: &[u8]) -> Result<Self, InvalidResponse> {
+                match checked_rx_buf(rx_buf) {
+                    Ok(rx_buf) => Ok(Self {
+                        rx_buf,
+                        ..Self::new()
+                    }),
+                    Err(err) => Err(err),
+                }
+            }
+        }
+    )+};
+}
+impl_try_from_rx_buf!(
+    GetStatus,
+    GetIrqStatus,
+    GetDeviceErrors,
+    GetPacketType,
+    GetRssiInst,
+    GetRxBufferStatus,
+    GetPacketStatusLora,
+    GetPacketStatusGfsk,
+    GetStats<P>,
+);
+
+/// `SetPaConfig` parameters that may damage the device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidPaConfig {
+    pub device: Device,
+    pub pa_duty_cycle: u8,
+    pub hp_max: u8,
+}
+
+/// TX power outside of the range of the device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TxPowerOutOfRange {
+    pub device: Device,
+    pub power: i8,
+}
+
+/// RF frequency outside of the range of the device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrequencyOutOfRange {
+    pub freq_hz: u32,
+}
+
+/// # SetModulationParamsGfsk command
+/// Configures the GFSK modulation parameters of the radio.
+///
+/// - `br` = 32 * 32 MHz / bitrate
+/// - `fdev` = frequency deviation * 2^25 / 32 MHz
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{GfskBw, PulseShape, Rounded, SetModulationParamsGfsk};
+///
+/// const SET_MODULATION_PARAMS_GFSK: SetModulationParamsGfsk =
+///     SetModulationParamsGfsk::new(0x5000, PulseShape::Bt0_5, GfskBw::Bw117_3, 0x6666);
+/// assert_eq!(
+///     SET_MODULATION_PARAMS_GFSK.tx_buf,
+///     [0x8B, 0, 0x50, 0, 0x09, 0x0B, 0, 0x66, 0x66]
+/// );
+/// assert_eq!(SET_MODULATION_PARAMS_GFSK.rx_buf, [0; 9]);
+/// assert_eq!(SET_MODULATION_PARAMS_GFSK.descriptor().transfer_length, 9);
+///
+/// assert_eq!(
+///     SetModulationParamsGfsk::encode_bitrate(50_000),
+///     Rounded { raw: 0x5000, error: 0 }
+/// );
+/// assert_eq!(
+///     SetModulationParamsGfsk::encode_bitrate(300_000),
+///     Rounded { raw: 3413, error: 29 }
+/// );
+/// assert_eq!(
+///     SetModulationParamsGfsk::encode_fdev(25_000),
+///     Rounded { raw: 0x6666, error: 0 }
+/// );
+/// // Out of range values are clamped
+/// assert_eq!(SetModulationParamsGfsk::encode_bitrate(0).raw, 0xFF_FFFF);
+/// assert_eq!(SetModulationParamsGfsk::encode_bitrate(u32::MAX).raw, 1);
+/// assert_eq!(SetModulationParamsGfsk::encode_fdev(1_000_000_000).raw, 0xFF_FFFF);
+/// let set_modulation_params_gfsk =
+///     SetModulationParamsGfsk::from_units(50_000, PulseShape::Bt0_5, GfskBw::Bw117_3, 25_000);
+/// assert_eq!(set_modulation_params_gfsk.tx_buf, SET_MODULATION_PARAMS_GFSK.tx_buf);
+/// ```
+pub struct SetModulationParamsGfsk {
+    pub tx_buf: [u8; 9],
+    pub rx_buf: [u8; 9],
+}
+impl SetModulationParamsGfsk {
+    const OPCODE: u8 = 0x8B;
+
+    #[inline]
+    pub const fn new(br: u32, pulse_shape: PulseShape, bw: GfskBw, fdev: u32) -> Self {
+        Self {
+            tx_buf: [
+                Self::OPCODE,
+                (br >> 16) as u8,
+                (br >> 8) as u8,
+                br as u8,
+                pulse_shape as u8,
+                bw as u8,
+                (fdev >> 16) as u8,
+                (fdev >> 8) as u8,
+                fdev as u8,
+            ],
+            rx_buf: [0; 9],
+        }
+    }
+    /// Modulation parameters with the bitrate in bps and frequency deviation in Hz, rounded to
+    /// the nearest encodable values.
+    #[inline]
+    pub const fn from_units(
+        bitrate_bps: u32,
+        pulse_shape: PulseShape,
+        bw: GfskBw,
+        fdev_hz: u32,
+    ) -> Self {
+        Self::new(
+            Self::encode_bitrate(bitrate_bps).raw,
+            pulse_shape,
+            bw,
+            Self::encode_fdev(fdev_hz).raw,
+        )
+    }
+    /// Largest raw `br` and `fdev`.
+    const RAW_MAX: u64 = 0xFF_FFFF;
+
+    /// Raw `br` for a bitrate in bps, with the rounding error in bps. Bitrates outside the
+    /// encodable range, including 0, are clamped to the nearest encodable bitrate.
+    #[inline]
+    pub const fn encode_bitrate(bitrate_bps: u32) -> Rounded {
+        const BR_NUMERATOR: u64 = 32 * 32_000_000;
+        let raw = match (BR_NUMERATOR + bitrate_bps as u64 / 2).checked_div(bitrate_bps as u64) {
+            Some(0) => 1,
+            Some(raw) if raw <= Self::RAW_MAX => raw,
+            _ => Self::RAW_MAX,
+        };
+        let actual = (BR_NUMERATOR + raw / 2) / raw;
+        Rounded::new(raw, actual, bitrate_bps)
+    }
+    /// Raw `fdev` for a frequency deviation in Hz, with the rounding error in Hz. Deviations
+    /// above the encodable range are clamped to the largest encodable deviation.
+    #[inline]
+    pub const fn encode_fdev(fdev_hz: u32) -> Rounded {
+        let raw = (((fdev_hz as u64) << 25) + 16_000_000) / 32_000_000;
+        let raw = if raw > Self::RAW_MAX {
+            Self::RAW_MAX
+        } else {
+            raw
+        };
+        let actual = (raw * 32_000_000 + (1 << 24)) >> 25;
+        Rounded::new(raw, actual, fdev_hz)
+    }
+    #[inline]
+    pub const fn descriptor(&self) -> SpiDescriptor {
+        SpiDescriptor {
+            tx_buf_ptr: self.tx_buf.as_ptr(),
+            rx_buf_ptr: self.rx_buf.as_ptr(),
+            transfer_length: 9,
+        }
+    }
+}
+/// Gaussian filter applied to the GFSK pulses, by its bandwidth-time product.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PulseShape {
+    NoFilter = 0x00,
+    Bt0_3 = 0x08,
+    Bt0_5 = 0x09,
+    Bt0_7 = 0x0A,
+    Bt1 = 0x0B,
+}
+/// GFSK RX bandwidth in kHz.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GfskBw {
+    Bw4_8 = 0x1F,
+    Bw5_8 = 0x17,
+    Bw7_3 = 0x0F,
+    Bw9_7 = 0x1E,
+    Bw11_7 = 0x16,
+    Bw14_6 = 0x0E,
+    Bw19_5 = 0x1D,
+    Bw23_4 = 0x15,
+    Bw29_3 = 0x0D,
+    Bw39_0 = 0x1C,
+    Bw46_9 = 0x14,
+    Bw58_6 = 0x0C,
+    Bw78_2 = 0x1B,
+    Bw93_8 = 0x13,
+    Bw117_3 = 0x0B,
+    Bw156_2 = 0x1A,
+    Bw187_2 = 0x12,
+    Bw234_3 = 0x0A,
+    Bw312_0 = 0x19,
+    Bw373_6 = 0x11,
+    Bw467_0 = 0x09,
+}
+/// A value rounded to its raw register encoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rounded {
+    pub raw: u32,
+    /// Encoded value minus the requested value, saturated to the range of `i32`.
+    pub error: i32,
+}
+impl Rounded {
+    #[inline]
+    const fn new(raw: u64, actual: u64, requested: u32) -> Self {
+        let error = actual as i64 - requested as i64;
+        Self {
+            raw: raw as u32,
+            error: if error > i32::MAX as i64 {
+                i32::MAX
+            } else if error < i32::MIN as i64 {
+                i32::MIN
+            } else {
+                error as i32
+            },
+        }
+    }
+}
 
 /// # SetPacketParams command
 /// Sets the parameters of the packet handling block.
@@ -1482,29 +3145,123 @@ impl SetPacketParams {
     }
 }
 #[repr(u8)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum HeaderType {
     VariableLength = 0x00,
     FixedLength = 0x01,
 }
 #[repr(u8)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum InvertIq {
     Standard = 0x00,
     Inverted = 0x01,
 }
 
+/// # SetPacketParamsGfsk command
+/// Sets the parameters of the GFSK packet handling block. The preamble and sync word lengths
+/// are in bits.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{AddrComp, PreambleDetector, SetPacketParamsGfsk};
+///
+/// const SET_PACKET_PARAMS_GFSK: SetPacketParamsGfsk = SetPacketParamsGfsk::new(
+///     40,
+///     PreambleDetector::Bits16,
+///     24,
+///     AddrComp::Off,
+///     true,
+///     255,
+///     0x06,
+///     true,
+/// );
+/// assert_eq!(
+///     SET_PACKET_PARAMS_GFSK.tx_buf,
+///     [0x8C, 0, 40, 0x05, 24, 0x00, 0x01, 255, 0x06, 0x01]
+/// );
+/// assert_eq!(SET_PACKET_PARAMS_GFSK.rx_buf, [0; 10]);
+/// assert_eq!(SET_PACKET_PARAMS_GFSK.descriptor().transfer_length, 10);
+/// ```
+pub struct SetPacketParamsGfsk {
+    pub tx_buf: [u8; 10],
+    pub rx_buf: [u8; 10],
+}
+impl SetPacketParamsGfsk {
+    const OPCODE: u8 = 0x8C;
+
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub const fn new(
+        preamble_length: u16,
+        preamble_detector: PreambleDetector,
+        sync_word_length: u8,
+        addr_comp: AddrComp,
+        variable_length: bool,
+        payload_length: u8,
+        crc_type: u8,
+        whitening: bool,
+    ) -> Self {
+        Self {
+            tx_buf: [
+                Self::OPCODE,
+                (preamble_length >> 8) as u8,
+                preamble_length as u8,
+                preamble_detector as u8,
+                sync_word_length,
+                addr_comp as u8,
+                variable_length as u8,
+                payload_length,
+                crc_type,
+                whitening as u8,
+            ],
+            rx_buf: [0; 10],
+        }
+    }
+    #[inline]
+    pub const fn descriptor(&self) -> SpiDescriptor {
+        SpiDescriptor {
+            tx_buf_ptr: self.tx_buf.as_ptr(),
+            rx_buf_ptr: self.rx_buf.as_ptr(),
+            transfer_length: 10,
+        }
+    }
+}
+/// Length of the preamble the GFSK receiver must detect before looking for the sync word.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PreambleDetector {
+    Off = 0x00,
+    Bits8 = 0x04,
+    Bits16 = 0x05,
+    Bits24 = 0x06,
+    Bits32 = 0x07,
+}
+/// GFSK address filtering on the first payload byte, against the `NodeAddress` and
+/// `BroadcastAddress` registers.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddrComp {
+    Off = 0x00,
+    Node = 0x01,
+    NodeAndBroadcast = 0x02,
+}
+
 /// # SetCadParams command
 /// Sets the parameters for the Channel Activity Detection (CAD) operation.
 ///
 /// ## Example
 /// ```
-/// use sx126x_spi_buffers::commands::{SetCadParams, CadSymbolNum, CadExitMode};
+/// use sx126x_spi_buffers::commands::{Bw, CadExitMode, CadSymbolNum, SetCadParams, Sf};
 ///
 /// const SET_CAD_PARAMS: SetCadParams = SetCadParams::new(CadSymbolNum::CadOn4Symb, 23, 10, CadExitMode::CadOnly, 1200);
 /// assert_eq!(SET_CAD_PARAMS.tx_buf, [0x88, 2, 23, 10, 0, 0, 0x04, 0xB0]);
 /// assert_eq!(SET_CAD_PARAMS.rx_buf, [0; 8]);
 /// assert_eq!(SET_CAD_PARAMS.descriptor().transfer_length, 8);
+///
+/// const SET_CAD_PARAMS_SF12: SetCadParams =
+///     SetCadParams::recommended(Sf::Sf12, Bw::Bw125, CadExitMode::CadOnly, 0);
+/// assert_eq!(SET_CAD_PARAMS_SF12.tx_buf, [0x88, 2, 28, 10, 0, 0, 0, 0]);
+/// assert_eq!(SetCadParams::recommended_for(Sf::Sf12).tx_buf, SET_CAD_PARAMS_SF12.tx_buf);
 /// ```
 pub struct SetCadParams {
     pub tx_buf: [u8; 8],
@@ -1535,6 +3292,24 @@ impl SetCadParams {
             rx_buf: [0; 8],
         }
     }
+    /// CAD parameters recommended by Semtech AN1200.48 for `sf` and `bw`. Bandwidths below
+    /// 500 kHz use the 125 kHz recommendations and SF5 and SF6 use those of SF7.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn recommended(sf: Sf, bw: Bw, exit_mode: CadExitMode, timeout: u32) -> Self {
+        let table = match bw {
+            Bw::Bw500 => &CAD_PARAMS_BW500,
+            _ => &CAD_PARAMS_BW125,
+        };
+        let (symbol_num, det_peak, det_min) = table[sf as usize - Sf::Sf5 as usize];
+        Self::new(symbol_num, det_peak, det_min, exit_mode, timeout)
+    }
+    /// CAD parameters for `sf` at 125 kHz, with the defaults `CadOnly` exit mode and no
+    /// timeout, as [`recommended`](Self::recommended) with `Bw::Bw125`.
+    #[inline]
+    pub const fn recommended_for(sf: Sf) -> Self {
+        Self::recommended(sf, Bw::Bw125, CadExitMode::CadOnly, 0)
+    }
     #[inline]
     pub const fn descriptor(&self) -> SpiDescriptor {
         SpiDescriptor {
@@ -1544,8 +3319,32 @@ impl SetCadParams {
         }
     }
 }
+/// Recommended symbol number, `det_peak` and `det_min` at 125 kHz, indexed by spreading factor
+/// from SF5.
+pub const CAD_PARAMS_BW125: [(CadSymbolNum, u8, u8); 8] = [
+    (CadSymbolNum::CadOn2Symb, 22, 10),
+    (CadSymbolNum::CadOn2Symb, 22, 10),
+    (CadSymbolNum::CadOn2Symb, 22, 10),
+    (CadSymbolNum::CadOn2Symb, 22, 10),
+    (CadSymbolNum::CadOn4Symb, 23, 10),
+    (CadSymbolNum::CadOn4Symb, 24, 10),
+    (CadSymbolNum::CadOn4Symb, 25, 10),
+    (CadSymbolNum::CadOn4Symb, 28, 10),
+];
+/// Recommended symbol number, `det_peak` and `det_min` at 500 kHz, indexed by spreading factor
+/// from SF5.
+pub const CAD_PARAMS_BW500: [(CadSymbolNum, u8, u8); 8] = [
+    (CadSymbolNum::CadOn4Symb, 21, 10),
+    (CadSymbolNum::CadOn4Symb, 21, 10),
+    (CadSymbolNum::CadOn4Symb, 21, 10),
+    (CadSymbolNum::CadOn4Symb, 22, 10),
+    (CadSymbolNum::CadOn4Symb, 22, 10),
+    (CadSymbolNum::CadOn4Symb, 23, 10),
+    (CadSymbolNum::CadOn4Symb, 25, 10),
+    (CadSymbolNum::CadOn8Symb, 29, 10),
+];
 #[repr(u8)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CadSymbolNum {
     CadOn1Symb = 0x00,
     CadOn2Symb = 0x01,
@@ -1554,7 +3353,7 @@ pub enum CadSymbolNum {
     CadOn16Symb = 0x04,
 }
 #[repr(u8)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CadExitMode {
     CadOnly = 0x00,
     CadRx = 0x01,
@@ -1646,6 +3445,8 @@ impl SetLoraSymbNumTimeout {
 /// get_status.rx_buf[1] = 0x64;
 /// assert_eq!(get_status.status().chip_mode(), ChipMode::Tx);
 /// assert_eq!(get_status.status().command_status(), CommandStatus::DataIsAvailableToHost);
+/// assert!(get_status.is_tx() && get_status.is_data_available());
+/// assert!(!get_status.is_command_error());
 /// ```
 pub struct GetStatus {
     pub tx_buf: [u8; 2],
@@ -1673,6 +3474,51 @@ impl GetStatus {
     pub const fn status(&self) -> Status {
         Status::from_bits(self.rx_buf[1])
     }
+    /// Undecoded status byte.
+    #[inline]
+    pub const fn raw(&self) -> u8 {
+        self.rx_buf[1]
+    }
+    /// Status, rejecting reserved bits and values.
+    #[inline]
+    pub const fn try_status(&self) -> Result<Status, InvalidResponse> {
+        Status::try_from_bits(self.rx_buf[1])
+    }
+    /// See [`Status::is_data_available`].
+    #[inline]
+    pub const fn is_data_available(&self) -> bool {
+        self.status().is_data_available()
+    }
+    /// See [`Status::is_command_error`].
+    #[inline]
+    pub const fn is_command_error(&self) -> bool {
+        self.status().is_command_error()
+    }
+    /// See [`Status::is_tx_done`].
+    #[inline]
+    pub const fn is_tx_done(&self) -> bool {
+        self.status().is_tx_done()
+    }
+    /// See [`Status::is_standby`].
+    #[inline]
+    pub const fn is_standby(&self) -> bool {
+        self.status().is_standby()
+    }
+    /// See [`Status::is_fs`].
+    #[inline]
+    pub const fn is_fs(&self) -> bool {
+        self.status().is_fs()
+    }
+    /// See [`Status::is_rx`].
+    #[inline]
+    pub const fn is_rx(&self) -> bool {
+        self.status().is_rx()
+    }
+    /// See [`Status::is_tx`].
+    #[inline]
+    pub const fn is_tx(&self) -> bool {
+        self.status().is_tx()
+    }
 }
 #[bitfield(u8, order = Msb)]
 #[derive(PartialEq, Eq)]
@@ -1686,6 +3532,64 @@ pub struct Status {
     #[bits(1)]
     __: bool,
 }
+impl Status {
+    /// Status byte, rejecting a set bit 0 and reserved chip modes and command statuses.
+    ///
+    /// Bit 7 and the `0x1` command status are accepted as devices return them, e.g. `0xA2` in
+    /// STBY_RC.
+    #[inline]
+    pub const fn try_from_bits(bits: u8) -> Result<Self, InvalidResponse> {
+        let (chip_mode, command_status) = ((bits >> 4) & 0x07, (bits >> 1) & 0x07);
+        if bits & 0x01 != 0
+            || !matches!(chip_mode, 0x2..=0x6)
+            || !matches!(command_status, 0x1..=0x6)
+        {
+            return Err(InvalidResponse::Status(bits));
+        }
+        Ok(Self::from_bits(bits))
+    }
+    /// Whether the command status is `DataIsAvailableToHost`.
+    #[inline]
+    pub const fn is_data_available(self) -> bool {
+        matches!(self.command_status(), CommandStatus::DataIsAvailableToHost)
+    }
+    /// Whether the command status is `CommandTimeout`, `CommandProcessingError` or
+    /// `FailureToExecuteCommand`.
+    #[inline]
+    pub const fn is_command_error(self) -> bool {
+        matches!(
+            self.command_status(),
+            CommandStatus::CommandTimeout
+                | CommandStatus::CommandProcessingError
+                | CommandStatus::FailureToExecuteCommand
+        )
+    }
+    /// Whether the command status is `CommandTxDone`.
+    #[inline]
+    pub const fn is_tx_done(self) -> bool {
+        matches!(self.command_status(), CommandStatus::CommandTxDone)
+    }
+    /// Whether the chip is in STBY_RC or STBY_XOSC mode.
+    #[inline]
+    pub const fn is_standby(self) -> bool {
+        matches!(self.chip_mode(), ChipMode::StbyRc | ChipMode::StbyXosc)
+    }
+    /// Whether the chip is in FS mode.
+    #[inline]
+    pub const fn is_fs(self) -> bool {
+        matches!(self.chip_mode(), ChipMode::Fs)
+    }
+    /// Whether the chip is in RX mode.
+    #[inline]
+    pub const fn is_rx(self) -> bool {
+        matches!(self.chip_mode(), ChipMode::Rx)
+    }
+    /// Whether the chip is in TX mode.
+    #[inline]
+    pub const fn is_tx(self) -> bool {
+        matches!(self.chip_mode(), ChipMode::Tx)
+    }
+}
 #[repr(u8)]
 #[derive(Debug, PartialEq, Eq)]
 pub enum ChipMode {
@@ -1703,6 +3607,11 @@ impl ChipMode {
     const fn into_bits(self) -> u8 {
         self as u8
     }
+    /// Raw 3-bit chip mode value, including the reserved values.
+    #[inline]
+    pub const fn raw(self) -> u8 {
+        self as u8
+    }
     #[inline]
     const fn from_bits(value: u8) -> Self {
         unsafe { core::mem::transmute(value & 0x07) }
@@ -1725,6 +3634,11 @@ impl CommandStatus {
     const fn into_bits(self) -> u8 {
         self as u8
     }
+    /// Raw 3-bit command status value, including the reserved values.
+    #[inline]
+    pub const fn raw(self) -> u8 {
+        self as u8
+    }
     #[inline]
     const fn from_bits(value: u8) -> Self {
         unsafe { core::mem::transmute(value & 0x07) }
@@ -1842,7 +3756,12 @@ impl GetRxBufferStatus {
 /// get_packet_status_lora.rx_buf[4] = 162;
 /// assert_eq!(get_packet_status_lora.rssi_pkt(), -92);
 /// assert_eq!(get_packet_status_lora.snr_pkt(), -1);
+/// assert_eq!(get_packet_status_lora.snr_pkt_quarters(), -4);
 /// assert_eq!(get_packet_status_lora.signal_rssi_pkt(), -81);
+/// get_packet_status_lora.rx_buf[2] = 185;
+/// assert_eq!(get_packet_status_lora.rssi_pkt(), -92);
+/// assert_eq!(get_packet_status_lora.rssi_pkt_half_db(), -185);
+/// assert_eq!(get_packet_status_lora.signal_rssi_pkt_half_db(), -162);
 /// ```
 pub struct GetPacketStatusLora {
     pub tx_buf: [u8; 5],
@@ -1875,18 +3794,128 @@ impl GetPacketStatusLora {
     pub const fn snr_pkt(&self) -> i8 {
         (self.rx_buf[3] as i8) / 4
     }
+    /// SNR of the last packet in quarter dB, without the truncation of [`snr_pkt`](Self::snr_pkt).
+    #[inline]
+    pub const fn snr_pkt_quarters(&self) -> i16 {
+        self.rx_buf[3] as i8 as i16
+    }
     #[inline]
     pub const fn signal_rssi_pkt(&self) -> i8 {
         -((self.rx_buf[4] / 2) as i8)
     }
+    /// RSSI of the last packet in half dB, without the truncation of
+    /// [`rssi_pkt`](Self::rssi_pkt).
+    #[inline]
+    pub const fn rssi_pkt_half_db(&self) -> i16 {
+        -(self.rx_buf[2] as i16)
+    }
+    /// RSSI of the LoRa signal of the last packet in half dB, without the truncation of
+    /// [`signal_rssi_pkt`](Self::signal_rssi_pkt).
+    #[inline]
+    pub const fn signal_rssi_pkt_half_db(&self) -> i16 {
+        -(self.rx_buf[4] as i16)
+    }
+    /// Undecoded `RssiPkt`, `SnrPkt` and `SignalRssiPkt` bytes.
+    #[inline]
+    pub const fn raw(&self) -> [u8; 3] {
+        [self.rx_buf[2], self.rx_buf[3], self.rx_buf[4]]
+    }
 }
 
-/// # GetStatsLora command
-/// Returns the number of received packets, CRC errors, and header errors for LoRa packets.
+/// # GetPacketStatusGfsk command
+/// Gets the reception status and signal strength of the last received GFSK packet.
 ///
 /// ## Example
 /// ```
-/// use sx126x_spi_buffers::commands::GetStatsLora;
+/// use sx126x_spi_buffers::commands::{GetPacketStatusGfsk, RxStatus};
+///
+/// let mut get_packet_status_gfsk: GetPacketStatusGfsk = GetPacketStatusGfsk::new();
+/// assert_eq!(get_packet_status_gfsk.tx_buf, [0x14, 0, 0, 0, 0]);
+/// assert_eq!(get_packet_status_gfsk.rx_buf, [0; 5]);
+/// assert_eq!(get_packet_status_gfsk.descriptor().transfer_length, 5);
+///
+/// get_packet_status_gfsk.rx_buf[2] = 0x12;
+/// get_packet_status_gfsk.rx_buf[3] = 150;
+/// get_packet_status_gfsk.rx_buf[4] = 160;
+/// assert_eq!(
+///     get_packet_status_gfsk.rx_status(),
+///     RxStatus::new().with_crc_err(true).with_pkt_received(true)
+/// );
+/// assert_eq!(get_packet_status_gfsk.rssi_sync(), -75);
+/// assert_eq!(get_packet_status_gfsk.rssi_avg(), -80);
+/// ```
+pub struct GetPacketStatusGfsk {
+    pub tx_buf: [u8; 5],
+    pub rx_buf: [u8; 5],
+}
+impl GetPacketStatusGfsk {
+    const OPCODE: u8 = 0x14;
+
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            tx_buf: [Self::OPCODE, 0, 0, 0, 0],
+            rx_buf: [0; 5],
+        }
+    }
+    #[inline]
+    pub const fn descriptor(&self) -> SpiDescriptor {
+        SpiDescriptor {
+            tx_buf_ptr: self.tx_buf.as_ptr(),
+            rx_buf_ptr: self.rx_buf.as_ptr(),
+            transfer_length: 5,
+        }
+    }
+    #[inline]
+    pub const fn rx_status(&self) -> RxStatus {
+        RxStatus::from_bits(self.rx_buf[2])
+    }
+    /// RSSI when the sync word was detected.
+    #[inline]
+    pub const fn rssi_sync(&self) -> i8 {
+        -((self.rx_buf[3] / 2) as i8)
+    }
+    /// RSSI averaged over the packet.
+    #[inline]
+    pub const fn rssi_avg(&self) -> i8 {
+        -((self.rx_buf[4] / 2) as i8)
+    }
+    /// Undecoded `RxStatus`, `RssiSync` and `RssiAvg` bytes.
+    #[inline]
+    pub const fn raw(&self) -> [u8; 3] {
+        [self.rx_buf[2], self.rx_buf[3], self.rx_buf[4]]
+    }
+}
+/// Reception status of the last GFSK packet.
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+pub struct RxStatus {
+    #[bits(1)]
+    pub pkt_sent: bool,
+    #[bits(1)]
+    pub pkt_received: bool,
+    #[bits(1)]
+    pub abort_err: bool,
+    #[bits(1)]
+    pub length_err: bool,
+    #[bits(1)]
+    pub crc_err: bool,
+    #[bits(1)]
+    pub addr_err: bool,
+    #[bits(1)]
+    pub sync_err: bool,
+    #[bits(1)]
+    pub preamble_err: bool,
+}
+
+/// # GetStats command
+/// Returns the number of received packets, CRC errors, and header errors for LoRa packets or
+/// length errors for GFSK packets. The packet type `P` is [`Lora`] or [`Gfsk`], the accessors
+/// common to both are available on any `GetStats<P>`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{GetStats, GetStatsGfsk, GetStatsLora};
 ///
 /// let mut get_stats_lora: GetStatsLora = GetStatsLora::new();
 /// assert_eq!(get_stats_lora.tx_buf, [0x10, 0, 0, 0, 0, 0, 0, 0]);
@@ -1902,12 +3931,31 @@ impl GetPacketStatusLora {
 /// assert_eq!(get_stats_lora.nb_pkt_received(), 0x5118);
 /// assert_eq!(get_stats_lora.nb_pkt_crc_error(), 0x0315);
 /// assert_eq!(get_stats_lora.nb_pkt_header_err(), 0x5581);
+/// assert_eq!(get_stats_lora.raw(), [0x5118, 0x0315, 0x5581]);
+///
+/// let mut get_stats_gfsk: GetStatsGfsk = GetStatsGfsk::new();
+/// get_stats_gfsk.rx_buf[7] = 0x02;
+/// assert_eq!(get_stats_gfsk.nb_pkt_length_error(), 2);
+///
+/// fn packet_error_count<P>(get_stats: &GetStats<P>) -> u16 {
+///     get_stats.nb_pkt_crc_error()
+/// }
+/// assert_eq!(packet_error_count(&get_stats_lora), 0x0315);
 /// ```
-pub struct GetStatsLora {
+pub struct GetStats<P> {
     pub tx_buf: [u8; 8],
     pub rx_buf: [u8; 8],
+    packet_type: PhantomData<P>,
 }
-impl GetStatsLora {
+/// LoRa packet type of [`GetStats`].
+pub struct Lora;
+/// GFSK packet type of [`GetStats`].
+pub struct Gfsk;
+/// `GetStats` of LoRa packets.
+pub type GetStatsLora = GetStats<Lora>;
+/// `GetStats` of GFSK packets.
+pub type GetStatsGfsk = GetStats<Gfsk>;
+impl<P> GetStats<P> {
     const OPCODE: u8 = 0x10;
 
     #[inline]
@@ -1915,6 +3963,7 @@ impl GetStatsLora {
         Self {
             tx_buf: [Self::OPCODE, 0, 0, 0, 0, 0, 0, 0],
             rx_buf: [0; 8],
+            packet_type: PhantomData,
         }
     }
     #[inline]
@@ -1933,11 +3982,37 @@ impl GetStatsLora {
     pub const fn nb_pkt_crc_error(&self) -> u16 {
         (self.rx_buf[4] as u16) << 8 | (self.rx_buf[5]) as u16
     }
+    /// Undecoded counters, in response order.
+    #[inline]
+    pub const fn raw(&self) -> [u16; 3] {
+        [
+            self.nb_pkt_received(),
+            self.nb_pkt_crc_error(),
+            (self.rx_buf[6] as u16) << 8 | (self.rx_buf[7]) as u16,
+        ]
+    }
+    /// `GetStats` with `tx_buf` as sent, e.g. decoded from a capture.
+    #[inline]
+    pub(crate) const fn from_tx_buf(tx_buf: [u8; 8]) -> Self {
+        Self {
+            tx_buf,
+            rx_buf: [0; 8],
+            packet_type: PhantomData,
+        }
+    }
+}
+impl GetStats<Lora> {
     #[inline]
     pub const fn nb_pkt_header_err(&self) -> u16 {
         (self.rx_buf[6] as u16) << 8 | (self.rx_buf[7]) as u16
     }
 }
+impl GetStats<Gfsk> {
+    #[inline]
+    pub const fn nb_pkt_length_error(&self) -> u16 {
+        (self.rx_buf[6] as u16) << 8 | (self.rx_buf[7]) as u16
+    }
+}
 
 /// # ResetStats command
 /// Resets the number of packets received counters.
@@ -2015,7 +4090,30 @@ impl GetDeviceErrors {
     }
     #[inline]
     pub const fn op_error(&self) -> OpError {
-        OpError::from_bits((self.rx_buf[2] as u16) << 8 | self.rx_buf[3] as u16)
+        OpError::from_bits(self.raw())
+    }
+    /// Undecoded error register, including the reserved bits.
+    #[inline]
+    pub const fn raw(&self) -> u16 {
+        (self.rx_buf[2] as u16) << 8 | self.rx_buf[3] as u16
+    }
+    /// Device errors, rejecting reserved bits.
+    #[inline]
+    pub const fn try_op_error(&self) -> Result<OpError, InvalidResponse> {
+        // Bits 7 and 9 to 15
+        if self.rx_buf[2] & 0xFE != 0 {
+            return Err(InvalidResponse::Reserved {
+                index: 2,
+                value: self.rx_buf[2],
+            });
+        }
+        if self.rx_buf[3] & 0x80 != 0 {
+            return Err(InvalidResponse::Reserved {
+                index: 3,
+                value: self.rx_buf[3],
+            });
+        }
+        Ok(self.op_error())
     }
 }
 #[bitfield(u16)]
@@ -2042,6 +4140,88 @@ pub struct OpError {
     #[bits(7)]
     __: u8,
 }
+impl OpError {
+    /// Iterator over the set error flags, from the lowest bit.
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::commands::{OpError, OpErrorFlag};
+    ///
+    /// let op_error = OpError::new().with_pll_lock_err(true).with_pa_ramp_err(true);
+    /// let mut flags = op_error.iter();
+    /// assert_eq!(flags.next(), Some(OpErrorFlag::PllLock));
+    /// assert_eq!(flags.next(), Some(OpErrorFlag::PaRamp));
+    /// assert_eq!(flags.next(), None);
+    /// ```
+    #[inline]
+    pub const fn iter(self) -> OpErrorIter {
+        // Without the reserved bits 7 and 9 to 15
+        OpErrorIter(self.into_bits() & 0x017F)
+    }
+    /// Whether no error is set.
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.into_bits() & 0x017F == 0
+    }
+    /// Whether `xosc_start_err` is the only error. With a TCXO, it is raised at power up as the
+    /// oscillator is started before `SetDio3AsTcxoCtrl` enables the TCXO, and can be cleared and
+    /// ignored.
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::commands::OpError;
+    ///
+    /// assert!(OpError::new().with_xosc_start_err(true).is_only_xosc_start_err());
+    /// assert!(!OpError::new().with_xosc_start_err(true).with_pll_lock_err(true).is_only_xosc_start_err());
+    /// ```
+    #[inline]
+    pub const fn is_only_xosc_start_err(self) -> bool {
+        self.into_bits() & 0x017F == OpErrorFlag::XoscStart.mask().into_bits()
+    }
+}
+/// A single [`OpError`] flag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OpErrorFlag {
+    Rc64kCalib,
+    Rc13mCalib,
+    PllCalib,
+    AdcCalib,
+    ImgCalib,
+    XoscStart,
+    PllLock,
+    PaRamp,
+}
+impl OpErrorFlag {
+    /// Bit of the flag in the error register.
+    #[inline]
+    pub const fn bit(self) -> u8 {
+        match self {
+            Self::PaRamp => 8,
+            flag => flag as u8,
+        }
+    }
+    /// The flag as an [`OpError`] mask.
+    #[inline]
+    pub const fn mask(self) -> OpError {
+        OpError::from_bits(1 << self.bit())
+    }
+    /// Flag at `bit` of the error register, `None` for reserved bits.
+    #[inline]
+    pub const fn from_bit(bit: u8) -> Option<Self> {
+        Some(match bit {
+            0 => Self::Rc64kCalib,
+            1 => Self::Rc13mCalib,
+            2 => Self::PllCalib,
+            3 => Self::AdcCalib,
+            4 => Self::ImgCalib,
+            5 => Self::XoscStart,
+            6 => Self::PllLock,
+            8 => Self::PaRamp,
+            _ => return None,
+        })
+    }
+}
+flag_iter!(OpErrorIter: OpError => OpErrorFlag);
 
 /// # ClearDeviceErrors command
 /// Clears the error flags.
@@ -2079,9 +4259,68 @@ impl ClearDeviceErrors {
     }
 }
 
+/// Name of the command with `opcode`, without the packet type suffix of the commands sharing an
+/// opcode, e.g. `SetModulationParams` for `0x8B`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::opcode_name;
+///
+/// assert_eq!(opcode_name(0x86), Some("SetRfFrequency"));
+/// assert_eq!(opcode_name(0x8B), Some("SetModulationParams"));
+/// assert_eq!(opcode_name(0xFF), None);
+/// ```
+pub const fn opcode_name(opcode: u8) -> Option<&'static str> {
+    Some(match opcode {
+        0x00 => "ResetStats",
+        0x02 => "ClearIrqStatus",
+        0x07 => "ClearDeviceErrors",
+        0x08 => "SetDioIrqParams",
+        0x0D => "WriteRegister",
+        0x0E => "WriteBuffer",
+        0x10 => "GetStats",
+        0x11 => "GetPacketType",
+        0x12 => "GetIrqStatus",
+        0x13 => "GetRxBufferStatus",
+        0x14 => "GetPacketStatus",
+        0x15 => "GetRssiInst",
+        0x17 => "GetDeviceErrors",
+        0x1D => "ReadRegister",
+        0x1E => "ReadBuffer",
+        0x80 => "SetStandby",
+        0x82 => "SetRx",
+        0x83 => "SetTx",
+        0x84 => "SetSleep",
+        0x86 => "SetRfFrequency",
+        0x88 => "SetCadParams",
+        0x89 => "Calibrate",
+        0x8A => "SetPacketType",
+        0x8B => "SetModulationParams",
+        0x8C => "SetPacketParams",
+        0x8E => "SetTxParams",
+        0x8F => "SetBufferBaseAddress",
+        0x93 => "SetRxTxFallbackMode",
+        0x94 => "SetRxDutyCycle",
+        0x95 => "SetPaConfig",
+        0x96 => "SetRegulatorMode",
+        0x97 => "SetDio3AsTcxoCtrl",
+        0x98 => "CalibrateImage",
+        0x9D => "SetDio2AsRfSwitchCtrl",
+        0x9F => "StopTimerOnPreamble",
+        0xA0 => "SetLoraSymbNumTimeout",
+        0xC0 => "GetStatus",
+        0xC1 => "SetFs",
+        0xC5 => "SetCad",
+        0xD1 => "SetTxContinuousWave",
+        0xD2 => "SetTxInfinitePreamble",
+        _ => return None,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "nightly")]
     use crate::registers::LoraSyncWordMsb;
 
     #[test]
@@ -2105,12 +4344,125 @@ mod tests {
         assert_eq!(get_packet_type.packet_type(), PacketType::Lora);
     }
 
+    #[test]
+    fn test_try_parse_responses() {
+        let get_irq_status = GetIrqStatus::try_from_rx_buf(&[0xA2, 0xA4, 0x40, 0x02]).unwrap();
+        assert_eq!(
+            get_irq_status.try_irq_status(),
+            Ok(Irq::new().with_rx_done(true).with_lr_fhss_hop(true))
+        );
+        let get_irq_status = GetIrqStatus::try_from_rx_buf(&[0xA2, 0xA4, 0x80, 0x02]).unwrap();
+        assert_eq!(
+            get_irq_status.try_irq_status(),
+            Err(InvalidResponse::Reserved {
+                index: 2,
+                value: 0x80
+            })
+        );
+        assert_eq!(
+            GetIrqStatus::try_from_rx_buf(&[0xA2, 0xA4, 0x00]).err(),
+            Some(InvalidResponse::Length(InvalidLength {
+                len: 3,
+                expected: 4
+            }))
+        );
+        assert_eq!(
+            GetRssiInst::try_from_rx_buf(&[0xFF, 0xFF, 0xFF]).err(),
+            Some(InvalidResponse::Status(0xFF))
+        );
+        let get_packet_type = GetPacketType::try_from_rx_buf(&[0xA2, 0xA4, 0x02]).unwrap();
+        assert_eq!(
+            get_packet_type.try_packet_type(),
+            Err(InvalidResponse::Reserved {
+                index: 2,
+                value: 0x02
+            })
+        );
+        assert_eq!(
+            Status::try_from_bits(0x64).map(|status| status.chip_mode()),
+            Ok(ChipMode::Tx)
+        );
+        assert_eq!(
+            Status::try_from_bits(0x20).err(),
+            Some(InvalidResponse::Status(0x20))
+        );
+        assert_eq!(
+            Status::try_from_bits(0x02).err(),
+            Some(InvalidResponse::Status(0x02))
+        );
+    }
+
+    #[test]
+    fn test_status_fields_are_3_bits() {
+        for value in 0..8 {
+            let status = Status::from_bits(value << 4 | value << 1);
+            assert_eq!(status.chip_mode().raw(), value);
+            assert_eq!(status.command_status().raw(), value);
+        }
+        assert_eq!(
+            Status::from_bits(0x0E).command_status(),
+            CommandStatus::Reserved3
+        );
+    }
+
+    #[test]
+    fn test_descriptor_mut() {
+        let mut get_irq_status = GetIrqStatus::new();
+        let descriptor = Command::descriptor_mut(&mut get_irq_status);
+        assert_eq!(descriptor, get_irq_status.descriptor());
+        unsafe { descriptor.rx_buf_ptr.cast_mut().add(3).write(0x01) };
+        assert!(get_irq_status.irq_status().tx_done());
+    }
+
     #[test]
     fn test_set_rf_frequency() {
         static SET_RF_FREQUENCY: SetRfFrequency = SetRfFrequency::new(455_081_984);
         assert_eq!(SET_RF_FREQUENCY.tx_buf, [0x86, 0x1B, 0x20, 0, 0]);
     }
 
+    #[test]
+    fn test_optimal_pa_config_within_device_limits() {
+        for device in [
+            Device::Sx1261,
+            Device::Sx1262,
+            Device::Sx1268,
+            Device::Llcc68,
+        ] {
+            let (pa_config, power) =
+                SetPaConfig::optimal_for(device, device.max_output_power()).unwrap();
+            let [_, pa_duty_cycle, hp_max, device_sel, _] = pa_config.tx_buf;
+            assert_eq!(
+                SetPaConfig::try_new_for(device, pa_duty_cycle, hp_max).map(|c| c.tx_buf),
+                Ok(pa_config.tx_buf)
+            );
+            assert_eq!(device_sel, device.device_sel());
+            assert!(SetTxParams::try_new_for(device, power, RampTime::Ramp200U).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_default_device_limits() {
+        let max_power = DEFAULT_DEVICE.max_tx_power();
+        assert!(SetTxParams::try_new(max_power, RampTime::Ramp200U).is_ok());
+        assert_eq!(
+            SetTxParams::try_new(max_power + 1, RampTime::Ramp200U).err(),
+            Some(TxPowerOutOfRange {
+                device: DEFAULT_DEVICE,
+                power: max_power + 1
+            })
+        );
+        let (pa_config, _) = SetPaConfig::optimal(DEFAULT_DEVICE.max_output_power()).unwrap();
+        assert_eq!(pa_config.tx_buf[3], DEFAULT_DEVICE.device_sel());
+        let (min_hz, max_hz) = DEFAULT_DEVICE.freq_range_hz();
+        assert!(SetRfFrequency::try_from_hz(min_hz).is_ok());
+        assert_eq!(
+            SetRfFrequency::try_from_hz(max_hz + 1).err(),
+            Some(FrequencyOutOfRange {
+                freq_hz: max_hz + 1
+            })
+        );
+    }
+
     #[test]
     fn test_set_buffer_base_address() {
         static SET_BUFFER_BASE_ADDRESS: SetBufferBaseAddress =
@@ -2119,6 +4471,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "llcc68"))]
     fn test_set_mod_params() {
         static SET_MODULATION_PARAMS_LORA: SetModulationParamsLora =
             SetModulationParamsLora::new(Sf::Sf10, Bw::Bw125, Cr::Cr4_5, false);
@@ -2128,6 +4481,22 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "llcc68")]
+    #[should_panic(expected = "SF/BW combination unsupported by the LLCC68")]
+    fn test_set_mod_params_llcc68() {
+        let _ = SetModulationParamsLora::new_auto_ldro(Sf::Sf10, Bw::Bw125, Cr::Cr4_5);
+    }
+
+    #[test]
+    fn test_modulation_units() {
+        assert_eq!(Sf::Sf7.factor(), 7);
+        assert_eq!(Sf::Sf12.chips_per_symbol(), 4096);
+        assert_eq!(Bw::Bw62_50.hz(), 62_500);
+        assert_eq!((Cr::Cr4_7.numerator(), Cr::Cr4_7.denominator()), (4, 7));
+        assert_eq!(Cr::Cr4_8Li.denominator(), 8);
+    }
+
     #[test]
     fn test_set_packet_params() {
         static SET_PACKET_PARAMS: SetPacketParams =
@@ -2146,6 +4515,7 @@ mod tests {
         assert_eq!(SET_DIO_IRQ_PARAMS.tx_buf, [0x08, 0, 3, 0, 3, 0, 0, 0, 0]);
     }
 
+    #[cfg(feature = "nightly")]
     #[test]
     fn test_write_sync_word() {
         static WRITE_SYNC_WORD: WriteRegister = WriteRegister::new(LoraSyncWordMsb(0x14));
@@ -2170,6 +4540,7 @@ mod tests {
         assert_eq!(SET_DIO2_RF_SWITCH_CTRL.tx_buf, [0x9D, 1]);
     }
 
+    #[cfg(feature = "nightly")]
     #[test]
     fn test_write_buffer() {
         static WRITE_BUFFER: WriteBuffer<5> = WriteBuffer::new(0x00, [0x00, 0x00, 0x00]);
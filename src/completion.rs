@@ -0,0 +1,51 @@
+//! Transfer completion flag, set from the SPI/DMA transfer complete interrupt and taken by the
+//! application.
+//!
+//! Taking the flag needs an atomic swap. With the `portable-atomic` feature it is provided by
+//! `portable-atomic`, so the flag is also available on cores without compare and swap, e.g.
+//! thumbv6m, AVR and MSP430 (see the `portable-atomic` documentation for the cfg to select on
+//! those targets).
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::completion::CompletionFlag;
+//!
+//! static TRANSFER_DONE: CompletionFlag = CompletionFlag::new();
+//!
+//! // In the transfer complete interrupt
+//! TRANSFER_DONE.set();
+//!
+//! assert!(TRANSFER_DONE.take());
+//! assert!(!TRANSFER_DONE.take());
+//! ```
+#![allow(clippy::new_without_default)]
+
+#[cfg(not(feature = "portable-atomic"))]
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "portable-atomic")]
+use portable_atomic::{AtomicBool, Ordering};
+
+/// # CompletionFlag
+/// Set once a transfer has completed, cleared when taken.
+pub struct CompletionFlag(AtomicBool);
+impl CompletionFlag {
+    #[inline]
+    pub const fn new() -> Self {
+        Self(AtomicBool::new(false))
+    }
+    /// Marks the transfer as complete.
+    #[inline]
+    pub fn set(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+    /// Whether the transfer has completed, without clearing the flag.
+    #[inline]
+    pub fn is_set(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+    /// Clears the flag, returning whether the transfer had completed.
+    #[inline]
+    pub fn take(&self) -> bool {
+        self.0.swap(false, Ordering::Acquire)
+    }
+}
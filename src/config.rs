@@ -0,0 +1,434 @@
+//! High-level radio configuration, expanded into an ordered initialization sequence.
+
+use super::DEFAULT_DEVICE;
+use super::commands::{
+    Bw, Calibrate, CalibrateImage, CalibrationSetting, Cr, Device, HeaderType,
+    ImageCalibrationBand, InvertIq, Irq, PacketType, RampTime, SetBufferBaseAddress,
+    SetDio2AsRfSwitchCtrl, SetDio3AsTcxoCtrl, SetDioIrqParams, SetModulationParamsLora,
+    SetPaConfig, SetPacketParams, SetPacketType, SetRegulatorMode, SetRfFrequency, SetStandby,
+    SetTxParams, Sf, SpiDescriptor, StdbyConfig, TcxoVoltage, WriteRegisters,
+};
+use super::regions::{PowerLimitExceeded, Region};
+use super::sequences::LoraNetwork;
+
+/// # RadioConfig
+/// LoRa radio configuration. Start from `new()` and override the defaults with the `with_*`
+/// methods, then call `init()` to get the command buffers in the order required by the
+/// datasheet.
+///
+/// Defaults:
+/// - LDO regulator, no TCXO, DIO2 not used as RF switch control
+/// - image calibration for the band of `rf_freq`, if it is in one of the
+///   [`ImageCalibrationBand`]s
+/// - SF7, 125 kHz, CR 4/5, no low data rate optimization
+/// - maximum output power of the [`DEFAULT_DEVICE`](crate::DEFAULT_DEVICE) with 200 us ramp
+///   time, see `with_device()`: `SetPaConfig(0x04, 0x07, 0)` and 22 dBm for +22 dBm on the
+///   SX1262
+/// - 8 symbol preamble, variable length header, 255 byte payload, CRC on, standard IQ
+/// - private network sync word
+/// - `TxDone`, `RxDone` and `Timeout` IRQs enabled and mapped to DIO1
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{
+///     commands::{Bw, Cr, Sf, TcxoVoltage},
+///     config::{RadioConfig, RadioInit},
+///     sequences::LoraNetwork,
+/// };
+///
+/// static INIT: RadioInit = RadioConfig::new(0x3640_0000)
+///     .with_tcxo(TcxoVoltage::V1_8, 320)
+///     .with_dio2_as_rf_switch(true)
+///     .with_modulation(Sf::Sf9, Bw::Bw125, Cr::Cr4_5)
+///     .with_network(LoraNetwork::Public)
+///     .init();
+/// assert_eq!(INIT.set_rf_frequency.tx_buf, [0x86, 0x36, 0x40, 0, 0]);
+/// assert_eq!(INIT.set_modulation_params.tx_buf, [0x8B, 0x09, 0x04, 0x01, 0]);
+/// assert_eq!(INIT.write_sync_word.tx_buf, [0x0D, 0x07, 0x40, 0x34, 0x44]);
+/// assert_eq!(INIT.calibrate_image.as_ref().unwrap().tx_buf, [0x98, 0xD7, 0xDB]);
+/// assert_eq!(INIT.descriptors().count(), 15);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RadioConfig {
+    pub rf_freq: u32,
+    pub dc_dc: bool,
+    pub tcxo: Option<(TcxoVoltage, u32)>,
+    pub dio2_as_rf_switch: bool,
+    pub sf: Sf,
+    pub bw: Bw,
+    pub cr: Cr,
+    pub low_data_rate_optimize: bool,
+    pub pa_duty_cycle: u8,
+    pub hp_max: u8,
+    pub device_sel: u8,
+    pub power: u8,
+    pub ramp_time: RampTime,
+    pub preamble_length: u16,
+    pub header_type: HeaderType,
+    pub payload_length: u8,
+    pub crc: bool,
+    pub invert_iq: InvertIq,
+    pub network: LoraNetwork,
+    pub irq_mask: Irq,
+    pub dio1_mask: Irq,
+    pub dio2_mask: Irq,
+    pub dio3_mask: Irq,
+}
+impl RadioConfig {
+    /// Default configuration at the RF frequency `rf_freq`, in the same units as
+    /// [`SetRfFrequency`].
+    #[inline]
+    pub const fn new(rf_freq: u32) -> Self {
+        let irq = Irq::new()
+            .with_tx_done(true)
+            .with_rx_done(true)
+            .with_timeout(true);
+        Self {
+            rf_freq,
+            dc_dc: false,
+            tcxo: None,
+            dio2_as_rf_switch: false,
+            sf: Sf::Sf7,
+            bw: Bw::Bw125,
+            cr: Cr::Cr4_5,
+            low_data_rate_optimize: false,
+            pa_duty_cycle: 0x04,
+            hp_max: 0x07,
+            device_sel: 0,
+            power: 22,
+            ramp_time: RampTime::Ramp200U,
+            preamble_length: 8,
+            header_type: HeaderType::VariableLength,
+            payload_length: 255,
+            crc: true,
+            invert_iq: InvertIq::Standard,
+            network: LoraNetwork::Private,
+            irq_mask: irq,
+            dio1_mask: irq,
+            dio2_mask: Irq::new(),
+            dio3_mask: Irq::new(),
+        }
+        .with_device(DEFAULT_DEVICE)
+    }
+    #[inline]
+    pub const fn with_dc_dc(mut self, dc_dc: bool) -> Self {
+        self.dc_dc = dc_dc;
+        self
+    }
+    /// Powers a TCXO from DIO3 with `delay` in units of 15.625 us.
+    #[inline]
+    pub const fn with_tcxo(mut self, voltage: TcxoVoltage, delay: u32) -> Self {
+        self.tcxo = Some((voltage, delay));
+        self
+    }
+    #[inline]
+    pub const fn with_dio2_as_rf_switch(mut self, enable: bool) -> Self {
+        self.dio2_as_rf_switch = enable;
+        self
+    }
+    /// Also enables low data rate optimization if it is required for `sf` and `bw`.
+    ///
+    /// ## Panics
+    /// With the `llcc68` feature, if the LLCC68 does not support `sf` at `bw`.
+    #[inline]
+    pub const fn with_modulation(mut self, sf: Sf, bw: Bw, cr: Cr) -> Self {
+        #[cfg(feature = "llcc68")]
+        assert!(
+            Device::Llcc68.supports(sf, bw),
+            "SF/BW combination unsupported by the LLCC68"
+        );
+        self.sf = sf;
+        self.bw = bw;
+        self.cr = cr;
+        self.low_data_rate_optimize = SetModulationParamsLora::ldro_required(sf, bw);
+        self
+    }
+    #[inline]
+    pub const fn with_low_data_rate_optimize(mut self, enable: bool) -> Self {
+        self.low_data_rate_optimize = enable;
+        self
+    }
+    /// Sets the PA config and TX power for the maximum output power of `device`, e.g.
+    /// `SetPaConfig(0x06, 0x00, 1)` and 14 dBm for +15 dBm on SX1261.
+    #[inline]
+    pub const fn with_device(self, device: Device) -> Self {
+        match SetPaConfig::optimal_for(device, device.max_output_power()) {
+            Some((set_pa_config, power)) => self
+                .with_pa_config(
+                    set_pa_config.tx_buf[1],
+                    set_pa_config.tx_buf[2],
+                    set_pa_config.tx_buf[3],
+                )
+                .with_tx_params(power as u8, self.ramp_time),
+            // `optimal()` covers the maximum output power of every device
+            None => self,
+        }
+    }
+    #[inline]
+    pub const fn with_pa_config(mut self, pa_duty_cycle: u8, hp_max: u8, device_sel: u8) -> Self {
+        self.pa_duty_cycle = pa_duty_cycle;
+        self.hp_max = hp_max;
+        self.device_sel = device_sel;
+        self
+    }
+    #[inline]
+    pub const fn with_tx_params(mut self, power: u8, ramp_time: RampTime) -> Self {
+        self.power = power;
+        self.ramp_time = ramp_time;
+        self
+    }
+    /// Sets the TX params, rejecting a TX power above the limit of `region`.
+    #[inline]
+    pub const fn with_tx_params_for_region(
+        self,
+        power: i8,
+        ramp_time: RampTime,
+        region: Region,
+        antenna_gain: i8,
+    ) -> Result<Self, PowerLimitExceeded> {
+        match region.check_tx_power(power, antenna_gain) {
+            Ok(power) => Ok(self.with_tx_params(power as u8, ramp_time)),
+            Err(exceeded) => Err(exceeded),
+        }
+    }
+    #[inline]
+    pub const fn with_packet_params(
+        mut self,
+        preamble_length: u16,
+        header_type: HeaderType,
+        payload_length: u8,
+        crc: bool,
+        invert_iq: InvertIq,
+    ) -> Self {
+        self.preamble_length = preamble_length;
+        self.header_type = header_type;
+        self.payload_length = payload_length;
+        self.crc = crc;
+        self.invert_iq = invert_iq;
+        self
+    }
+    #[inline]
+    pub const fn with_network(mut self, network: LoraNetwork) -> Self {
+        self.network = network;
+        self
+    }
+    #[inline]
+    pub const fn with_irq(
+        mut self,
+        irq_mask: Irq,
+        dio1_mask: Irq,
+        dio2_mask: Irq,
+        dio3_mask: Irq,
+    ) -> Self {
+        self.irq_mask = irq_mask;
+        self.dio1_mask = dio1_mask;
+        self.dio2_mask = dio2_mask;
+        self.dio3_mask = dio3_mask;
+        self
+    }
+    /// Expands the configuration into its initialization commands.
+    ///
+    /// ## Panics
+    /// With the `llcc68` feature, if the LLCC68 does not support `sf` at `bw`.
+    #[inline]
+    pub const fn init(&self) -> RadioInit {
+        RadioInit {
+            set_standby: SetStandby::new(StdbyConfig::StdbyRc),
+            set_regulator_mode: SetRegulatorMode::new(self.dc_dc),
+            set_dio3_as_tcxo_ctrl: match self.tcxo {
+                Some((voltage, delay)) => Some(SetDio3AsTcxoCtrl::new(voltage, delay)),
+                None => None,
+            },
+            calibrate: Calibrate::new(CalibrationSetting::from_bits(0x7F)),
+            calibrate_image: match ImageCalibrationBand::from_rf_freq(self.rf_freq) {
+                Some(band) => Some(CalibrateImage::for_band(band)),
+                None => None,
+            },
+            set_dio2_as_rf_switch_ctrl: SetDio2AsRfSwitchCtrl::new(self.dio2_as_rf_switch),
+            set_packet_type: SetPacketType::new(PacketType::Lora),
+            set_rf_frequency: SetRfFrequency::new(self.rf_freq),
+            set_pa_config: SetPaConfig::new(self.pa_duty_cycle, self.hp_max, self.device_sel),
+            set_tx_params: SetTxParams::new(self.power, self.ramp_time),
+            set_buffer_base_address: SetBufferBaseAddress::new(0x00, 0x00),
+            set_modulation_params: SetModulationParamsLora::new(
+                self.sf,
+                self.bw,
+                self.cr,
+                self.low_data_rate_optimize,
+            ),
+            set_packet_params: SetPacketParams::new(
+                self.preamble_length,
+                self.header_type,
+                self.payload_length,
+                self.crc,
+                self.invert_iq,
+            ),
+            write_sync_word: self.network.write_sync_word(),
+            set_dio_irq_params: SetDioIrqParams::new(
+                self.irq_mask,
+                self.dio1_mask,
+                self.dio2_mask,
+                self.dio3_mask,
+            ),
+        }
+    }
+}
+
+/// # RadioInit
+/// Initialization commands generated by [`RadioConfig::init`]. `descriptors()` yields them in
+/// order, starting from `STDBY_RC`:
+/// 1. Regulator mode, then TCXO control followed by a full calibration, since the calibration
+///    at power up fails without the TCXO running. The image calibration for the band of the RF
+///    frequency follows, replacing the 902-928 MHz calibration of the full calibration.
+/// 2. DIO2 RF switch control.
+/// 3. Packet type, which must be set before any RF, modulation or packet parameters.
+/// 4. RF frequency, PA config and TX params, buffer base addresses, modulation and packet
+///    parameters, sync word.
+/// 5. IRQ configuration.
+pub struct RadioInit {
+    pub set_standby: SetStandby,
+    pub set_regulator_mode: SetRegulatorMode,
+    pub set_dio3_as_tcxo_ctrl: Option<SetDio3AsTcxoCtrl>,
+    pub calibrate: Calibrate,
+    /// `None` if the RF frequency is outside the [`ImageCalibrationBand`]s.
+    pub calibrate_image: Option<CalibrateImage>,
+    pub set_dio2_as_rf_switch_ctrl: SetDio2AsRfSwitchCtrl,
+    pub set_packet_type: SetPacketType,
+    pub set_rf_frequency: SetRfFrequency,
+    pub set_pa_config: SetPaConfig,
+    pub set_tx_params: SetTxParams,
+    pub set_buffer_base_address: SetBufferBaseAddress,
+    pub set_modulation_params: SetModulationParamsLora,
+    pub set_packet_params: SetPacketParams,
+    pub write_sync_word: WriteRegisters<5>,
+    pub set_dio_irq_params: SetDioIrqParams,
+}
+impl RadioInit {
+    pub fn descriptors(&self) -> impl Iterator<Item = SpiDescriptor> + '_ {
+        [
+            Some(self.set_standby.descriptor()),
+            Some(self.set_regulator_mode.descriptor()),
+            self.set_dio3_as_tcxo_ctrl
+                .as_ref()
+                .map(SetDio3AsTcxoCtrl::descriptor),
+            Some(self.calibrate.descriptor()),
+            self.calibrate_image
+                .as_ref()
+                .map(CalibrateImage::descriptor),
+            Some(self.set_dio2_as_rf_switch_ctrl.descriptor()),
+            Some(self.set_packet_type.descriptor()),
+            Some(self.set_rf_frequency.descriptor()),
+            Some(self.set_pa_config.descriptor()),
+            Some(self.set_tx_params.descriptor()),
+            Some(self.set_buffer_base_address.descriptor()),
+            Some(self.set_modulation_params.descriptor()),
+            Some(self.set_packet_params.descriptor()),
+            Some(self.write_sync_word.descriptor()),
+            Some(self.set_dio_irq_params.descriptor()),
+        ]
+        .into_iter()
+        .flatten()
+    }
+    /// Descriptors restoring the configuration after a sleep entered with
+    /// `SetSleep::new(warm_start)`:
+    /// - cold start: the full bring-up of [`descriptors()`](Self::descriptors), since the
+    ///   configuration and the calibration are lost.
+    /// - warm start: only `SetStandby(STDBY_RC)`, the configuration and the calibration being
+    ///   retained. Registers outside the retention list, e.g. the RX boosted gain, are restored
+    ///   separately with [`SleepRetention`](super::sequences::SleepRetention).
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::config::RadioConfig;
+    ///
+    /// let init = RadioConfig::new(0x3640_0000).init();
+    /// assert_eq!(init.restart_descriptors(false).count(), 14);
+    /// assert!(init.restart_descriptors(true).eq([init.set_standby.descriptor()]));
+    /// ```
+    pub fn restart_descriptors(
+        &self,
+        warm_start: bool,
+    ) -> impl Iterator<Item = SpiDescriptor> + '_ {
+        let len = if warm_start { 1 } else { usize::MAX };
+        self.descriptors().take(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_init_order() {
+        let init = RadioConfig::new(0x3640_0000).init();
+        let opcodes = init
+            .descriptors()
+            .map(|descriptor| unsafe { *descriptor.tx_buf_ptr });
+        assert!(opcodes.eq([
+            0x80, 0x96, 0x89, 0x98, 0x9D, 0x8A, 0x86, 0x95, 0x8E, 0x8F, 0x8B, 0x8C, 0x0D, 0x08
+        ]));
+        assert_eq!(init.calibrate.tx_buf, [0x89, 0x7F]);
+        assert_eq!(init.calibrate_image.unwrap().tx_buf, [0x98, 0xD7, 0xDB]);
+        assert!(RadioConfig::new(0).init().calibrate_image.is_none());
+        assert_eq!(init.set_packet_params.tx_buf, [0x8C, 0, 8, 0, 255, 1, 0]);
+        assert_eq!(
+            init.set_dio_irq_params.tx_buf,
+            [0x08, 0x02, 0x03, 0x02, 0x03, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_init_tcxo() {
+        let init = RadioConfig::new(0x3640_0000)
+            .with_tcxo(TcxoVoltage::V1_8, 320)
+            .init();
+        let descriptors: [SpiDescriptor; 4] = core::array::from_fn({
+            let mut descriptors = init.descriptors();
+            move |_| descriptors.next().unwrap()
+        });
+        assert_eq!(
+            unsafe { core::slice::from_raw_parts(descriptors[2].tx_buf_ptr, 5) },
+            [0x97, 0x02, 0, 0x01, 0x40]
+        );
+        assert_eq!(unsafe { *descriptors[3].tx_buf_ptr }, 0x89);
+    }
+
+    #[test]
+    #[cfg(not(feature = "llcc68"))]
+    fn test_modulation_ldro() {
+        let config = RadioConfig::new(0x3640_0000).with_modulation(Sf::Sf12, Bw::Bw125, Cr::Cr4_5);
+        assert!(config.low_data_rate_optimize);
+        let config = config.with_modulation(Sf::Sf9, Bw::Bw125, Cr::Cr4_5);
+        assert!(!config.low_data_rate_optimize);
+    }
+
+    #[test]
+    fn test_with_device() {
+        let init = RadioConfig::new(0x3640_0000)
+            .with_device(Device::Sx1261)
+            .init();
+        assert_eq!(init.set_pa_config.tx_buf, [0x95, 0x06, 0x00, 0x01, 0x01]);
+        assert_eq!(init.set_tx_params.tx_buf, [0x8E, 14, 0x04]);
+        assert_eq!(
+            RadioConfig::new(0x3640_0000).with_device(DEFAULT_DEVICE),
+            RadioConfig::new(0x3640_0000)
+        );
+    }
+
+    #[test]
+    fn test_tx_params_for_region() {
+        let config = RadioConfig::new(0x3640_0000)
+            .with_tx_params_for_region(14, RampTime::Ramp40U, Region::Eu868, 0)
+            .unwrap();
+        assert_eq!(config.init().set_tx_params.tx_buf, [0x8E, 14, 0x02]);
+        assert_eq!(
+            RadioConfig::new(0x3640_0000)
+                .with_tx_params_for_region(22, RampTime::Ramp40U, Region::Eu868, 0)
+                .err(),
+            Some(PowerLimitExceeded {
+                requested: 22,
+                max: 16
+            })
+        );
+    }
+}
@@ -0,0 +1,281 @@
+//! Debugging helpers.
+#![allow(clippy::new_without_default)]
+
+use super::commands::{ReadRegister, ReadRegisters, SpiDescriptor};
+use super::registers::{
+    CrcInitialValue, Dio3OutputVoltageControl, DioInputEnable, DioOutputEnable, EventMask,
+    FreqErrorIndicator0, IqPolaritySetup, LoraSyncWordMsb, LrFhssControl, NodeAddress,
+    OcpConfiguration, RandomNumberGen0, RegisterBlock, RegisterValue, RtcControl, RxGain,
+    RxGainRetention0, TxClampConfig, TxModulation, WhiteningInitialValueMsb, XtaTrim,
+};
+#[cfg(feature = "stm32wl")]
+use super::stm32wl::{SmpsControl0, SmpsControl2};
+use core::fmt;
+
+/// # RegisterDump
+/// Reads every register known to the crate, grouped into one transfer per contiguous range.
+/// Once all `descriptors()` have completed, `values()` returns the register contents and the
+/// `Display` implementation prints one register per line. The STM32WL SMPS registers are
+/// included with the `stm32wl` feature.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::debug::RegisterDump;
+///
+/// let mut dump: RegisterDump = RegisterDump::new();
+/// assert_eq!(dump.descriptors().len(), RegisterDump::READS);
+/// assert_eq!(dump.lora_sync_word.tx_buf, [0x1D, 0x07, 0x40, 0, 0, 0]);
+///
+/// dump.lora_sync_word.rx_buf[4..6].copy_from_slice(&[0x34, 0x44]);
+/// let values = dump.values();
+/// let sync_word_msb = values.iter().find(|register| register.address == 0x0740).unwrap();
+/// assert_eq!(sync_word_msb.value, 0x34);
+///
+/// let mut out = String::new();
+/// core::fmt::write(&mut out, format_args!("{dump}")).unwrap();
+/// assert!(out.contains("0x0740 LoraSyncWordMsb: 0x34\n0x0741 LoraSyncWordLsb: 0x44\n"));
+/// ```
+pub struct RegisterDump {
+    pub rx_gain_retention: ReadRegisters<7>,
+    pub lr_fhss: ReadRegisters<7>,
+    pub dio_output_enable: ReadRegister<DioOutputEnable>,
+    pub dio_control: ReadRegisters<7>,
+    pub whitening: ReadRegisters<6>,
+    pub gfsk_crc_sync_word: ReadRegisters<16>,
+    pub gfsk_addresses: ReadRegisters<6>,
+    pub iq_polarity: ReadRegister<IqPolaritySetup>,
+    pub lora_sync_word: ReadRegisters<6>,
+    pub freq_error: ReadRegisters<7>,
+    pub random_number: ReadRegisters<8>,
+    pub tx_modulation: ReadRegister<TxModulation>,
+    pub rx_gain: ReadRegister<RxGain>,
+    pub tx_clamp: ReadRegister<TxClampConfig>,
+    pub ocp: ReadRegister<OcpConfiguration>,
+    pub rtc_control: ReadRegister<RtcControl>,
+    pub xtal_trim: ReadRegisters<6>,
+    #[cfg(feature = "stm32wl")]
+    pub smps_control_0: ReadRegister<SmpsControl0>,
+    pub dio3_output_voltage: ReadRegister<Dio3OutputVoltageControl>,
+    #[cfg(feature = "stm32wl")]
+    pub smps_control_2: ReadRegister<SmpsControl2>,
+    pub event_mask: ReadRegister<EventMask>,
+}
+impl RegisterDump {
+    /// Number of transfers of `descriptors()`.
+    pub const READS: usize = if cfg!(feature = "stm32wl") { 21 } else { 19 };
+    /// Number of registers of `values()`.
+    pub const REGISTERS: usize = Self::NAMES.len();
+
+    const NAMES: [&'static str; if cfg!(feature = "stm32wl") { 47 } else { 45 }] = [
+        "RxGainRetention0",
+        "RxGainRetention1",
+        "RxGainRetention2",
+        "LrFhssControl",
+        "LrFhssPacketLength",
+        "LrFhssNumHops",
+        "DioOutputEnable",
+        "DioInputEnable",
+        "DioPullUpControl",
+        "DioPullDownControl",
+        "WhiteningInitialValueMsb",
+        "WhiteningInitialValueLsb",
+        "CrcInitialValueMsb",
+        "CrcInitialValueLsb",
+        "CrcPolynomialMsb",
+        "CrcPolynomialLsb",
+        "GfskSyncWord0",
+        "GfskSyncWord1",
+        "GfskSyncWord2",
+        "GfskSyncWord3",
+        "GfskSyncWord4",
+        "GfskSyncWord5",
+        "GfskSyncWord6",
+        "GfskSyncWord7",
+        "NodeAddress",
+        "BroadcastAddress",
+        "IqPolaritySetup",
+        "LoraSyncWordMsb",
+        "LoraSyncWordLsb",
+        "FreqErrorIndicator0",
+        "FreqErrorIndicator1",
+        "FreqErrorIndicator2",
+        "RandomNumberGen0",
+        "RandomNumberGen1",
+        "RandomNumberGen2",
+        "RandomNumberGen3",
+        "TxModulation",
+        "RxGain",
+        "TxClampConfig",
+        "OcpConfiguration",
+        "RtcControl",
+        "XtaTrim",
+        "XtbTrim",
+        #[cfg(feature = "stm32wl")]
+        "SmpsControl0",
+        "Dio3OutputVoltageControl",
+        #[cfg(feature = "stm32wl")]
+        "SmpsControl2",
+        "EventMask",
+    ];
+
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            rx_gain_retention: ReadRegisters::<7>::new::<RxGainRetention0>(),
+            lr_fhss: ReadRegisters::<7>::new::<LrFhssControl>(),
+            dio_output_enable: ReadRegister::new(),
+            dio_control: ReadRegisters::<7>::new::<DioInputEnable>(),
+            whitening: ReadRegisters::<6>::new::<WhiteningInitialValueMsb>(),
+            gfsk_crc_sync_word: ReadRegisters::<16>::at_address(CrcInitialValue::ADDRESS),
+            gfsk_addresses: ReadRegisters::<6>::new::<NodeAddress>(),
+            iq_polarity: ReadRegister::new(),
+            lora_sync_word: ReadRegisters::<6>::new::<LoraSyncWordMsb>(),
+            freq_error: ReadRegisters::<7>::new::<FreqErrorIndicator0>(),
+            random_number: ReadRegisters::<8>::new::<RandomNumberGen0>(),
+            tx_modulation: ReadRegister::new(),
+            rx_gain: ReadRegister::new(),
+            tx_clamp: ReadRegister::new(),
+            ocp: ReadRegister::new(),
+            rtc_control: ReadRegister::new(),
+            xtal_trim: ReadRegisters::<6>::new::<XtaTrim>(),
+            #[cfg(feature = "stm32wl")]
+            smps_control_0: ReadRegister::new(),
+            dio3_output_voltage: ReadRegister::new(),
+            #[cfg(feature = "stm32wl")]
+            smps_control_2: ReadRegister::new(),
+            event_mask: ReadRegister::new(),
+        }
+    }
+    #[inline]
+    pub const fn descriptors(&self) -> [SpiDescriptor; Self::READS] {
+        [
+            self.rx_gain_retention.descriptor(),
+            self.lr_fhss.descriptor(),
+            self.dio_output_enable.descriptor(),
+            self.dio_control.descriptor(),
+            self.whitening.descriptor(),
+            self.gfsk_crc_sync_word.descriptor(),
+            self.gfsk_addresses.descriptor(),
+            self.iq_polarity.descriptor(),
+            self.lora_sync_word.descriptor(),
+            self.freq_error.descriptor(),
+            self.random_number.descriptor(),
+            self.tx_modulation.descriptor(),
+            self.rx_gain.descriptor(),
+            self.tx_clamp.descriptor(),
+            self.ocp.descriptor(),
+            self.rtc_control.descriptor(),
+            self.xtal_trim.descriptor(),
+            #[cfg(feature = "stm32wl")]
+            self.smps_control_0.descriptor(),
+            self.dio3_output_voltage.descriptor(),
+            #[cfg(feature = "stm32wl")]
+            self.smps_control_2.descriptor(),
+            self.event_mask.descriptor(),
+        ]
+    }
+    /// Register values in the order of the register names.
+    #[allow(clippy::indexing_slicing)]
+    pub fn values(&self) -> [RegisterValue; Self::REGISTERS] {
+        let reads: [(&[u8], &[u8]); Self::READS] = [
+            (
+                &self.rx_gain_retention.tx_buf,
+                &self.rx_gain_retention.rx_buf,
+            ),
+            (&self.lr_fhss.tx_buf, &self.lr_fhss.rx_buf),
+            (
+                &self.dio_output_enable.tx_buf,
+                &self.dio_output_enable.rx_buf,
+            ),
+            (&self.dio_control.tx_buf, &self.dio_control.rx_buf),
+            (&self.whitening.tx_buf, &self.whitening.rx_buf),
+            (
+                &self.gfsk_crc_sync_word.tx_buf,
+                &self.gfsk_crc_sync_word.rx_buf,
+            ),
+            (&self.gfsk_addresses.tx_buf, &self.gfsk_addresses.rx_buf),
+            (&self.iq_polarity.tx_buf, &self.iq_polarity.rx_buf),
+            (&self.lora_sync_word.tx_buf, &self.lora_sync_word.rx_buf),
+            (&self.freq_error.tx_buf, &self.freq_error.rx_buf),
+            (&self.random_number.tx_buf, &self.random_number.rx_buf),
+            (&self.tx_modulation.tx_buf, &self.tx_modulation.rx_buf),
+            (&self.rx_gain.tx_buf, &self.rx_gain.rx_buf),
+            (&self.tx_clamp.tx_buf, &self.tx_clamp.rx_buf),
+            (&self.ocp.tx_buf, &self.ocp.rx_buf),
+            (&self.rtc_control.tx_buf, &self.rtc_control.rx_buf),
+            (&self.xtal_trim.tx_buf, &self.xtal_trim.rx_buf),
+            #[cfg(feature = "stm32wl")]
+            (&self.smps_control_0.tx_buf, &self.smps_control_0.rx_buf),
+            (
+                &self.dio3_output_voltage.tx_buf,
+                &self.dio3_output_voltage.rx_buf,
+            ),
+            #[cfg(feature = "stm32wl")]
+            (&self.smps_control_2.tx_buf, &self.smps_control_2.rx_buf),
+            (&self.event_mask.tx_buf, &self.event_mask.rx_buf),
+        ];
+        let mut values = [RegisterValue {
+            address: 0,
+            value: 0,
+        }; Self::REGISTERS];
+        let mut i = 0;
+        for (tx_buf, rx_buf) in reads {
+            let address = (tx_buf[1] as u16) << 8 | tx_buf[2] as u16;
+            for (offset, value) in rx_buf[4..].iter().enumerate() {
+                values[i] = RegisterValue {
+                    address: address + offset as u16,
+                    value: *value,
+                };
+                i += 1;
+            }
+        }
+        values
+    }
+}
+impl fmt::Display for RegisterDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, register) in Self::NAMES.iter().zip(self.values()) {
+            writeln!(
+                f,
+                "{:#06X} {}: {:#04X}",
+                register.address, name, register.value
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Addresses of the `Register` and `RegisterBlock` impls declared in `source`.
+    fn declared_addresses(source: &str) -> impl Iterator<Item = u16> + '_ {
+        source.lines().filter_map(|line| {
+            let (_, address) = line
+                .split_once("const ADDRESS: u16 = 0x")
+                .or_else(|| line.split_once("): 0x"))?;
+            u16::from_str_radix(address.get(..4)?, 16).ok()
+        })
+    }
+
+    #[test]
+    fn test_register_dump_covers_every_register() {
+        let dump = RegisterDump::new();
+        let values = dump.values();
+        let sources = [
+            include_str!("registers.rs"),
+            #[cfg(feature = "stm32wl")]
+            include_str!("stm32wl.rs"),
+        ];
+        let mut count = 0;
+        for address in sources.into_iter().flat_map(declared_addresses) {
+            assert!(
+                values.iter().any(|register| register.address == address),
+                "register {address:#06X} is not dumped"
+            );
+            count += 1;
+        }
+        assert!(count >= 35, "only {count} registers found");
+    }
+}
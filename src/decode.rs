@@ -0,0 +1,354 @@
+//! Parsing of captured MOSI bytes back into typed commands, for protocol sniffing tools and
+//! round-trip tests of the command buffers.
+//!
+//! [`decode`] parses the bytes of one transfer, i.e. one NSS low period. [`Decoder`] splits a
+//! capture without NSS boundaries using the length of each command. The lengths of
+//! `SetModulationParams` and `SetPacketParams` depend on the packet type, which the decoder
+//! follows from the `SetPacketType` commands of the capture, starting from GFSK as after reset.
+//! Register and data buffer accesses have no fixed length and take the rest of the capture, so
+//! captures containing them must be split at NSS and passed to [`decode`].
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::{
+//!     commands::{PacketType, SetPacketType, SetRfFrequency, SetStandby, StdbyConfig},
+//!     decode::{self, DecodedCommand, Decoder},
+//! };
+//!
+//! let set_rf_frequency = SetRfFrequency::from_hz(868_100_000);
+//! match decode::decode(&set_rf_frequency.tx_buf) {
+//!     Ok(DecodedCommand::SetRfFrequency(command)) => {
+//!         assert_eq!(command.tx_buf, set_rf_frequency.tx_buf)
+//!     }
+//!     _ => unreachable!(),
+//! }
+//!
+//! let mut capture = [0; 9];
+//! capture[..2].copy_from_slice(&SetStandby::new(StdbyConfig::StdbyRc).tx_buf);
+//! capture[2..4].copy_from_slice(&SetPacketType::new(PacketType::Lora).tx_buf);
+//! capture[4..].copy_from_slice(&set_rf_frequency.tx_buf);
+//! let mut decoder = Decoder::new(&capture);
+//! assert!(matches!(decoder.next(), Some(Ok(DecodedCommand::SetStandby(_)))));
+//! assert!(matches!(decoder.next(), Some(Ok(DecodedCommand::SetPacketType(_)))));
+//! assert!(matches!(decoder.next(), Some(Ok(DecodedCommand::SetRfFrequency(_)))));
+//! assert!(decoder.next().is_none());
+//! ```
+
+use super::commands::{
+    Calibrate, CalibrateImage, ClearDeviceErrors, ClearIrqStatus, GetDeviceErrors, GetIrqStatus,
+    GetPacketStatusLora, GetPacketType, GetRssiInst, GetRxBufferStatus, GetStatsLora, GetStatus,
+    PacketType, ResetStats, SetBufferBaseAddress, SetCad, SetCadParams, SetDio2AsRfSwitchCtrl,
+    SetDio3AsTcxoCtrl, SetDioIrqParams, SetFs, SetLoraSymbNumTimeout, SetModulationParamsGfsk,
+    SetModulationParamsLora, SetPaConfig, SetPacketParams, SetPacketParamsGfsk, SetPacketType,
+    SetRegulatorMode, SetRfFrequency, SetRx, SetRxDutyCycle, SetRxTxFallbackMode, SetSleep,
+    SetStandby, SetTx, SetTxContinuousWave, SetTxInfinitePreamble, SetTxParams,
+    StopTimerOnPreamble, opcode_name,
+};
+use super::fmt::Command;
+use core::fmt;
+
+/// Error decoding a command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// No bytes to decode.
+    Empty,
+    /// The opcode is not a known command.
+    UnknownOpcode(u8),
+    /// The number of bytes does not match the command.
+    InvalidLength { opcode: u8, len: usize },
+}
+
+macro_rules! decoded_commands {
+    (@build $command:ident, $tx_buf:ident, $len:literal) => {
+        $command {
+            tx_buf: $tx_buf,
+            rx_buf: [0; $len],
+        }
+    };
+    (@build $command:ident, $tx_buf:ident, $len:literal, $from_tx_buf:path) => {
+        $from_tx_buf($tx_buf)
+    };
+    ($($command:ident = $opcode:literal, $len:literal $(=> $from_tx_buf:path)?;)*) => {
+        /// A decoded command. Fixed length commands are the command buffers of the
+        /// [`commands`](super::commands) module, with a zero `rx_buf`.
+        pub enum DecodedCommand<'a> {
+            $($command($command),)*
+            /// `WriteRegister` of any number of registers.
+            WriteRegister { address: u16, data: &'a [u8] },
+            /// `ReadRegister` of `len` registers.
+            ReadRegister { address: u16, len: usize },
+            /// `WriteBuffer` of `data` at `offset`.
+            WriteBuffer { offset: u8, data: &'a [u8] },
+            /// `ReadBuffer` of `len` bytes at `offset`.
+            ReadBuffer { offset: u8, len: usize },
+        }
+
+        /// Decodes a fixed length command, `None` if no command has `opcode` and the length of
+        /// `bytes`.
+        fn decode_fixed(bytes: &[u8]) -> Option<DecodedCommand<'_>> {
+            let opcode = *bytes.first()?;
+            $(
+                if opcode == $opcode {
+                    if let Ok(tx_buf) = <[u8; $len]>::try_from(bytes) {
+                        return Some(DecodedCommand::$command(decoded_commands!(
+                            @build $command, tx_buf, $len $(, $from_tx_buf)?
+                        )));
+                    }
+                }
+            )*
+            None
+        }
+
+        /// Length of the fixed length command with `opcode`. `SetModulationParams` and
+        /// `SetPacketParams` take the length of `packet_type`.
+        const fn fixed_length(opcode: u8, packet_type: &PacketType) -> Option<usize> {
+            let lora = matches!(packet_type, PacketType::Lora);
+            match opcode {
+                0x8B => return Some(if lora { 5 } else { 9 }),
+                0x8C => return Some(if lora { 7 } else { 10 }),
+                _ => {}
+            }
+            $(
+                if opcode == $opcode {
+                    return Some($len);
+                }
+            )*
+            None
+        }
+
+        impl fmt::Debug for DecodedCommand<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    $(DecodedCommand::$command(command) => {
+                        write!(f, "{}", Command(&command.tx_buf))
+                    })*
+                    DecodedCommand::WriteRegister { address, data } => f
+                        .debug_struct("WriteRegister")
+                        .field("address", address)
+                        .field("data", data)
+                        .finish(),
+                    DecodedCommand::ReadRegister { address, len } => f
+                        .debug_struct("ReadRegister")
+                        .field("address", address)
+                        .field("len", len)
+                        .finish(),
+                    DecodedCommand::WriteBuffer { offset, data } => f
+                        .debug_struct("WriteBuffer")
+                        .field("offset", offset)
+                        .field("data", data)
+                        .finish(),
+                    DecodedCommand::ReadBuffer { offset, len } => f
+                        .debug_struct("ReadBuffer")
+                        .field("offset", offset)
+                        .field("len", len)
+                        .finish(),
+                }
+            }
+        }
+    };
+}
+
+decoded_commands! {
+    ResetStats = 0x00, 7;
+    ClearIrqStatus = 0x02, 3;
+    ClearDeviceErrors = 0x07, 3;
+    SetDioIrqParams = 0x08, 9;
+    GetStatsLora = 0x10, 8 => GetStatsLora::from_tx_buf;
+    GetPacketType = 0x11, 3;
+    GetIrqStatus = 0x12, 4;
+    GetRxBufferStatus = 0x13, 4;
+    GetPacketStatusLora = 0x14, 5;
+    GetRssiInst = 0x15, 3;
+    GetDeviceErrors = 0x17, 4;
+    SetStandby = 0x80, 2;
+    SetRx = 0x82, 4;
+    SetTx = 0x83, 4;
+    SetSleep = 0x84, 2;
+    SetRfFrequency = 0x86, 5;
+    SetCadParams = 0x88, 8;
+    Calibrate = 0x89, 2;
+    SetPacketType = 0x8A, 2;
+    SetModulationParamsLora = 0x8B, 5;
+    SetModulationParamsGfsk = 0x8B, 9;
+    SetPacketParams = 0x8C, 7;
+    SetPacketParamsGfsk = 0x8C, 10;
+    SetTxParams = 0x8E, 3;
+    SetBufferBaseAddress = 0x8F, 3;
+    SetRxTxFallbackMode = 0x93, 2;
+    SetRxDutyCycle = 0x94, 7;
+    SetPaConfig = 0x95, 5;
+    SetRegulatorMode = 0x96, 2;
+    SetDio3AsTcxoCtrl = 0x97, 5;
+    CalibrateImage = 0x98, 3;
+    SetDio2AsRfSwitchCtrl = 0x9D, 2;
+    StopTimerOnPreamble = 0x9F, 2;
+    SetLoraSymbNumTimeout = 0xA0, 2;
+    GetStatus = 0xC0, 2;
+    SetFs = 0xC1, 1;
+    SetCad = 0xC5, 1;
+    SetTxContinuousWave = 0xD1, 1;
+    SetTxInfinitePreamble = 0xD2, 1;
+}
+
+/// Decodes the MOSI bytes of one transfer.
+pub fn decode(bytes: &[u8]) -> Result<DecodedCommand<'_>, DecodeError> {
+    let len = bytes.len();
+    match bytes {
+        [] => Err(DecodeError::Empty),
+        [0x0D, msb, lsb, data @ ..] if !data.is_empty() => Ok(DecodedCommand::WriteRegister {
+            address: u16::from_be_bytes([*msb, *lsb]),
+            data,
+        }),
+        [0x1D, msb, lsb, _, data @ ..] if !data.is_empty() => Ok(DecodedCommand::ReadRegister {
+            address: u16::from_be_bytes([*msb, *lsb]),
+            len: data.len(),
+        }),
+        [0x0E, offset, data @ ..] => Ok(DecodedCommand::WriteBuffer {
+            offset: *offset,
+            data,
+        }),
+        [0x1E, offset, _, data @ ..] => Ok(DecodedCommand::ReadBuffer {
+            offset: *offset,
+            len: data.len(),
+        }),
+        [opcode @ (0x0D | 0x1D | 0x0E | 0x1E), ..] => Err(DecodeError::InvalidLength {
+            opcode: *opcode,
+            len,
+        }),
+        [opcode, ..] => match decode_fixed(bytes) {
+            Some(command) => Ok(command),
+            None if opcode_name(*opcode).is_some() => Err(DecodeError::InvalidLength {
+                opcode: *opcode,
+                len,
+            }),
+            None => Err(DecodeError::UnknownOpcode(*opcode)),
+        },
+    }
+}
+
+/// # Decoder
+/// Iterator over the commands of a capture without NSS boundaries. Stops after the first
+/// error.
+pub struct Decoder<'a> {
+    bytes: &'a [u8],
+    packet_type: PacketType,
+}
+impl<'a> Decoder<'a> {
+    /// Decoder of `bytes`, starting with the GFSK packet type as after reset.
+    #[inline]
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self::with_packet_type(bytes, PacketType::Gfsk)
+    }
+    /// Decoder of `bytes` captured while `packet_type` was set.
+    #[inline]
+    pub const fn with_packet_type(bytes: &'a [u8], packet_type: PacketType) -> Self {
+        Self { bytes, packet_type }
+    }
+    /// Bytes not decoded yet.
+    #[inline]
+    pub const fn remaining(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+impl<'a> Iterator for Decoder<'a> {
+    type Item = Result<DecodedCommand<'a>, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let &opcode = self.bytes.first()?;
+        let len = match fixed_length(opcode, &self.packet_type) {
+            Some(len) if len <= self.bytes.len() => len,
+            // Register and data buffer accesses, unknown opcodes and truncated commands
+            _ => self.bytes.len(),
+        };
+        let (bytes, rest) = self.bytes.split_at(len);
+        let decoded = decode(bytes);
+        self.bytes = if decoded.is_ok() { rest } else { &[] };
+        if let Ok(DecodedCommand::SetPacketType(set_packet_type)) = &decoded {
+            self.packet_type = match set_packet_type.tx_buf[1] {
+                0x01 => PacketType::Lora,
+                0x03 => PacketType::LrFhss,
+                _ => PacketType::Gfsk,
+            };
+        }
+        Some(decoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{Bw, Cr, HeaderType, InvertIq, ReadRegisters, Sf, WriteRegister};
+    use crate::registers::{LoraSyncWordMsb, Register};
+
+    #[test]
+    fn test_decode_round_trip() {
+        let set_modulation_params =
+            SetModulationParamsLora::new(Sf::Sf7, Bw::Bw125, Cr::Cr4_5, false);
+        let set_packet_params =
+            SetPacketParams::new(8, HeaderType::VariableLength, 14, true, InvertIq::Standard);
+        let mut capture = [0; 16];
+        capture[..2].copy_from_slice(&SetPacketType::new(PacketType::Lora).tx_buf);
+        capture[2..7].copy_from_slice(&set_modulation_params.tx_buf);
+        capture[7..14].copy_from_slice(&set_packet_params.tx_buf);
+        capture[14..].copy_from_slice(&[0x0E, 0x00]);
+
+        let mut decoder = Decoder::new(&capture);
+        assert!(matches!(
+            decoder.next(),
+            Some(Ok(DecodedCommand::SetPacketType(_)))
+        ));
+        match decoder.next() {
+            Some(Ok(DecodedCommand::SetModulationParamsLora(command))) => {
+                assert_eq!(command.tx_buf, set_modulation_params.tx_buf)
+            }
+            _ => panic!("expected SetModulationParamsLora"),
+        }
+        match decoder.next() {
+            Some(Ok(DecodedCommand::SetPacketParams(command))) => {
+                assert_eq!(command.tx_buf, set_packet_params.tx_buf)
+            }
+            _ => panic!("expected SetPacketParams"),
+        }
+        assert!(matches!(
+            decoder.next(),
+            Some(Ok(DecodedCommand::WriteBuffer {
+                offset: 0,
+                data: &[]
+            }))
+        ));
+        assert!(decoder.next().is_none());
+    }
+
+    #[test]
+    fn test_decode_registers_and_errors() {
+        let write_register = WriteRegister::at_address(LoraSyncWordMsb::ADDRESS, 0x34);
+        assert!(matches!(
+            decode(&write_register.tx_buf),
+            Ok(DecodedCommand::WriteRegister {
+                address: 0x0740,
+                data: &[0x34]
+            })
+        ));
+        let read_registers = ReadRegisters::<6>::at_address(LoraSyncWordMsb::ADDRESS);
+        assert!(matches!(
+            decode(&read_registers.tx_buf),
+            Ok(DecodedCommand::ReadRegister {
+                address: 0x0740,
+                len: 2
+            })
+        ));
+
+        assert_eq!(decode(&[]).err(), Some(DecodeError::Empty));
+        assert_eq!(
+            decode(&[0xFF]).err(),
+            Some(DecodeError::UnknownOpcode(0xFF))
+        );
+        assert_eq!(
+            decode(&[0x86, 0x36]).err(),
+            Some(DecodeError::InvalidLength {
+                opcode: 0x86,
+                len: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! Dispatching of IRQ flags to registered handlers, driven by the result of `GetIrqStatus`.
+//!
+//! A [`Dispatcher`] holds one handler per [`IrqFlag`]. Handlers are any `FnMut(IrqFlag) -> R`
+//! of a single type `H`, e.g. `fn(IrqFlag)` pointers, `&'static mut dyn FnMut(IrqFlag)` or,
+//! with `alloc`, boxed closures. Handlers may return a value, e.g. an application event enum,
+//! collected with [`Dispatcher::dispatch_with`].
+//!
+//! ## Example
+//! ```
+//! use core::sync::atomic::{AtomicU32, Ordering};
+//! use sx126x_spi_buffers::{
+//!     commands::{GetIrqStatus, Irq, IrqFlag},
+//!     dispatch::Dispatcher,
+//! };
+//!
+//! static TX_DONE_COUNT: AtomicU32 = AtomicU32::new(0);
+//!
+//! let mut dispatcher: Dispatcher<fn(IrqFlag)> = Dispatcher::new();
+//! dispatcher.register(IrqFlag::TxDone, |_| {
+//!     TX_DONE_COUNT.fetch_add(1, Ordering::Relaxed);
+//! });
+//! dispatcher.register(IrqFlag::Timeout, |_| {});
+//! assert_eq!(dispatcher.mask(), Irq::TX_DONE | Irq::TIMEOUT);
+//!
+//! let mut get_irq_status = GetIrqStatus::new();
+//! get_irq_status.rx_buf = [0xA2, 0xA4, 0x00, 0x03];
+//! let unhandled = dispatcher.dispatch(get_irq_status.irq_status());
+//! assert_eq!(TX_DONE_COUNT.load(Ordering::Relaxed), 1);
+//! assert_eq!(unhandled, Irq::RX_DONE);
+//! ```
+//!
+//! Handlers returning events:
+//! ```
+//! use sx126x_spi_buffers::{
+//!     commands::{Irq, IrqFlag},
+//!     dispatch::Dispatcher,
+//! };
+//!
+//! #[derive(Debug, PartialEq)]
+//! enum Event {
+//!     Sent,
+//!     Received,
+//! }
+//!
+//! let mut dispatcher: Dispatcher<fn(IrqFlag) -> Event> = Dispatcher::new();
+//! dispatcher.register(IrqFlag::TxDone, |_| Event::Sent);
+//! dispatcher.register(IrqFlag::RxDone, |_| Event::Received);
+//!
+//! let mut events = [None, None];
+//! let mut len = 0;
+//! dispatcher.dispatch_with(Irq::TX_DONE | Irq::RX_DONE, |event| {
+//!     events[len] = Some(event);
+//!     len += 1;
+//! });
+//! assert_eq!(events, [Some(Event::Sent), Some(Event::Received)]);
+//! ```
+#![allow(clippy::new_without_default)]
+
+use super::commands::{Irq, IrqFlag};
+
+/// # Dispatcher
+/// Handlers of IRQ flags, called for each set flag from the lowest bit.
+pub struct Dispatcher<H> {
+    handlers: [Option<H>; IrqFlag::ALL.len()],
+}
+impl<H> Dispatcher<H> {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            handlers: [
+                None, None, None, None, None, None, None, None, None, None, None,
+            ],
+        }
+    }
+    /// Registers the handler of `flag`, returning the handler it replaces.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub fn register(&mut self, flag: IrqFlag, handler: H) -> Option<H> {
+        self.handlers[Self::index(flag)].replace(handler)
+    }
+    /// Removes the handler of `flag`.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub fn unregister(&mut self, flag: IrqFlag) -> Option<H> {
+        self.handlers[Self::index(flag)].take()
+    }
+    /// Flags with a handler, e.g. for the IRQ mask of `SetDioIrqParams`.
+    pub fn mask(&self) -> Irq {
+        IrqFlag::ALL
+            .iter()
+            .zip(&self.handlers)
+            .filter(|(_, handler)| handler.is_some())
+            .fold(Irq::NONE, |mask, (flag, _)| mask | flag.mask())
+    }
+    #[inline]
+    fn index(flag: IrqFlag) -> usize {
+        // `IrqFlag::ALL` is ordered by discriminant
+        flag as usize
+    }
+}
+impl<H: FnMut(IrqFlag) -> R, R> Dispatcher<H> {
+    /// Calls the handler of each flag set in `irq`, passing its result to `result`. Returns the
+    /// set flags without handler.
+    #[allow(clippy::indexing_slicing)]
+    pub fn dispatch_with(&mut self, irq: Irq, mut result: impl FnMut(R)) -> Irq {
+        let mut unhandled = Irq::NONE;
+        for flag in irq {
+            match &mut self.handlers[Self::index(flag)] {
+                Some(handler) => result(handler(flag)),
+                None => unhandled |= flag.mask(),
+            }
+        }
+        unhandled
+    }
+}
+impl<H: FnMut(IrqFlag)> Dispatcher<H> {
+    /// Calls the handler of each flag set in `irq`. Returns the set flags without handler.
+    #[inline]
+    pub fn dispatch(&mut self, irq: Irq) -> Irq {
+        self.dispatch_with(irq, |()| {})
+    }
+}
@@ -0,0 +1,56 @@
+//! Workarounds for the known limitations listed in section 15 of the SX126x datasheet.
+#![allow(clippy::new_without_default)]
+
+use super::commands::{Rmw, SpiDescriptor, WriteRegister};
+use super::registers::{EventMask, Events, Register, RtcControl};
+
+/// # Implicit header mode timeout workaround
+/// When the device is in RX with implicit header mode and a timeout, the RTC keeps running
+/// after the timeout and may generate a spurious timeout event later (datasheet 15.3).
+/// The RTC has to be stopped and the timeout event cleared after any RX with timeout.
+///
+/// Clearing the event is a read-modify-write of [`EventMask`]:
+/// 1. Transfer `descriptors()` - stops the RTC and reads the event mask.
+/// 2. Call `update()` once the read has completed.
+/// 3. Transfer `clear_event.write_descriptor()` - writes the event mask back with the timeout
+///    bit set.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::errata::ImplicitHeaderTimeout;
+///
+/// let mut fix: ImplicitHeaderTimeout = ImplicitHeaderTimeout::new();
+/// assert_eq!(fix.stop_rtc.tx_buf, [0x0D, 0x09, 0x02, 0x00]);
+/// assert_eq!(fix.clear_event.read.tx_buf, [0x1D, 0x09, 0x44, 0, 0]);
+/// assert_eq!(fix.descriptors()[1].transfer_length, 5);
+///
+/// fix.clear_event.read.rx_buf[4] = 0x10;
+/// fix.update();
+/// assert_eq!(fix.clear_event.write.tx_buf, [0x0D, 0x09, 0x44, 0x12]);
+/// ```
+pub struct ImplicitHeaderTimeout {
+    pub stop_rtc: WriteRegister,
+    pub clear_event: Rmw<EventMask>,
+}
+impl ImplicitHeaderTimeout {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            stop_rtc: WriteRegister::at_address(RtcControl::ADDRESS, 0x00),
+            clear_event: Rmw::new(0x00, Events::new().with_timeout(true).into_bits()),
+        }
+    }
+    /// Descriptors for stopping the RTC and reading the event mask.
+    #[inline]
+    pub const fn descriptors(&self) -> [SpiDescriptor; 2] {
+        [
+            self.stop_rtc.descriptor(),
+            self.clear_event.read_descriptor(),
+        ]
+    }
+    /// Prepares `clear_event.write` from the event mask read by `clear_event.read`.
+    #[inline]
+    pub const fn update(&mut self) {
+        self.clear_event.update();
+    }
+}
@@ -0,0 +1,44 @@
+//! C accessors for [`SpiDescriptor`], for handing descriptors to C DMA drivers.
+//!
+//! With the `ffi` feature `SpiDescriptor` is `#[repr(C)]`, so C code can also read the fields
+//! directly:
+//! ```c
+//! typedef struct SpiDescriptor {
+//!     const uint8_t *tx_buf_ptr;
+//!     const uint8_t *rx_buf_ptr;
+//!     uint16_t transfer_length;
+//! } SpiDescriptor;
+//! ```
+//! `transfer_length` is a `uint8_t` or `size_t` with the `transfer-length-u8` or
+//! `transfer-length-usize` features.
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::{commands::SetStandby, commands::StdbyConfig, ffi};
+//!
+//! let set_standby = SetStandby::new(StdbyConfig::StdbyRc);
+//! let descriptor = set_standby.descriptor();
+//! assert_eq!(ffi::sx126x_spi_descriptor_tx_buf(&descriptor), set_standby.tx_buf.as_ptr());
+//! assert_eq!(ffi::sx126x_spi_descriptor_rx_buf(&descriptor), set_standby.rx_buf.as_ptr());
+//! assert_eq!(ffi::sx126x_spi_descriptor_transfer_length(&descriptor), 2);
+//! ```
+
+use super::commands::SpiDescriptor;
+
+/// TX buffer of the transfer.
+#[unsafe(no_mangle)]
+pub extern "C" fn sx126x_spi_descriptor_tx_buf(descriptor: &SpiDescriptor) -> *const u8 {
+    descriptor.tx_buf_ptr
+}
+
+/// RX buffer of the transfer. The buffer is written by the DMA although the pointer is const.
+#[unsafe(no_mangle)]
+pub extern "C" fn sx126x_spi_descriptor_rx_buf(descriptor: &SpiDescriptor) -> *const u8 {
+    descriptor.rx_buf_ptr
+}
+
+/// Transfer length in bytes.
+#[unsafe(no_mangle)]
+pub extern "C" fn sx126x_spi_descriptor_transfer_length(descriptor: &SpiDescriptor) -> usize {
+    descriptor.len()
+}
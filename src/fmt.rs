@@ -0,0 +1,276 @@
+//! Human readable rendering of commands, for logging over RTT or a serial port and for golden
+//! files in tests.
+//!
+//! [`Command`] renders the TX bytes of a command as its name, the fields decoded from known
+//! opcodes and the bytes in hex, [`Hex`] only the bytes.
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::{
+//!     commands::{RampTime, SetFs, SetRfFrequency, SetTxParams},
+//!     fmt::Command,
+//! };
+//!
+//! let set_rf_frequency = SetRfFrequency::from_hz(868_100_000);
+//! assert_eq!(
+//!     format!("{}", Command(&set_rf_frequency.tx_buf)),
+//!     "SetRfFrequency { 868.1 MHz } => 86 36 41 99 99"
+//! );
+//!
+//! let set_tx_params = SetTxParams::new(14, RampTime::Ramp200U);
+//! assert_eq!(
+//!     format!("{}", Command(&set_tx_params.tx_buf)),
+//!     "SetTxParams { 14 dBm, 200 us ramp } => 8E 0E 04"
+//! );
+//!
+//! assert_eq!(format!("{}", Command(&SetFs::new().tx_buf)), "SetFs => C1");
+//! ```
+
+use super::commands::opcode_name;
+use core::fmt;
+
+/// # Hex
+/// Space separated upper case hex bytes, e.g. `86 36 41 99 99`.
+pub struct Hex<'a>(pub &'a [u8]);
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, byte) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{byte:02X}")?;
+        }
+        Ok(())
+    }
+}
+
+/// # Command
+/// TX bytes of a command, rendered as `Name { fields } => bytes`. Commands without decoded
+/// fields are rendered as `Name => bytes`, unknown opcodes as `Opcode(0xXX) => bytes`.
+pub struct Command<'a>(pub &'a [u8]);
+impl fmt::Display for Command<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((&opcode, args)) = self.0.split_first() else {
+            return f.write_str("Empty");
+        };
+        match opcode_name(opcode) {
+            Some(name) => f.write_str(name)?,
+            None => write!(f, "Opcode({opcode:#04X})")?,
+        }
+        fields(f, opcode, args)?;
+        write!(f, " => {}", Hex(self.0))
+    }
+}
+
+/// Writes ` { fields }` of the commands with decoded fields.
+fn fields(f: &mut fmt::Formatter<'_>, opcode: u8, args: &[u8]) -> fmt::Result {
+    match (opcode, args) {
+        (0x80, &[config, ..]) => match config {
+            0 => f.write_str(" { StdbyRc }"),
+            _ => f.write_str(" { StdbyXosc }"),
+        },
+        (0x84, &[config, ..]) => write!(
+            f,
+            " {{ {} start{} }}",
+            if config & 0x04 != 0 { "warm" } else { "cold" },
+            if config & 0x01 != 0 {
+                ", RTC wake-up"
+            } else {
+                ""
+            }
+        ),
+        (0x82 | 0x83, &[a, b, c, ..]) => {
+            let timeout = u32::from_be_bytes([0, a, b, c]);
+            match timeout {
+                0 => f.write_str(" { no timeout }"),
+                0xFF_FFFF if opcode == 0x82 => f.write_str(" { continuous }"),
+                // Steps of 15.625 us
+                _ => write!(f, " {{ {} us }}", (timeout as u64 * 125).div_ceil(8)),
+            }
+        }
+        (0x86, &[a, b, c, d, ..]) => {
+            let freq = u32::from_be_bytes([a, b, c, d]) as u64;
+            let hz = (freq * 32_000_000 + (1 << 24)) >> 25;
+            let khz = (hz + 500) / 1000;
+            f.write_str(" { ")?;
+            mhz(f, khz)?;
+            f.write_str(" MHz }")
+        }
+        (0x8A, &[packet_type, ..]) => match packet_type {
+            0x00 => f.write_str(" { Gfsk }"),
+            0x01 => f.write_str(" { Lora }"),
+            0x03 => f.write_str(" { LrFhss }"),
+            _ => Ok(()),
+        },
+        (0x8B, &[sf, bw, cr, ldro]) => {
+            write!(f, " {{ SF{sf}, ")?;
+            match bw_khz(bw) {
+                Some(khz) => write!(f, "{khz} kHz")?,
+                None => write!(f, "BW {bw:#04X}")?,
+            }
+            match cr {
+                0x01..=0x04 => write!(f, ", CR 4/{}", 4 + cr)?,
+                0x05 | 0x06 => write!(f, ", CR 4/{} LI", cr)?,
+                0x07 => f.write_str(", CR 4/8 LI")?,
+                _ => write!(f, ", CR {cr:#04X}")?,
+            }
+            write!(f, ", LDRO {} }}", if ldro != 0 { "on" } else { "off" })
+        }
+        (
+            0x8C,
+            &[
+                preamble_msb,
+                preamble_lsb,
+                header_type,
+                payload_length,
+                crc,
+                invert_iq,
+            ],
+        ) => {
+            write!(
+                f,
+                " {{ preamble {}, {} header, {} bytes, CRC {}, {} IQ }}",
+                u16::from_be_bytes([preamble_msb, preamble_lsb]),
+                if header_type != 0 {
+                    "fixed"
+                } else {
+                    "variable"
+                },
+                payload_length,
+                if crc != 0 { "on" } else { "off" },
+                if invert_iq != 0 {
+                    "inverted"
+                } else {
+                    "standard"
+                }
+            )
+        }
+        (0x8E, &[power, ramp_time, ..]) => {
+            write!(f, " {{ {} dBm", power as i8)?;
+            const RAMP_US: [u16; 8] = [10, 20, 40, 80, 200, 800, 1700, 3400];
+            match RAMP_US.get(ramp_time as usize) {
+                Some(us) => write!(f, ", {us} us ramp }}"),
+                None => f.write_str(" }"),
+            }
+        }
+        (0x95, &[pa_duty_cycle, hp_max, device_sel, ..]) => write!(
+            f,
+            " {{ duty cycle {pa_duty_cycle:#04X}, hp_max {hp_max:#04X}, device_sel {device_sel} }}"
+        ),
+        (0x96, &[mode, ..]) => match mode {
+            0 => f.write_str(" { LDO }"),
+            _ => f.write_str(" { DC-DC }"),
+        },
+        (0x8F, &[tx_base, rx_base, ..]) => {
+            write!(f, " {{ TX {tx_base:#04X}, RX {rx_base:#04X} }}")
+        }
+        (0x0D | 0x1D, &[msb, lsb, ..]) => {
+            write!(f, " {{ {:#06X} }}", u16::from_be_bytes([msb, lsb]))
+        }
+        (0x0E | 0x1E, &[offset, ..]) => write!(f, " {{ offset {offset:#04X} }}"),
+        (0x02, &[msb, lsb, ..]) => {
+            write!(f, " {{ {:#06X} }}", u16::from_be_bytes([msb, lsb]))
+        }
+        (0x08, &[a, b, c, d, e, g, h, i, ..]) => write!(
+            f,
+            " {{ IRQ {:#06X}, DIO1 {:#06X}, DIO2 {:#06X}, DIO3 {:#06X} }}",
+            u16::from_be_bytes([a, b]),
+            u16::from_be_bytes([c, d]),
+            u16::from_be_bytes([e, g]),
+            u16::from_be_bytes([h, i])
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Writes `khz` in MHz without trailing zeros, e.g. `868.1`.
+fn mhz(f: &mut fmt::Formatter<'_>, khz: u64) -> fmt::Result {
+    let (mut frac, mut digits) = (khz % 1000, 3);
+    if frac == 0 {
+        return write!(f, "{}", khz / 1000);
+    }
+    while frac % 10 == 0 {
+        frac /= 10;
+        digits -= 1;
+    }
+    write!(f, "{}.{:0digits$}", khz / 1000, frac)
+}
+
+/// LoRa bandwidth in kHz of the `SetModulationParams` value.
+fn bw_khz(bw: u8) -> Option<&'static str> {
+    Some(match bw {
+        0x00 => "7.81",
+        0x08 => "10.42",
+        0x01 => "15.63",
+        0x09 => "20.83",
+        0x02 => "31.25",
+        0x0A => "41.67",
+        0x03 => "62.5",
+        0x04 => "125",
+        0x05 => "250",
+        0x06 => "500",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{
+        Bw, Cr, HeaderType, InvertIq, SetModulationParamsLora, SetPacketParams, SetRx, Sf,
+    };
+
+    struct Buf {
+        bytes: [u8; 128],
+        len: usize,
+    }
+    impl fmt::Write for Buf {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            self.bytes
+                .get_mut(self.len..end)
+                .ok_or(fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    fn render(tx_buf: &[u8]) -> Buf {
+        let mut buf = Buf {
+            bytes: [0; 128],
+            len: 0,
+        };
+        fmt::write(&mut buf, format_args!("{}", Command(tx_buf))).unwrap();
+        buf
+    }
+
+    #[test]
+    fn test_command() {
+        let set_modulation_params =
+            SetModulationParamsLora::new(Sf::Sf9, Bw::Bw125, Cr::Cr4_8, false);
+        let buf = render(&set_modulation_params.tx_buf);
+        assert_eq!(
+            &buf.bytes[..buf.len],
+            b"SetModulationParams { SF9, 125 kHz, CR 4/8, LDRO off } => 8B 09 04 04 00"
+        );
+
+        let set_packet_params =
+            SetPacketParams::new(8, HeaderType::VariableLength, 14, true, InvertIq::Standard);
+        let buf = render(&set_packet_params.tx_buf);
+        assert_eq!(
+            &buf.bytes[..buf.len],
+            b"SetPacketParams { preamble 8, variable header, 14 bytes, CRC on, standard IQ } \
+              => 8C 00 08 00 0E 01 00"
+        );
+
+        let buf = render(&SetRx::new(0xFF_FFFF).tx_buf);
+        assert_eq!(
+            &buf.bytes[..buf.len],
+            b"SetRx { continuous } => 82 FF FF FF"
+        );
+
+        let buf = render(&[0xFF, 0x01]);
+        assert_eq!(&buf.bytes[..buf.len], b"Opcode(0xFF) => FF 01");
+    }
+}
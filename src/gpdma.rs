@@ -0,0 +1,189 @@
+//! Linked-list items of the STM32 GPDMA (STM32U5, STM32H5, ...), to run a chain of descriptors,
+//! e.g. an initialization sequence, from a single DMA start without the CPU between commands.
+//!
+//! The SPI TX and RX requests use one GPDMA channel each. [`GpdmaLinkedList`] holds one
+//! [`Lli`] per descriptor for each channel: the TX items update the block size, the source
+//! address and the link, the RX items the block size, the destination address and the link.
+//! The other channel registers, e.g. the request selection and the peripheral data register
+//! address, keep the values programmed before the start.
+//!
+//! The SPI must end each transfer with NSS high, e.g. by pulsing NSS between data frames or
+//! with one SPI transfer size per item, and each command must leave the radio ready for the
+//! next one: BUSY is not checked between items, so commands with a long BUSY time such as
+//! `Calibrate` must end a chain.
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::{
+//!     commands::{PacketType, SetPacketType, SetStandby, StdbyConfig},
+//!     gpdma::{self, GpdmaLinkedList},
+//! };
+//!
+//! // 48 bytes of items, aligned so they can't straddle a 64 KB boundary
+//! #[repr(align(64))]
+//! struct Aligned(GpdmaLinkedList<2>);
+//!
+//! let set_standby = SetStandby::new(StdbyConfig::StdbyRc);
+//! let set_packet_type = SetPacketType::new(PacketType::Lora);
+//! let mut aligned = Aligned(GpdmaLinkedList::new());
+//! let list = &mut aligned.0;
+//! list.link(&[set_standby.descriptor(), set_packet_type.descriptor()]).unwrap();
+//!
+//! assert_eq!(list.tx[0].cbr1, 2);
+//! assert_eq!(list.tx[0].addr, set_standby.descriptor().raw().src);
+//! assert_eq!(list.rx[1].addr, set_packet_type.descriptor().raw().dst);
+//! // The last items end the list
+//! assert_eq!(list.tx[1].cllr, gpdma::CLLR_UB1 | gpdma::CLLR_USA);
+//!
+//! // Channel registers: CBR1 = 0, CLBAR = list.lba(), CLLR = list.tx_cllr() or list.rx_cllr()
+//! assert_eq!(list.tx_cllr() & gpdma::CLLR_ULL, gpdma::CLLR_ULL);
+//! ```
+#![allow(clippy::new_without_default)]
+
+use super::commands::SpiDescriptor;
+
+/// `CLLR.UB1`: the item updates `CBR1`.
+pub const CLLR_UB1: u32 = 1 << 29;
+/// `CLLR.USA`: the item updates `CSAR`.
+pub const CLLR_USA: u32 = 1 << 28;
+/// `CLLR.UDA`: the item updates `CDAR`.
+pub const CLLR_UDA: u32 = 1 << 27;
+/// `CLLR.ULL`: the item updates `CLLR`.
+pub const CLLR_ULL: u32 = 1 << 16;
+/// `CLLR.LA`: low 16 bits of the address of the next item, word aligned.
+const CLLR_LA: u32 = 0xFFFC;
+
+/// A list whose items straddle a 64 KB boundary, so they can't share the `CLBAR` the GPDMA
+/// combines with the link address of each item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SplitRegion {
+    /// Address of the first byte of the items.
+    pub start: u32,
+    /// Address of the last byte of the items.
+    pub end: u32,
+}
+
+/// A GPDMA linked-list item updating `CBR1`, one address register and `CLLR`, in the order the
+/// GPDMA loads them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct Lli {
+    /// Block size in bytes.
+    pub cbr1: u32,
+    /// `CSAR` of TX items, `CDAR` of RX items.
+    pub addr: u32,
+    /// Update bits and address of the next item, without `ULL` in the last item.
+    pub cllr: u32,
+}
+impl Lli {
+    const EMPTY: Self = Self {
+        cbr1: 0,
+        addr: 0,
+        cllr: 0,
+    };
+}
+
+/// # GpdmaLinkedList
+/// Linked-list items of `N` descriptors for the TX and RX channels. The items contain their
+/// own addresses: call `link()` once the list is at its final address, e.g. in a `static`, and
+/// don't move it while the DMA runs.
+///
+/// The GPDMA only takes the low 16 bits of the next item's address from each item, the upper
+/// 16 bits come from `CLBAR`: all items must be in the same 64 KB region, which `link()`
+/// checks. A list can straddle a region boundary wherever the linker places it; aligning it to
+/// the next power of two of its size, at most 64 KB, rules that out.
+#[repr(C, align(4))]
+pub struct GpdmaLinkedList<const N: usize> {
+    pub tx: [Lli; N],
+    pub rx: [Lli; N],
+}
+impl<const N: usize> GpdmaLinkedList<N> {
+    const MIN_SIZE: () = assert!(N >= 1, "N must be at least 1");
+
+    #[inline]
+    pub const fn new() -> Self {
+        let () = Self::MIN_SIZE;
+        Self {
+            tx: [Lli::EMPTY; N],
+            rx: [Lli::EMPTY; N],
+        }
+    }
+    /// Fills the items of `descriptors`, transferred in order, or leaves them unchanged if they
+    /// straddle a 64 KB boundary.
+    #[allow(clippy::indexing_slicing)]
+    pub fn link(&mut self, descriptors: &[SpiDescriptor; N]) -> Result<(), SplitRegion> {
+        Self::check_region(
+            core::ptr::from_ref(self).expose_provenance() as u32,
+            size_of::<Self>() as u32,
+        )?;
+        for (index, descriptor) in descriptors.iter().enumerate() {
+            let raw = descriptor.raw();
+            let next = index + 1;
+            let (tx_link, rx_link) = if next < N {
+                (
+                    CLLR_ULL | Self::la(&self.tx[next]),
+                    CLLR_ULL | Self::la(&self.rx[next]),
+                )
+            } else {
+                (0, 0)
+            };
+            self.tx[index] = Lli {
+                cbr1: raw.len,
+                addr: raw.src,
+                cllr: CLLR_UB1 | CLLR_USA | tx_link,
+            };
+            self.rx[index] = Lli {
+                cbr1: raw.len,
+                addr: raw.dst,
+                cllr: CLLR_UB1 | CLLR_UDA | rx_link,
+            };
+        }
+        Ok(())
+    }
+    /// Checks that the `len` bytes at `start` are in one 64 KB region.
+    #[inline]
+    const fn check_region(start: u32, len: u32) -> Result<(), SplitRegion> {
+        let end = start.wrapping_add(len - 1);
+        if start & 0xFFFF_0000 == end & 0xFFFF_0000 {
+            Ok(())
+        } else {
+            Err(SplitRegion { start, end })
+        }
+    }
+    /// `CLBAR` of both channels, the upper 16 bits of the address of the items.
+    #[inline]
+    pub fn lba(&self) -> u32 {
+        (core::ptr::from_ref(self).expose_provenance() as u32) & 0xFFFF_0000
+    }
+    /// `CLLR` of the TX channel loading its first item.
+    #[inline]
+    pub fn tx_cllr(&self) -> u32 {
+        CLLR_UB1 | CLLR_USA | CLLR_ULL | Self::la(&self.tx[0])
+    }
+    /// `CLLR` of the RX channel loading its first item.
+    #[inline]
+    pub fn rx_cllr(&self) -> u32 {
+        CLLR_UB1 | CLLR_UDA | CLLR_ULL | Self::la(&self.rx[0])
+    }
+    #[inline]
+    fn la(item: &Lli) -> u32 {
+        (core::ptr::from_ref(item).expose_provenance() as u32) & CLLR_LA
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_region() {
+        assert_eq!(GpdmaLinkedList::<2>::check_region(0x2000_FFD0, 48), Ok(()));
+        assert_eq!(
+            GpdmaLinkedList::<2>::check_region(0x2000_FFE0, 48),
+            Err(SplitRegion {
+                start: 0x2000_FFE0,
+                end: 0x2001_000F
+            })
+        );
+    }
+}
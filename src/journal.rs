@@ -0,0 +1,174 @@
+//! Journal of configuration commands, replayed to restore the configuration after a reset,
+//! brown-out or cold start sleep.
+#![allow(clippy::new_without_default)]
+
+use super::commands::{SpiDescriptor, transfer_length};
+
+/// Error recording a command in a [`ConfigJournal`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JournalError {
+    /// No free entry left for a new command.
+    Full,
+    /// The command is longer than [`JournalEntry::CAPACITY`].
+    TooLong,
+}
+
+/// A recorded command.
+pub struct JournalEntry {
+    pub tx_buf: [u8; JournalEntry::CAPACITY],
+    pub rx_buf: [u8; JournalEntry::CAPACITY],
+    len: u8,
+}
+impl JournalEntry {
+    /// Maximum command length in bytes.
+    pub const CAPACITY: usize = 16;
+
+    const EMPTY: Self = Self {
+        tx_buf: [0; Self::CAPACITY],
+        rx_buf: [0; Self::CAPACITY],
+        len: 0,
+    };
+
+    /// Opcode, plus the address for `WriteRegister`.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    const fn key(bytes: &[u8]) -> (u8, u16) {
+        if bytes[0] == 0x0D && bytes.len() >= 3 {
+            (bytes[0], (bytes[1] as u16) << 8 | bytes[2] as u16)
+        } else {
+            (bytes[0], 0)
+        }
+    }
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub fn bytes(&self) -> &[u8] {
+        &self.tx_buf[..self.len as usize]
+    }
+    #[inline]
+    pub const fn descriptor(&self) -> SpiDescriptor {
+        SpiDescriptor {
+            tx_buf_ptr: self.tx_buf.as_ptr(),
+            rx_buf_ptr: self.rx_buf.as_ptr(),
+            transfer_length: transfer_length(self.len as usize),
+        }
+    }
+}
+
+/// # ConfigJournal
+/// Records configuration commands as they are submitted. Recording a command with the same
+/// opcode (and the same address for register writes) as an earlier one replaces it in place,
+/// so the journal holds the latest configuration in the original submission order.
+///
+/// To restore the configuration, transfer `descriptors()` from STDBY_RC.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{
+///     commands::{PacketType, SetPacketType, SetRfFrequency, WriteRegister},
+///     journal::ConfigJournal,
+///     registers::LoraSyncWordMsb,
+/// };
+///
+/// let mut journal: ConfigJournal<8> = ConfigJournal::new();
+/// journal.record(&SetPacketType::new(PacketType::Lora).tx_buf).unwrap();
+/// journal.record(&SetRfFrequency::new(0x3640_0000).tx_buf).unwrap();
+/// journal.record(&WriteRegister::new(LoraSyncWordMsb(0x34)).tx_buf).unwrap();
+/// journal.record(&SetRfFrequency::new(0x3650_0000).tx_buf).unwrap();
+///
+/// assert_eq!(journal.len(), 3);
+/// assert_eq!(journal.entries[1].bytes(), [0x86, 0x36, 0x50, 0, 0]);
+/// assert_eq!(journal.descriptors().map(|d| d.len()).sum::<usize>(), 11);
+/// ```
+pub struct ConfigJournal<const N: usize> {
+    pub entries: [JournalEntry; N],
+    len: usize,
+}
+impl<const N: usize> ConfigJournal<N> {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            entries: [JournalEntry::EMPTY; N],
+            len: 0,
+        }
+    }
+    /// Records the TX buffer of a command. Empty buffers are ignored.
+    #[allow(clippy::indexing_slicing)]
+    pub const fn record(&mut self, tx_buf: &[u8]) -> Result<(), JournalError> {
+        if tx_buf.is_empty() {
+            return Ok(());
+        }
+        if tx_buf.len() > JournalEntry::CAPACITY {
+            return Err(JournalError::TooLong);
+        }
+        let key = JournalEntry::key(tx_buf);
+        let mut i: usize = 0;
+        while i < self.len {
+            let entry_key = JournalEntry::key(&self.entries[i].tx_buf);
+            if entry_key.0 == key.0 && entry_key.1 == key.1 {
+                break;
+            }
+            i += 1;
+        }
+        if i == N {
+            return Err(JournalError::Full);
+        }
+        let entry = &mut self.entries[i];
+        let mut j: usize = 0;
+        while j < tx_buf.len() {
+            entry.tx_buf[j] = tx_buf[j];
+            j += 1;
+        }
+        entry.len = tx_buf.len() as u8;
+        if i == self.len {
+            self.len += 1;
+        }
+        Ok(())
+    }
+    /// Number of recorded commands.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    #[inline]
+    pub const fn clear(&mut self) {
+        self.len = 0;
+    }
+    /// Descriptors replaying the recorded commands in order.
+    pub fn descriptors(&self) -> impl Iterator<Item = SpiDescriptor> + '_ {
+        self.entries[..self.len]
+            .iter()
+            .map(JournalEntry::descriptor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{SetRfFrequency, WriteRegister};
+    use crate::registers::{LoraSyncWordLsb, LoraSyncWordMsb};
+
+    #[test]
+    fn test_journal_register_addresses() {
+        let mut journal: ConfigJournal<2> = ConfigJournal::new();
+        journal
+            .record(&WriteRegister::new(LoraSyncWordMsb(0x34)).tx_buf)
+            .unwrap();
+        journal
+            .record(&WriteRegister::new(LoraSyncWordLsb(0x44)).tx_buf)
+            .unwrap();
+        journal
+            .record(&WriteRegister::new(LoraSyncWordMsb(0x14)).tx_buf)
+            .unwrap();
+        assert_eq!(journal.entries[0].bytes(), [0x0D, 0x07, 0x40, 0x14]);
+        assert_eq!(journal.entries[1].bytes(), [0x0D, 0x07, 0x41, 0x44]);
+        assert_eq!(
+            journal.record(&SetRfFrequency::new(0).tx_buf),
+            Err(JournalError::Full)
+        );
+        assert_eq!(journal.record(&[0; 17]), Err(JournalError::TooLong));
+    }
+}
@@ -1,19 +1,182 @@
 //! <div class="warning">
-//! <strong>Requires Rust Nightly</strong>
+//! <strong>The default <code>nightly</code> feature requires Rust Nightly</strong>
 //! </div>
 //!
+//! The default `nightly` feature enables constructors whose buffer size is computed with
+//! `generic_const_exprs`, e.g. `WriteBuffer::new`, and makes `Register` a const trait so that
+//! typed register commands such as `WriteRegister::new` can be built in `const` items. Without
+//! it the crate builds on stable: the equivalents taking an explicit size, e.g.
+//! `WriteBuffer::from_slice`, remain available, typed register commands are built at runtime,
+//! and `WriteRegister::at_address`/`WriteRegisters::at_address` build register writes in `const`
+//! items. Examples using the `nightly` APIs only run with the feature.
+//!
+//! ## Panics
+//! Buffer sizes given by const generics are checked when the code is built, e.g. a
+//! `ReadBuffer<2>` has no room for the status bytes and fails to compile:
+//! ```compile_fail
+//! use sx126x_spi_buffers::commands::ReadBuffer;
+//!
+//! let read_buffer: ReadBuffer<2> = ReadBuffer::new(0);
+//! ```
+//! Runtime arguments are only checked with a panic by the functions below, documented in their
+//! `Panics` section. Each has a checked variant returning a `Result` or `Option` instead, so
+//! builds that must not panic can avoid them:
+//! - `WriteBuffer::from_slice` and `from_str`: `WriteBuffer::try_from_slice`
+//! - `WriteRegisters::at_address`, `from_slice`, and `from_block` without the `nightly` feature:
+//!   `WriteRegisters::try_at_address`
+//! - `ReadBuffer::write_descriptor`: `ReadBuffer::try_write_descriptor`
+//! - `ChannelPlan::descriptor`: `ChannelPlan::try_descriptor`
+//! - `TxBatch::frame_descriptors`: `TxBatch::try_frame_descriptors`
+//! - `TxTransaction::prepare`: `TxTransaction::try_prepare`
+//! - `LrFhssTx::prepare`: `LrFhssTx::try_prepare`
+//! - with the `llcc68` feature, `SetModulationParamsLora::new` and `new_auto_ldro` and the
+//!   functions built on them, `RadioConfig::with_modulation` and `init`, `LoraPreset::new`,
+//!   `RxWindow::new`, `DataRate::modulation_params` and `lora_phy::modulation_params`:
+//!   `SetModulationParamsLora::try_new`
+//!
+//! With the `transfer-length-u8` feature, descriptors of transfers longer than 255 bytes panic.
+//! The assertions of the `test-utils` and `test-vectors` features panic on mismatches, as they
+//! are meant for tests.
+//!
+//! Outside of tests the crate denies `clippy::panic`, `clippy::unwrap_used`,
+//! `clippy::expect_used`, `clippy::indexing_slicing` and `clippy::missing_panics_doc`, so
+//! `cargo clippy` fails on any other panic path. Only the functions above allow `clippy::panic`,
+//! and only functions whose indices are bounded by construction, e.g. by a loop over `0..N` or
+//! by a [`Slot`](pool::Slot) of the same pool, allow `clippy::indexing_slicing`.
+//!
 #![no_std]
-#![allow(incomplete_features)]
-#![feature(generic_const_exprs)]
-#![feature(const_trait_impl)]
+#![cfg_attr(feature = "nightly", allow(incomplete_features))]
+#![cfg_attr(feature = "nightly", feature(generic_const_exprs))]
+#![cfg_attr(feature = "nightly", feature(const_trait_impl))]
+#![cfg_attr(
+    not(test),
+    deny(
+        clippy::panic,
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::indexing_slicing,
+        clippy::missing_panics_doc
+    )
+)]
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+/// Makes an item `const` only with the `nightly` feature. `[const]` in the item's signature
+/// expands to `const` with the feature and is dropped without it, e.g.
+/// `impl [const] Register for ...` or `pub [const] fn new<R: [const] Register>(...)`.
+///
+/// Stable compilers reject `impl const` and `const` bounds even in configured out code, so they
+/// are only spelled out in the expansion.
+#[cfg(feature = "nightly")]
+macro_rules! maybe_const {
+    (@munch [$($out:tt)*] { $($body:tt)* }) => {
+        $($out)* { $($body)* }
+    };
+    (@munch [$($out:tt)*] [const] $($rest:tt)*) => {
+        $crate::maybe_const! { @munch [$($out)* const] $($rest)* }
+    };
+    (@munch [$($out:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::maybe_const! { @munch [$($out)* $token] $($rest)* }
+    };
+    ($($item:tt)*) => {
+        $crate::maybe_const! { @munch [] $($item)* }
+    };
+}
+#[cfg(not(feature = "nightly"))]
+macro_rules! maybe_const {
+    (@munch [$($out:tt)*] { $($body:tt)* }) => {
+        $($out)* { $($body)* }
+    };
+    (@munch [$($out:tt)*] [const] $($rest:tt)*) => {
+        $crate::maybe_const! { @munch [$($out)*] $($rest)* }
+    };
+    (@munch [$($out:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::maybe_const! { @munch [$($out)* $token] $($rest)* }
+    };
+    ($($item:tt)*) => {
+        $crate::maybe_const! { @munch [] $($item)* }
+    };
+}
+pub(crate) use maybe_const;
+
+pub mod attributes;
+pub mod buffer;
+pub mod cache;
+pub mod channels;
 pub mod commands;
+#[cfg(any(target_has_atomic = "8", feature = "portable-atomic"))]
+pub mod completion;
+pub mod config;
+pub mod debug;
+pub mod decode;
+pub mod dispatch;
+pub mod errata;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod fmt;
+#[cfg(feature = "stm32-gpdma")]
+pub mod gpdma;
+pub mod journal;
+pub mod link;
+#[cfg(feature = "log")]
+pub mod logging;
+#[cfg(feature = "lora-phy")]
+pub mod lora_phy;
+pub mod lorawan;
+pub mod lr_fhss;
+#[cfg(feature = "embedded-hal-mock")]
+pub mod mock;
+pub mod pool;
+pub mod power;
+pub mod presets;
+#[cfg(feature = "heapless")]
+pub mod queue;
+pub mod radio;
+pub mod regions;
 pub mod registers;
+pub mod sequences;
+#[cfg(feature = "stm32wl")]
+pub mod stm32wl;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+#[cfg(feature = "test-vectors")]
+pub mod test_vectors;
+pub mod timestamp;
+pub mod timing;
+pub mod transactions;
+#[cfg(feature = "alloc")]
+pub mod vec;
+pub mod verify;
+
+#[cfg(any(
+    all(feature = "sx1261", feature = "sx1262"),
+    all(feature = "sx1261", feature = "sx1268"),
+    all(feature = "sx1261", feature = "llcc68"),
+    all(feature = "sx1262", feature = "sx1268"),
+    all(feature = "sx1262", feature = "llcc68"),
+    all(feature = "sx1268", feature = "llcc68"),
+))]
+compile_error!("only one of the `sx1261`, `sx1262`, `sx1268` and `llcc68` features can be enabled");
+
+/// Device selected with the `sx1261`, `sx1262`, `sx1268` or `llcc68` feature, the SX1262 if
+/// none is enabled. The checked constructors without a [`Device`](commands::Device) parameter,
+/// e.g. `SetTxParams::try_new`, validate against its limits.
+pub const DEFAULT_DEVICE: commands::Device = if cfg!(feature = "sx1261") {
+    commands::Device::Sx1261
+} else if cfg!(feature = "sx1268") {
+    commands::Device::Sx1268
+} else if cfg!(feature = "llcc68") {
+    commands::Device::Llcc68
+} else {
+    commands::Device::Sx1262
+};
 
 #[cfg(test)]
 mod tests {
     use super::commands::{self, SetSleep, SetStandby, SpiDescriptor, StdbyConfig, WriteBuffer};
+    use crate::command_sequence;
     use arraydeque::ArrayDeque;
 
     unsafe impl Sync for commands::SpiDescriptor {}
@@ -23,7 +186,8 @@ mod tests {
         let mut queue: ArrayDeque<&commands::SpiDescriptor, 8> = ArrayDeque::new();
         static SET_SLEEP_BUFS: SetSleep = commands::SetSleep::new(true);
         static SET_STANDY_BUFS: SetStandby = commands::SetStandby::new(StdbyConfig::StdbyRc);
-        static WRITE_BUFFER_BUFS: WriteBuffer<7> = commands::WriteBuffer::new(0, [2, 4, 7, 9, 3]);
+        static WRITE_BUFFER_BUFS: WriteBuffer<7> =
+            commands::WriteBuffer::from_slice(0, &[2, 4, 7, 9, 3]);
 
         static SET_SLEEP: SpiDescriptor = SET_SLEEP_BUFS.descriptor();
         static SET_STANDBY: SpiDescriptor = SET_STANDY_BUFS.descriptor();
@@ -34,18 +198,37 @@ mod tests {
         let _ = queue.push_back(&WRITE_BUFFER);
 
         let mut desc = queue.pop_front().unwrap();
-        let mut tx_buf =
-            unsafe { core::slice::from_raw_parts(desc.tx_buf_ptr, desc.transfer_length as usize) };
+        let mut tx_buf = unsafe { core::slice::from_raw_parts(desc.tx_buf_ptr, desc.len()) };
         assert_eq!(tx_buf, [0x84, 1 << 2]);
 
         desc = queue.pop_front().unwrap();
-        tx_buf =
-            unsafe { core::slice::from_raw_parts(desc.tx_buf_ptr, desc.transfer_length as usize) };
+        tx_buf = unsafe { core::slice::from_raw_parts(desc.tx_buf_ptr, desc.len()) };
         assert_eq!(tx_buf, [0x80, 0]);
 
         desc = queue.pop_front().unwrap();
-        tx_buf =
-            unsafe { core::slice::from_raw_parts(desc.tx_buf_ptr, desc.transfer_length as usize) };
+        tx_buf = unsafe { core::slice::from_raw_parts(desc.tx_buf_ptr, desc.len()) };
+        assert_eq!(tx_buf, [0x0E, 0, 2, 4, 7, 9, 3]);
+    }
+
+    #[test]
+    fn test_command_sequence() {
+        command_sequence! {
+            static SEQUENCE = {
+                SET_SLEEP: SetSleep = commands::SetSleep::new(true);
+                SET_STANDBY: SetStandby = commands::SetStandby::new(StdbyConfig::StdbyRc);
+                WRITE_BUFFER: WriteBuffer<7> = commands::WriteBuffer::from_slice(0, &[2, 4, 7, 9, 3]);
+            };
+        }
+        assert_eq!(
+            *SEQUENCE.descriptors(),
+            [
+                SET_SLEEP.descriptor(),
+                SET_STANDBY.descriptor(),
+                WRITE_BUFFER.descriptor()
+            ]
+        );
+        let tx_buf =
+            unsafe { core::slice::from_raw_parts(SEQUENCE[2].tx_buf_ptr, SEQUENCE[2].len()) };
         assert_eq!(tx_buf, [0x0E, 0, 2, 4, 7, 9, 3]);
     }
 }
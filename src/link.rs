@@ -0,0 +1,98 @@
+//! LoRa link budget estimation.
+//!
+//! The sensitivities are typical values, derived from the receiver noise figure of 6 dB and the
+//! demodulator SNR limit of each spreading factor in the SX1261/2 datasheet, rounded towards the
+//! less sensitive dBm.
+
+use super::commands::{Bw, Sf};
+
+/// Typical sensitivity in dBm, indexed by spreading factor from SF5 and bandwidth from 7.8 kHz.
+pub const SENSITIVITY: [[i16; 10]; 8] = [
+    [-131, -130, -128, -127, -125, -124, -122, -119, -116, -113],
+    [-134, -132, -131, -129, -128, -126, -125, -122, -119, -116],
+    [-136, -135, -133, -132, -130, -129, -127, -124, -121, -118],
+    [-139, -137, -136, -134, -133, -131, -130, -127, -124, -121],
+    [-141, -140, -138, -137, -135, -134, -132, -129, -126, -123],
+    [-144, -142, -141, -139, -138, -136, -135, -132, -129, -126],
+    [-146, -145, -143, -142, -140, -139, -137, -134, -131, -128],
+    [-149, -147, -146, -144, -143, -141, -140, -137, -134, -131],
+];
+
+/// Index of `bw` in the columns of [`SENSITIVITY`].
+#[inline]
+const fn bw_index(bw: Bw) -> usize {
+    match bw {
+        Bw::Bw7_8 => 0,
+        Bw::Bw10_42 => 1,
+        Bw::Bw15_63 => 2,
+        Bw::Bw20_83 => 3,
+        Bw::Bw31_25 => 4,
+        Bw::Bw41_67 => 5,
+        Bw::Bw62_50 => 6,
+        Bw::Bw125 => 7,
+        Bw::Bw250 => 8,
+        Bw::Bw500 => 9,
+    }
+}
+
+/// Typical sensitivity in dBm for `sf` and `bw`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{commands::{Bw, Sf}, link};
+///
+/// assert_eq!(link::sensitivity(Sf::Sf7, Bw::Bw125), -124);
+/// assert_eq!(link::sensitivity(Sf::Sf12, Bw::Bw125), -137);
+/// ```
+#[inline]
+#[allow(clippy::indexing_slicing)]
+pub const fn sensitivity(sf: Sf, bw: Bw) -> i16 {
+    SENSITIVITY[sf as usize - Sf::Sf5 as usize][bw_index(bw)]
+}
+
+/// Link margin in dB for a TX power of `tx_power` dBm and a path loss of `path_loss` dB, including
+/// antenna gains. A negative margin means the packet is unlikely to be received.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{commands::{Bw, Sf}, link};
+///
+/// assert_eq!(link::link_margin(14, 130, Sf::Sf7, Bw::Bw125), 8);
+/// assert_eq!(link::link_margin(14, 130, Sf::Sf5, Bw::Bw500), -3);
+/// ```
+#[inline]
+pub const fn link_margin(tx_power: i8, path_loss: i16, sf: Sf, bw: Bw) -> i16 {
+    tx_power as i16 - path_loss - sensitivity(sf, bw)
+}
+
+/// Fastest spreading factor from SF5 leaving at least `min_margin` dB of link margin, or `None` if
+/// even SF12 doesn't.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{commands::{Bw, Sf}, link};
+///
+/// assert_eq!(link::fastest_sf(14, 130, Bw::Bw125, 10), Some(Sf::Sf8));
+/// assert_eq!(link::fastest_sf(14, 150, Bw::Bw125, 10), None);
+/// ```
+#[allow(clippy::indexing_slicing)]
+pub const fn fastest_sf(tx_power: i8, path_loss: i16, bw: Bw, min_margin: i16) -> Option<Sf> {
+    const SFS: [Sf; 8] = [
+        Sf::Sf5,
+        Sf::Sf6,
+        Sf::Sf7,
+        Sf::Sf8,
+        Sf::Sf9,
+        Sf::Sf10,
+        Sf::Sf11,
+        Sf::Sf12,
+    ];
+    let mut i: usize = 0;
+    while i < SFS.len() {
+        if link_margin(tx_power, path_loss, SFS[i], bw) >= min_margin {
+            return Some(SFS[i]);
+        }
+        i += 1;
+    }
+    None
+}
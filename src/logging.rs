@@ -0,0 +1,74 @@
+//! Trace logging of commands and responses through the `log` crate, for host-side tests and
+//! Linux spidev users without defmt.
+//!
+//! Call [`command`] when a command is submitted and [`response`] once its transfer has
+//! completed, or [`submitted`] and [`completed`] with the descriptor. Both are logged at trace
+//! level with the `sx126x_spi_buffers::logging` target, commands as rendered by
+//! [`fmt::Command`](super::fmt::Command) and responses with their RX bytes and decoded status:
+//! ```text
+//! SetRfFrequency { 868.1 MHz } => 86 36 41 99 99
+//! GetIrqStatus <= A2 A4 00 02 Status { chip_mode: StbyRc, command_status: DataIsAvailableToHost }
+//! ```
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::{commands::GetIrqStatus, logging};
+//!
+//! let mut get_irq_status = GetIrqStatus::new();
+//! logging::command(&get_irq_status.tx_buf);
+//! get_irq_status.rx_buf = [0xA2, 0xA4, 0x00, 0x02];
+//! logging::response(&get_irq_status.tx_buf, &get_irq_status.rx_buf);
+//! ```
+
+use super::commands::{SpiDescriptor, Status, opcode_name};
+use super::fmt::{Command, Hex};
+
+/// Logs a submitted command.
+pub fn command(tx_buf: &[u8]) {
+    log::trace!("{}", Command(tx_buf));
+}
+
+/// Logs the response to the command in `tx_buf`, with the decoded status byte.
+pub fn response(tx_buf: &[u8], rx_buf: &[u8]) {
+    let name = tx_buf.first().and_then(|&opcode| opcode_name(opcode));
+    match rx_buf.get(1) {
+        Some(&status) => log::trace!(
+            "{} <= {} {:?}",
+            name.unwrap_or("Unknown"),
+            Hex(rx_buf),
+            Status::from_bits(status)
+        ),
+        None => log::trace!("{} <= {}", name.unwrap_or("Unknown"), Hex(rx_buf)),
+    }
+}
+
+/// Logs the command of a submitted descriptor.
+///
+/// ## Safety
+/// The TX buffer of `descriptor` must be valid for reads of `transfer_length` bytes.
+pub unsafe fn submitted(descriptor: &SpiDescriptor) {
+    if descriptor.is_empty() {
+        return;
+    }
+    // SAFETY: guaranteed by the caller
+    command(unsafe { core::slice::from_raw_parts(descriptor.tx_buf_ptr, descriptor.len()) });
+}
+
+/// Logs the response of a completed descriptor.
+///
+/// ## Safety
+/// The TX and RX buffers of `descriptor` must be valid for reads of `transfer_length` bytes, and
+/// not written while logging.
+pub unsafe fn completed(descriptor: &SpiDescriptor) {
+    if descriptor.is_empty() {
+        return;
+    }
+    // SAFETY: guaranteed by the caller
+    let (tx_buf, rx_buf) = unsafe {
+        (
+            core::slice::from_raw_parts(descriptor.tx_buf_ptr, descriptor.len()),
+            core::slice::from_raw_parts(descriptor.rx_buf_ptr, descriptor.len()),
+        )
+    };
+    response(tx_buf, rx_buf);
+}
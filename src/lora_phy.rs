@@ -0,0 +1,807 @@
+//! Interoperability with [`lora-phy`](https://crates.io/crates/lora-phy), the physical layer of
+//! the lora-rs LoRaWAN stack.
+//!
+//! [`Sx126x`] implements the `lora-phy` `RadioKind` trait with the command buffers of this crate,
+//! so a `lora-phy` based stack drives the radio through an [`Executor`], e.g. an SPI/DMA backend
+//! owned by the application. The `lora-phy` `InterfaceVariant` handles the reset, BUSY, DIO and
+//! RF switch lines as with `lora-phy`'s own SX126x implementation.
+//!
+//! The From conversions and [`modulation_params`] build commands from the `lora-phy` modulation
+//! parameters, for applications driving the radio themselves.
+
+use super::DEFAULT_DEVICE;
+use super::commands::{
+    Bw, CadExitMode, CalibrateImage, ClearIrqStatus, Command, Cr, Device, GetIrqStatus,
+    GetPacketStatusLora, GetRxBufferStatus, GetStatus, HeaderType, ImageCalibrationBand, InvertIq,
+    Irq, MAX_PAYLOAD_LEN, MaxReadBuffer, MaxWriteBuffer, SetBufferBaseAddress, SetCad,
+    SetCadParams, SetDioIrqParams, SetLoraSymbNumTimeout, SetModulationParamsLora, SetPaConfig,
+    SetPacketParams, SetRfFrequency, SetRx, SetRxDutyCycle, SetSleep, SetStandby, SetTx,
+    SetTxContinuousWave, SetTxParams, Sf, SpiDescriptor, StdbyConfig, WriteBuffer, WriteRegisters,
+    transfer_length,
+};
+use super::config::RadioConfig;
+use super::registers::{LoraSyncWord, Register16};
+use embedded_hal_async::delay::DelayNs;
+use lora_phy::mod_params::{
+    Bandwidth, CodingRate, ModulationParams, PacketParams, PacketStatus, RadioError, RadioMode,
+    RxMode, SpreadingFactor,
+};
+use lora_phy::mod_traits::{InterfaceVariant, IrqState, RadioKind};
+
+impl From<SpreadingFactor> for Sf {
+    #[inline]
+    fn from(sf: SpreadingFactor) -> Self {
+        match sf {
+            SpreadingFactor::_5 => Sf::Sf5,
+            SpreadingFactor::_6 => Sf::Sf6,
+            SpreadingFactor::_7 => Sf::Sf7,
+            SpreadingFactor::_8 => Sf::Sf8,
+            SpreadingFactor::_9 => Sf::Sf9,
+            SpreadingFactor::_10 => Sf::Sf10,
+            SpreadingFactor::_11 => Sf::Sf11,
+            SpreadingFactor::_12 => Sf::Sf12,
+        }
+    }
+}
+impl From<Sf> for SpreadingFactor {
+    #[inline]
+    fn from(sf: Sf) -> Self {
+        match sf {
+            Sf::Sf5 => SpreadingFactor::_5,
+            Sf::Sf6 => SpreadingFactor::_6,
+            Sf::Sf7 => SpreadingFactor::_7,
+            Sf::Sf8 => SpreadingFactor::_8,
+            Sf::Sf9 => SpreadingFactor::_9,
+            Sf::Sf10 => SpreadingFactor::_10,
+            Sf::Sf11 => SpreadingFactor::_11,
+            Sf::Sf12 => SpreadingFactor::_12,
+        }
+    }
+}
+
+impl From<Bandwidth> for Bw {
+    #[inline]
+    fn from(bw: Bandwidth) -> Self {
+        match bw {
+            Bandwidth::_7KHz => Bw::Bw7_8,
+            Bandwidth::_10KHz => Bw::Bw10_42,
+            Bandwidth::_15KHz => Bw::Bw15_63,
+            Bandwidth::_20KHz => Bw::Bw20_83,
+            Bandwidth::_31KHz => Bw::Bw31_25,
+            Bandwidth::_41KHz => Bw::Bw41_67,
+            Bandwidth::_62KHz => Bw::Bw62_50,
+            Bandwidth::_125KHz => Bw::Bw125,
+            Bandwidth::_250KHz => Bw::Bw250,
+            Bandwidth::_500KHz => Bw::Bw500,
+        }
+    }
+}
+impl From<Bw> for Bandwidth {
+    #[inline]
+    fn from(bw: Bw) -> Self {
+        match bw {
+            Bw::Bw7_8 => Bandwidth::_7KHz,
+            Bw::Bw10_42 => Bandwidth::_10KHz,
+            Bw::Bw15_63 => Bandwidth::_15KHz,
+            Bw::Bw20_83 => Bandwidth::_20KHz,
+            Bw::Bw31_25 => Bandwidth::_31KHz,
+            Bw::Bw41_67 => Bandwidth::_41KHz,
+            Bw::Bw62_50 => Bandwidth::_62KHz,
+            Bw::Bw125 => Bandwidth::_125KHz,
+            Bw::Bw250 => Bandwidth::_250KHz,
+            Bw::Bw500 => Bandwidth::_500KHz,
+        }
+    }
+}
+
+/// `lora-phy` has no long interleaving coding rates.
+impl From<CodingRate> for Cr {
+    #[inline]
+    fn from(cr: CodingRate) -> Self {
+        match cr {
+            CodingRate::_4_5 => Cr::Cr4_5,
+            CodingRate::_4_6 => Cr::Cr4_6,
+            CodingRate::_4_7 => Cr::Cr4_7,
+            CodingRate::_4_8 => Cr::Cr4_8,
+        }
+    }
+}
+
+/// `SetModulationParamsLora` for `lora-phy` parameters, with low data rate optimization enabled
+/// when required.
+///
+/// ## Panics
+/// With the `llcc68` feature, if the LLCC68 does not support `sf` at `bw`.
+///
+/// ## Example
+/// ```
+/// use lora_phy::mod_params::{Bandwidth, CodingRate, SpreadingFactor};
+/// use sx126x_spi_buffers::lora_phy::modulation_params;
+///
+/// let set_modulation_params =
+///     modulation_params(SpreadingFactor::_9, Bandwidth::_500KHz, CodingRate::_4_5);
+/// assert_eq!(set_modulation_params.tx_buf, [0x8B, 0x09, 0x06, 0x01, 0]);
+/// ```
+#[inline]
+pub fn modulation_params(
+    sf: SpreadingFactor,
+    bw: Bandwidth,
+    cr: CodingRate,
+) -> SetModulationParamsLora {
+    SetModulationParamsLora::new_auto_ldro(sf.into(), bw.into(), cr.into())
+}
+
+/// # Executor
+/// Asynchronous SPI/DMA backend of [`Sx126x`], transferring one descriptor per NSS transaction.
+#[allow(async_fn_in_trait)]
+pub trait Executor {
+    type Error;
+    /// Transfers `descriptor`, completing when its RX buffer holds the response.
+    ///
+    /// ## Safety
+    /// The TX buffer of `descriptor` must be valid for reads and its RX buffer valid for writes
+    /// of `transfer_length` bytes. The RX pointer must be derived from a mutable borrow, e.g.
+    /// with [`Command::descriptor_mut`], and no reference to the buffers may be used until the
+    /// returned future completes.
+    async unsafe fn execute(&mut self, descriptor: SpiDescriptor) -> Result<(), Self::Error>;
+}
+#[cfg(feature = "embedded-hal-mock")]
+impl Executor for super::mock::MockExecutor {
+    type Error = embedded_hal::spi::ErrorKind;
+    #[inline]
+    async unsafe fn execute(&mut self, descriptor: SpiDescriptor) -> Result<(), Self::Error> {
+        // SAFETY: guaranteed by the caller
+        unsafe { super::mock::MockExecutor::execute(self, descriptor) }
+    }
+}
+
+/// Waits for BUSY low, then transfers `descriptor`. Any executor error is reported as
+/// `RadioError::SPI`.
+///
+/// ## Safety
+/// See [`Executor::execute`].
+async unsafe fn transfer(
+    executor: &mut impl Executor,
+    iv: &mut impl InterfaceVariant,
+    descriptor: SpiDescriptor,
+) -> Result<(), RadioError> {
+    iv.wait_on_busy().await?;
+    // SAFETY: guaranteed by the caller
+    unsafe { executor.execute(descriptor) }
+        .await
+        .map_err(|_| RadioError::SPI)
+}
+
+/// # Sx126x
+/// `lora-phy` `RadioKind` of an SX126x driven through the executor `E`, with the reset, BUSY,
+/// DIO and RF switch lines of the `InterfaceVariant` `IV`.
+///
+/// The regulator mode, TCXO, DIO2 RF switch control and ramp time are those of the
+/// [`RadioConfig`] given to `new()`: `init_lora()` runs the start of its
+/// [`init()`](RadioConfig::init) sequence, up to the packet type. `lora-phy` sets the other
+/// parameters. Output powers and frequencies are checked against the device, by default the
+/// [`DEFAULT_DEVICE`](crate::DEFAULT_DEVICE), see `with_device()`.
+///
+/// Payloads are written and read with a [`MaxWriteBuffer`] and a [`MaxReadBuffer`] owned by
+/// the adapter, transferring only the payload bytes.
+pub struct Sx126x<E, IV> {
+    executor: E,
+    iv: IV,
+    config: RadioConfig,
+    device: Device,
+    tx_base_address: u8,
+    rx_base_address: u8,
+    write_buffer: MaxWriteBuffer,
+    read_buffer: MaxReadBuffer,
+}
+impl<E: Executor, IV: InterfaceVariant> Sx126x<E, IV> {
+    #[inline]
+    pub fn new(executor: E, iv: IV, config: RadioConfig) -> Self {
+        Self {
+            executor,
+            iv,
+            config,
+            device: DEFAULT_DEVICE,
+            tx_base_address: 0,
+            rx_base_address: 0,
+            write_buffer: MaxWriteBuffer::from_slice(0, &[]),
+            read_buffer: MaxReadBuffer::new(0),
+        }
+    }
+    /// Checks output powers, frequencies and modulations against `device`.
+    #[inline]
+    pub fn with_device(mut self, device: Device) -> Self {
+        self.device = device;
+        self
+    }
+    /// Executor and `InterfaceVariant`.
+    #[inline]
+    pub fn release(self) -> (E, IV) {
+        (self.executor, self.iv)
+    }
+    async fn execute(&mut self, command: &mut impl Command) -> Result<(), RadioError> {
+        // SAFETY: the pointers are derived from the mutable borrow of `command`, held until the
+        // transfer has completed
+        unsafe { transfer(&mut self.executor, &mut self.iv, command.descriptor_mut()) }.await
+    }
+}
+
+/// IRQs of `radio_mode`, mapped to DIO1.
+fn irq_mask(radio_mode: Option<RadioMode>) -> Irq {
+    match radio_mode {
+        Some(RadioMode::Transmit) => Irq::TX_DONE | Irq::TIMEOUT,
+        Some(RadioMode::Receive(_)) => {
+            Irq::RX_DONE | Irq::TIMEOUT | Irq::PREAMBLE_DETECTED | Irq::HEADER_ERR | Irq::CRC_ERR
+        }
+        Some(RadioMode::ChannelActivityDetection) => Irq::CAD_DONE | Irq::CAD_DETECTED,
+        _ => Irq::NONE,
+    }
+}
+
+/// `SetRx` timeout of continuous reception.
+const RX_CONTINUOUS: u32 = 0xFF_FFFF;
+/// Time to wait after `SetSleep` before waking the device, in microseconds.
+const SLEEP_SETTLE_US: u32 = 500;
+
+impl<E: Executor, IV: InterfaceVariant> RadioKind for Sx126x<E, IV> {
+    /// Runs the [`RadioConfig::init`] sequence up to the packet type, then sets the buffer base
+    /// addresses and the `lora-phy` sync word, e.g. `0x34` for `0x3444`.
+    async fn init_lora(&mut self, sync_word: u8) -> Result<(), RadioError> {
+        let mut init = self.config.init();
+        self.execute(&mut init.set_standby).await?;
+        self.execute(&mut init.set_regulator_mode).await?;
+        if let Some(set_dio3_as_tcxo_ctrl) = &mut init.set_dio3_as_tcxo_ctrl {
+            self.execute(set_dio3_as_tcxo_ctrl).await?;
+        }
+        self.execute(&mut init.calibrate).await?;
+        self.execute(&mut init.set_dio2_as_rf_switch_ctrl).await?;
+        self.execute(&mut init.set_packet_type).await?;
+        self.execute(&mut SetBufferBaseAddress::new(
+            self.tx_base_address,
+            self.rx_base_address,
+        ))
+        .await?;
+        let sync_word =
+            u16::from_be_bytes([(sync_word & 0xF0) | 0x04, ((sync_word & 0x0F) << 4) | 0x04]);
+        self.execute(&mut WriteRegisters::<5>::at_address(
+            <LoraSyncWord as Register16>::ADDRESS,
+            &sync_word.to_be_bytes(),
+        ))
+        .await
+    }
+    fn create_modulation_params(
+        &self,
+        spreading_factor: SpreadingFactor,
+        bandwidth: Bandwidth,
+        coding_rate: CodingRate,
+        frequency_in_hz: u32,
+    ) -> Result<ModulationParams, RadioError> {
+        let (sf, bw) = (spreading_factor.into(), bandwidth.into());
+        if !self.device.supports(sf, bw) {
+            return Err(RadioError::UnavailableSpreadingFactor);
+        }
+        let [_, _, _, _, low_data_rate_optimize] =
+            SetModulationParamsLora::new_auto_ldro(sf, bw, coding_rate.into()).tx_buf;
+        Ok(ModulationParams {
+            spreading_factor,
+            bandwidth,
+            coding_rate,
+            low_data_rate_optimize,
+            frequency_in_hz,
+        })
+    }
+    fn create_packet_params(
+        &self,
+        preamble_length: u16,
+        implicit_header: bool,
+        payload_length: u8,
+        crc_on: bool,
+        iq_inverted: bool,
+        _modulation_params: &ModulationParams,
+    ) -> Result<PacketParams, RadioError> {
+        Ok(PacketParams {
+            preamble_length,
+            implicit_header,
+            payload_length,
+            crc_on,
+            iq_inverted,
+        })
+    }
+    async fn reset(&mut self, delay: &mut impl DelayNs) -> Result<(), RadioError> {
+        self.iv.reset(delay).await
+    }
+    /// Wakes the device from sleep with a `GetStatus`.
+    async fn ensure_ready(&mut self, mode: RadioMode) -> Result<(), RadioError> {
+        if mode == RadioMode::Sleep {
+            self.execute(&mut GetStatus::new()).await?;
+        }
+        Ok(())
+    }
+    async fn set_standby(&mut self) -> Result<(), RadioError> {
+        self.execute(&mut SetStandby::new(StdbyConfig::StdbyRc))
+            .await?;
+        self.iv.disable_rf_switch().await
+    }
+    async fn set_sleep(
+        &mut self,
+        warm_start_if_possible: bool,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), RadioError> {
+        self.iv.disable_rf_switch().await?;
+        self.execute(&mut SetSleep::new(warm_start_if_possible))
+            .await?;
+        delay.delay_us(SLEEP_SETTLE_US).await;
+        Ok(())
+    }
+    async fn set_tx_rx_buffer_base_address(
+        &mut self,
+        tx_base_addr: usize,
+        rx_base_addr: usize,
+    ) -> Result<(), RadioError> {
+        let (Ok(tx_base_address), Ok(rx_base_address)) =
+            (u8::try_from(tx_base_addr), u8::try_from(rx_base_addr))
+        else {
+            return Err(RadioError::InvalidBaseAddress(tx_base_addr, rx_base_addr));
+        };
+        self.tx_base_address = tx_base_address;
+        self.rx_base_address = rx_base_address;
+        self.execute(&mut SetBufferBaseAddress::new(
+            tx_base_address,
+            rx_base_address,
+        ))
+        .await
+    }
+    /// Sets the [`SetPaConfig::optimal_for`] PA config of `output_power` in dBm, or the one of
+    /// the maximum output power with a lower `SetTxParams` power, and the ramp time of the
+    /// [`RadioConfig`].
+    async fn set_tx_power_and_ramp_time(
+        &mut self,
+        output_power: i32,
+        _mdltn_params: Option<&ModulationParams>,
+        _is_tx_prep: bool,
+    ) -> Result<(), RadioError> {
+        let power = i8::try_from(output_power)
+            .ok()
+            .filter(|&power| power <= self.device.max_output_power())
+            .ok_or(RadioError::InvalidOutputPowerForFrequency)?;
+        let (mut set_pa_config, tx_power) = match SetPaConfig::optimal_for(self.device, power) {
+            Some(optimal) => optimal,
+            None => {
+                let max_output_power = self.device.max_output_power();
+                let (set_pa_config, max_tx_power) =
+                    SetPaConfig::optimal_for(self.device, max_output_power)
+                        .ok_or(RadioError::InvalidOutputPowerForFrequency)?;
+                (set_pa_config, power - (max_output_power - max_tx_power))
+            }
+        };
+        let mut set_tx_params =
+            SetTxParams::try_new_for(self.device, tx_power, self.config.ramp_time)
+                .map_err(|_| RadioError::InvalidOutputPowerForFrequency)?;
+        self.execute(&mut set_pa_config).await?;
+        self.execute(&mut set_tx_params).await
+    }
+    async fn set_modulation_params(
+        &mut self,
+        mdltn_params: &ModulationParams,
+    ) -> Result<(), RadioError> {
+        let mut set_modulation_params = SetModulationParamsLora::try_new(
+            self.device,
+            mdltn_params.spreading_factor.into(),
+            mdltn_params.bandwidth.into(),
+            mdltn_params.coding_rate.into(),
+            mdltn_params.low_data_rate_optimize != 0,
+        )
+        .map_err(|_| RadioError::UnavailableSpreadingFactor)?;
+        self.execute(&mut set_modulation_params).await
+    }
+    async fn set_packet_params(&mut self, pkt_params: &PacketParams) -> Result<(), RadioError> {
+        self.execute(&mut SetPacketParams::new(
+            pkt_params.preamble_length,
+            if pkt_params.implicit_header {
+                HeaderType::FixedLength
+            } else {
+                HeaderType::VariableLength
+            },
+            pkt_params.payload_length,
+            pkt_params.crc_on,
+            if pkt_params.iq_inverted {
+                InvertIq::Inverted
+            } else {
+                InvertIq::Standard
+            },
+        ))
+        .await
+    }
+    /// Calibrates the [`ImageCalibrationBand`] of `frequency_in_hz`, rejecting frequencies
+    /// outside of the bands.
+    async fn calibrate_image(&mut self, frequency_in_hz: u32) -> Result<(), RadioError> {
+        let band = ImageCalibrationBand::from_hz(frequency_in_hz)
+            .ok_or(RadioError::InvalidConfiguration)?;
+        self.execute(&mut CalibrateImage::for_band(band)).await
+    }
+    async fn set_channel(&mut self, frequency_in_hz: u32) -> Result<(), RadioError> {
+        let mut set_rf_frequency = SetRfFrequency::try_from_hz_for(self.device, frequency_in_hz)
+            .map_err(|_| RadioError::InvalidConfiguration)?;
+        self.execute(&mut set_rf_frequency).await
+    }
+    /// Writes `payload` at the TX base address, transferring only the payload bytes.
+    async fn set_payload(&mut self, payload: &[u8]) -> Result<(), RadioError> {
+        self.write_buffer = WriteBuffer::try_from_slice(self.tx_base_address, payload)
+            .map_err(|_| RadioError::PayloadSizeUnexpected(payload.len()))?;
+        let descriptor = SpiDescriptor {
+            transfer_length: transfer_length(payload.len() + 2),
+            ..self.write_buffer.descriptor_mut()
+        };
+        // SAFETY: the pointers are derived from a mutable borrow of the buffer, which is not
+        // used until the transfer has completed
+        unsafe { transfer(&mut self.executor, &mut self.iv, descriptor) }.await
+    }
+    async fn do_tx(&mut self) -> Result<(), RadioError> {
+        self.iv.enable_rf_switch_tx().await?;
+        self.execute(&mut SetTx::new(0)).await
+    }
+    /// Receives until a packet or the symbol timeout of `RxMode::Single`, saturated at 255
+    /// symbols.
+    async fn do_rx(&mut self, rx_mode: RxMode) -> Result<(), RadioError> {
+        self.iv.enable_rf_switch_rx().await?;
+        match rx_mode {
+            RxMode::Continuous => self.execute(&mut SetRx::new(RX_CONTINUOUS)).await,
+            RxMode::Single(symbols) => {
+                let symbols = u8::try_from(symbols).unwrap_or(u8::MAX);
+                self.execute(&mut SetLoraSymbNumTimeout::new(symbols))
+                    .await?;
+                self.execute(&mut SetRx::new(0)).await
+            }
+            RxMode::DutyCycle(params) => {
+                self.execute(&mut SetRxDutyCycle::new(params.rx_time, params.sleep_time))
+                    .await
+            }
+        }
+    }
+    /// Reads the received payload, of the `rx_pkt_params` length with an implicit header,
+    /// transferring only the payload bytes.
+    async fn get_rx_payload(
+        &mut self,
+        rx_pkt_params: &PacketParams,
+        receiving_buffer: &mut [u8],
+    ) -> Result<u8, RadioError> {
+        let mut get_rx_buffer_status = GetRxBufferStatus::new();
+        self.execute(&mut get_rx_buffer_status).await?;
+        let len = if rx_pkt_params.implicit_header {
+            rx_pkt_params.payload_length
+        } else {
+            get_rx_buffer_status.payload_length_rx()
+        };
+        let payload_len = len as usize;
+        if payload_len > MAX_PAYLOAD_LEN {
+            return Err(RadioError::PayloadSizeUnexpected(payload_len));
+        }
+        let Some(received) = receiving_buffer.get_mut(..payload_len) else {
+            return Err(RadioError::PayloadSizeMismatch(
+                payload_len,
+                receiving_buffer.len(),
+            ));
+        };
+        self.read_buffer
+            .set_offset(get_rx_buffer_status.rx_start_buffer_pointer());
+        let descriptor = SpiDescriptor {
+            transfer_length: transfer_length(payload_len + 3),
+            ..self.read_buffer.descriptor_mut()
+        };
+        // SAFETY: the pointers are derived from a mutable borrow of the buffer, which is not
+        // used until the transfer has completed
+        unsafe { transfer(&mut self.executor, &mut self.iv, descriptor) }.await?;
+        let data = self
+            .read_buffer
+            .data()
+            .get(..payload_len)
+            .ok_or(RadioError::PayloadSizeUnexpected(payload_len))?;
+        received.copy_from_slice(data);
+        Ok(len)
+    }
+    async fn get_rx_packet_status(&mut self) -> Result<PacketStatus, RadioError> {
+        let mut get_packet_status = GetPacketStatusLora::new();
+        self.execute(&mut get_packet_status).await?;
+        Ok(PacketStatus {
+            rssi: get_packet_status.rssi_pkt().into(),
+            snr: get_packet_status.snr_pkt().into(),
+        })
+    }
+    /// Detects activity with the [`SetCadParams::recommended`] parameters.
+    async fn do_cad(&mut self, mdltn_params: &ModulationParams) -> Result<(), RadioError> {
+        self.iv.enable_rf_switch_rx().await?;
+        self.execute(&mut SetCadParams::recommended(
+            mdltn_params.spreading_factor.into(),
+            mdltn_params.bandwidth.into(),
+            CadExitMode::CadOnly,
+            0,
+        ))
+        .await?;
+        self.execute(&mut SetCad::new()).await
+    }
+    /// Enables the IRQs of `radio_mode` on DIO1, none without a mode.
+    async fn set_irq_params(&mut self, radio_mode: Option<RadioMode>) -> Result<(), RadioError> {
+        let mask = irq_mask(radio_mode);
+        self.execute(&mut SetDioIrqParams::new(mask, mask, Irq::NONE, Irq::NONE))
+            .await
+    }
+    async fn set_tx_continuous_wave_mode(&mut self) -> Result<(), RadioError> {
+        self.iv.enable_rf_switch_tx().await?;
+        self.execute(&mut SetTxContinuousWave::new()).await
+    }
+    async fn await_irq(&mut self) -> Result<(), RadioError> {
+        self.iv.await_irq().await
+    }
+    /// Reads the IRQ status, clearing it if `clear_interrupts`. CRC and header errors yield
+    /// `None`, so that `lora-phy` keeps waiting for a valid packet.
+    async fn process_irq_event(
+        &mut self,
+        radio_mode: RadioMode,
+        cad_activity_detected: Option<&mut bool>,
+        clear_interrupts: bool,
+    ) -> Result<Option<IrqState>, RadioError> {
+        let mut get_irq_status = GetIrqStatus::new();
+        self.execute(&mut get_irq_status).await?;
+        let irq = get_irq_status.irq_status();
+        if clear_interrupts && !irq.is_empty() {
+            self.execute(&mut ClearIrqStatus::new(irq)).await?;
+        }
+        match radio_mode {
+            RadioMode::Transmit if irq.tx_done() => Ok(Some(IrqState::Done)),
+            RadioMode::Transmit if irq.timeout() => Err(RadioError::TransmitTimeout),
+            RadioMode::Receive(_) if irq.header_err() || irq.crc_err() => Ok(None),
+            RadioMode::Receive(_) if irq.rx_done() => Ok(Some(IrqState::Done)),
+            RadioMode::Receive(_) if irq.timeout() => Err(RadioError::ReceiveTimeout),
+            RadioMode::Receive(_) if irq.preamble_detected() => {
+                Ok(Some(IrqState::PreambleReceived))
+            }
+            RadioMode::ChannelActivityDetection if irq.cad_done() => {
+                if let Some(cad_activity_detected) = cad_activity_detected {
+                    *cad_activity_detected = irq.cad_detected();
+                }
+                Ok(Some(IrqState::Done))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "embedded-hal-mock"))]
+mod tests {
+    use super::*;
+    use crate::mock::{Exchange, MockExecutor};
+    use alloc::vec::Vec;
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    /// Records the calls other than `wait_on_busy`.
+    #[derive(Default)]
+    struct MockInterface {
+        calls: Vec<&'static str>,
+    }
+    impl InterfaceVariant for MockInterface {
+        async fn reset(&mut self, _delay: &mut impl DelayNs) -> Result<(), RadioError> {
+            self.calls.push("reset");
+            Ok(())
+        }
+        async fn wait_on_busy(&mut self) -> Result<(), RadioError> {
+            Ok(())
+        }
+        async fn await_irq(&mut self) -> Result<(), RadioError> {
+            self.calls.push("await_irq");
+            Ok(())
+        }
+        async fn enable_rf_switch_rx(&mut self) -> Result<(), RadioError> {
+            self.calls.push("rx");
+            Ok(())
+        }
+        async fn enable_rf_switch_tx(&mut self) -> Result<(), RadioError> {
+            self.calls.push("tx");
+            Ok(())
+        }
+        async fn disable_rf_switch(&mut self) -> Result<(), RadioError> {
+            self.calls.push("off");
+            Ok(())
+        }
+    }
+
+    fn radio(exchanges: &[Exchange<'_>]) -> Sx126x<MockExecutor, MockInterface> {
+        Sx126x::new(
+            MockExecutor::new(exchanges),
+            MockInterface::default(),
+            RadioConfig::new(0x3640_0000),
+        )
+        .with_device(Device::Sx1262)
+    }
+
+    #[test]
+    fn test_init_lora() {
+        let mut radio = radio(&[
+            Exchange {
+                tx: &[0x80, 0x00],
+                rx: &[0; 2],
+            },
+            Exchange {
+                tx: &[0x96, 0x00],
+                rx: &[0; 2],
+            },
+            Exchange {
+                tx: &[0x89, 0x7F],
+                rx: &[0; 2],
+            },
+            Exchange {
+                tx: &[0x9D, 0x00],
+                rx: &[0; 2],
+            },
+            Exchange {
+                tx: &[0x8A, 0x01],
+                rx: &[0; 2],
+            },
+            Exchange {
+                tx: &[0x8F, 0x00, 0x00],
+                rx: &[0; 3],
+            },
+            Exchange {
+                tx: &[0x0D, 0x07, 0x40, 0x34, 0x44],
+                rx: &[0; 5],
+            },
+        ]);
+        block_on(radio.init_lora(0x34)).unwrap();
+        radio.release().0.done();
+    }
+
+    #[test]
+    fn test_tx() {
+        let mut radio = radio(&[
+            Exchange {
+                tx: &[0x95, 0x04, 0x07, 0x00, 0x01],
+                rx: &[0; 5],
+            },
+            Exchange {
+                tx: &[0x8E, 10, 0x04],
+                rx: &[0; 3],
+            },
+            Exchange {
+                tx: &[0x0E, 0x00, b'h', b'e', b'l', b'l', b'o'],
+                rx: &[0; 7],
+            },
+            Exchange {
+                tx: &[0x83, 0, 0, 0],
+                rx: &[0; 4],
+            },
+            Exchange {
+                tx: &[0x12, 0, 0, 0],
+                rx: &[0xA2, 0xA2, 0x00, 0x01],
+            },
+            Exchange {
+                tx: &[0x02, 0x00, 0x01],
+                rx: &[0; 3],
+            },
+        ]);
+        block_on(radio.set_tx_power_and_ramp_time(10, None, true)).unwrap();
+        block_on(radio.set_payload(b"hello")).unwrap();
+        block_on(radio.do_tx()).unwrap();
+        let irq_state = block_on(radio.process_irq_event(RadioMode::Transmit, None, true));
+        assert_eq!(irq_state, Ok(Some(IrqState::Done)));
+        let (executor, iv) = radio.release();
+        assert_eq!(iv.calls, ["tx"]);
+        executor.done();
+    }
+
+    #[test]
+    fn test_rx() {
+        let mut radio = radio(&[
+            Exchange {
+                tx: &[0x13, 0, 0, 0],
+                rx: &[0xD2, 0xD2, 5, 0x80],
+            },
+            Exchange {
+                tx: &[0x1E, 0x80, 0, 0, 0, 0, 0, 0],
+                rx: &[0xD2, 0xD2, 0xD2, b'h', b'e', b'l', b'l', b'o'],
+            },
+            Exchange {
+                tx: &[0x14, 0, 0, 0, 0],
+                rx: &[0xD2, 0xD2, 184, 20, 170],
+            },
+        ]);
+        let modulation_params = radio
+            .create_modulation_params(
+                SpreadingFactor::_9,
+                Bandwidth::_125KHz,
+                CodingRate::_4_5,
+                868_100_000,
+            )
+            .unwrap();
+        let packet_params = radio
+            .create_packet_params(8, false, 255, true, false, &modulation_params)
+            .unwrap();
+        let mut payload = [0; 8];
+        let len = block_on(radio.get_rx_payload(&packet_params, &mut payload)).unwrap();
+        assert_eq!(&payload[..len as usize], b"hello");
+        let packet_status = block_on(radio.get_rx_packet_status()).unwrap();
+        assert_eq!(packet_status, PacketStatus { rssi: -92, snr: 5 });
+        radio.release().0.done();
+    }
+
+    #[test]
+    fn test_process_irq_event() {
+        let mut radio = radio(&[
+            Exchange {
+                tx: &[0x12, 0, 0, 0],
+                rx: &[0xD2, 0xD2, 0x00, 0x44],
+            },
+            Exchange {
+                tx: &[0x12, 0, 0, 0],
+                rx: &[0xD2, 0xD2, 0x01, 0x80],
+            },
+            Exchange {
+                tx: &[0x02, 0x01, 0x80],
+                rx: &[0; 3],
+            },
+            Exchange {
+                tx: &[0x12, 0, 0, 0],
+                rx: &[0xD2, 0xD2, 0x02, 0x00],
+            },
+        ]);
+        let receive = RadioMode::Receive(RxMode::Continuous);
+        assert_eq!(
+            block_on(radio.process_irq_event(receive, None, false)),
+            Ok(None)
+        );
+        let mut cad_activity_detected = false;
+        let irq_state = block_on(radio.process_irq_event(
+            RadioMode::ChannelActivityDetection,
+            Some(&mut cad_activity_detected),
+            true,
+        ));
+        assert_eq!(irq_state, Ok(Some(IrqState::Done)));
+        assert!(cad_activity_detected);
+        assert_eq!(
+            block_on(radio.process_irq_event(receive, None, false)),
+            Err(RadioError::ReceiveTimeout)
+        );
+        radio.release().0.done();
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let mut radio = radio(&[]);
+        assert_eq!(
+            block_on(radio.set_tx_power_and_ramp_time(23, None, true)),
+            Err(RadioError::InvalidOutputPowerForFrequency)
+        );
+        assert_eq!(
+            block_on(radio.set_tx_rx_buffer_base_address(0, 256)),
+            Err(RadioError::InvalidBaseAddress(0, 256))
+        );
+        assert_eq!(
+            block_on(radio.calibrate_image(300_000_000)),
+            Err(RadioError::InvalidConfiguration)
+        );
+        assert_eq!(
+            block_on(radio.set_payload(&[0; MAX_PAYLOAD_LEN + 1])),
+            Err(RadioError::PayloadSizeUnexpected(MAX_PAYLOAD_LEN + 1))
+        );
+        let llcc68 = radio.with_device(Device::Llcc68);
+        assert_eq!(
+            llcc68.create_modulation_params(
+                SpreadingFactor::_12,
+                Bandwidth::_125KHz,
+                CodingRate::_4_5,
+                868_100_000,
+            ),
+            Err(RadioError::UnavailableSpreadingFactor)
+        );
+        llcc68.release().0.done();
+    }
+}
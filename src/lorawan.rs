@@ -0,0 +1,269 @@
+//! LoRaWAN regional channel plans and data rates, from the LoRaWAN regional parameters
+//! (RP002-1.0.3), for building minimal LoRaWAN or LoRaWAN-like stacks.
+//!
+//! Maximum payload sizes are `M`, the MAC payload including the frame header, without dwell
+//! time limits.
+
+use super::channels::ChannelPlan;
+use super::commands::{Bw, Cr, Device, RampTime, SetModulationParamsLora, SetTxParams, Sf};
+use super::regions::Region;
+
+/// # DataRate
+/// LoRa spreading factor and bandwidth of a LoRaWAN data rate, with its maximum MAC payload size.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{
+///     commands::{Bw, Cr, Sf},
+///     lorawan::{self, DataRate},
+///     regions::Region,
+/// };
+///
+/// let dr0 = lorawan::data_rate(Region::Eu868, 0).unwrap();
+/// assert_eq!(dr0, DataRate { sf: Sf::Sf12, bw: Bw::Bw125, max_payload: 59 });
+/// # #[cfg(not(feature = "llcc68"))]
+/// assert_eq!(dr0.modulation_params().tx_buf, [0x8B, 0x0C, 0x04, 0x01, 1]);
+///
+/// assert_eq!(lorawan::data_rate(Region::Us915, 4).unwrap().bw, Bw::Bw500);
+/// assert_eq!(lorawan::data_rate(Region::Us915, 5), None);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DataRate {
+    pub sf: Sf,
+    pub bw: Bw,
+    /// Maximum MAC payload size in bytes.
+    pub max_payload: u8,
+}
+impl DataRate {
+    #[inline]
+    const fn new(sf: Sf, bw: Bw, max_payload: u8) -> Option<Self> {
+        Some(Self {
+            sf,
+            bw,
+            max_payload,
+        })
+    }
+    /// `SetModulationParamsLora` with the LoRaWAN coding rate 4/5.
+    ///
+    /// ## Panics
+    /// With the `llcc68` feature, if the LLCC68 does not support the data rate, e.g. EU868 DR0.
+    #[inline]
+    pub const fn modulation_params(&self) -> SetModulationParamsLora {
+        SetModulationParamsLora::new_auto_ldro(self.sf, self.bw, Cr::Cr4_5)
+    }
+}
+
+/// EU868 data rates, indexed by DR. DR7 is GFSK and DR8 - DR11 LR-FHSS.
+pub const EU868_DATA_RATES: [Option<DataRate>; 16] = [
+    DataRate::new(Sf::Sf12, Bw::Bw125, 59),
+    DataRate::new(Sf::Sf11, Bw::Bw125, 59),
+    DataRate::new(Sf::Sf10, Bw::Bw125, 59),
+    DataRate::new(Sf::Sf9, Bw::Bw125, 123),
+    DataRate::new(Sf::Sf8, Bw::Bw125, 230),
+    DataRate::new(Sf::Sf7, Bw::Bw125, 230),
+    DataRate::new(Sf::Sf7, Bw::Bw250, 230),
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+];
+/// US915 data rates, indexed by DR. DR0 - DR4 are uplink and DR8 - DR13 downlink data rates,
+/// DR5 and DR6 LR-FHSS.
+pub const US915_DATA_RATES: [Option<DataRate>; 16] = [
+    DataRate::new(Sf::Sf10, Bw::Bw125, 19),
+    DataRate::new(Sf::Sf9, Bw::Bw125, 61),
+    DataRate::new(Sf::Sf8, Bw::Bw125, 133),
+    DataRate::new(Sf::Sf7, Bw::Bw125, 250),
+    DataRate::new(Sf::Sf8, Bw::Bw500, 250),
+    None,
+    None,
+    None,
+    DataRate::new(Sf::Sf12, Bw::Bw500, 61),
+    DataRate::new(Sf::Sf11, Bw::Bw500, 137),
+    DataRate::new(Sf::Sf10, Bw::Bw500, 250),
+    DataRate::new(Sf::Sf9, Bw::Bw500, 250),
+    DataRate::new(Sf::Sf8, Bw::Bw500, 250),
+    DataRate::new(Sf::Sf7, Bw::Bw500, 250),
+    None,
+    None,
+];
+/// AU915 data rates, indexed by DR. DR0 - DR6 are uplink and DR8 - DR13 downlink data rates,
+/// DR7 LR-FHSS.
+pub const AU915_DATA_RATES: [Option<DataRate>; 16] = [
+    DataRate::new(Sf::Sf12, Bw::Bw125, 59),
+    DataRate::new(Sf::Sf11, Bw::Bw125, 59),
+    DataRate::new(Sf::Sf10, Bw::Bw125, 59),
+    DataRate::new(Sf::Sf9, Bw::Bw125, 123),
+    DataRate::new(Sf::Sf8, Bw::Bw125, 230),
+    DataRate::new(Sf::Sf7, Bw::Bw125, 230),
+    DataRate::new(Sf::Sf8, Bw::Bw500, 230),
+    None,
+    DataRate::new(Sf::Sf12, Bw::Bw500, 61),
+    DataRate::new(Sf::Sf11, Bw::Bw500, 137),
+    DataRate::new(Sf::Sf10, Bw::Bw500, 250),
+    DataRate::new(Sf::Sf9, Bw::Bw500, 250),
+    DataRate::new(Sf::Sf8, Bw::Bw500, 250),
+    DataRate::new(Sf::Sf7, Bw::Bw500, 250),
+    None,
+    None,
+];
+/// AS923 data rates, indexed by DR. DR7 is GFSK.
+pub const AS923_DATA_RATES: [Option<DataRate>; 16] = [
+    DataRate::new(Sf::Sf12, Bw::Bw125, 59),
+    DataRate::new(Sf::Sf11, Bw::Bw125, 59),
+    DataRate::new(Sf::Sf10, Bw::Bw125, 59),
+    DataRate::new(Sf::Sf9, Bw::Bw125, 123),
+    DataRate::new(Sf::Sf8, Bw::Bw125, 230),
+    DataRate::new(Sf::Sf7, Bw::Bw125, 230),
+    DataRate::new(Sf::Sf7, Bw::Bw250, 230),
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+];
+
+/// LoRa data rate `dr` of `region`, or `None` if it is not a LoRa data rate or the region has no
+/// table here.
+#[inline]
+#[allow(clippy::indexing_slicing)]
+pub const fn data_rate(region: Region, dr: u8) -> Option<DataRate> {
+    let table = match region {
+        Region::Eu868 => &EU868_DATA_RATES,
+        Region::Us915 => &US915_DATA_RATES,
+        Region::Au915 => &AU915_DATA_RATES,
+        Region::As923 => &AS923_DATA_RATES,
+        _ => return None,
+    };
+    if dr as usize >= table.len() {
+        return None;
+    }
+    table[dr as usize]
+}
+
+/// A LoRaWAN `TXPower` index not defined by the region.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidTxPower {
+    pub tx_power: u8,
+    /// Highest `TXPower` index of the region.
+    pub max: u8,
+}
+
+/// Highest LoRaWAN `TXPower` index of `region`.
+#[inline]
+pub const fn max_tx_power_index(region: Region) -> u8 {
+    match region {
+        Region::Eu868 | Region::As923 | Region::Kr920 | Region::Cn470 => 7,
+        Region::In865 => 10,
+        Region::Us915 | Region::Au915 => 14,
+    }
+}
+
+/// `SetTxParams` for the LoRaWAN `TXPower` index `tx_power`, which is the maximum EIRP of
+/// `region` minus 2 dB per step, clamped to the maximum TX power of `device`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{
+///     commands::{Device, RampTime},
+///     lorawan::{self, InvalidTxPower},
+///     regions::Region,
+/// };
+///
+/// let set_tx_params =
+///     lorawan::tx_params(Device::Sx1262, Region::Eu868, 1, RampTime::Ramp40U, 0).unwrap();
+/// assert_eq!(set_tx_params.tx_buf, [0x8E, 14, 0x02]);
+///
+/// // 30 dBm EIRP, above the 22 dBm of the SX1262 and the 14 dBm of the SX1261
+/// let set_tx_params =
+///     lorawan::tx_params(Device::Sx1262, Region::Us915, 0, RampTime::Ramp40U, 0).unwrap();
+/// assert_eq!(set_tx_params.tx_buf, [0x8E, 22, 0x02]);
+/// let set_tx_params =
+///     lorawan::tx_params(Device::Sx1261, Region::Us915, 0, RampTime::Ramp40U, 0).unwrap();
+/// assert_eq!(set_tx_params.tx_buf, [0x8E, 14, 0x02]);
+///
+/// assert_eq!(
+///     lorawan::tx_params(Device::Sx1262, Region::Eu868, 8, RampTime::Ramp40U, 0).err(),
+///     Some(InvalidTxPower { tx_power: 8, max: 7 })
+/// );
+/// ```
+#[inline]
+pub const fn tx_params(
+    device: Device,
+    region: Region,
+    tx_power: u8,
+    ramp_time: RampTime,
+    antenna_gain: i8,
+) -> Result<SetTxParams, InvalidTxPower> {
+    let max = max_tx_power_index(region);
+    if tx_power > max {
+        return Err(InvalidTxPower { tx_power, max });
+    }
+    // At most 14 steps of 2 dB below at most 30 dBm
+    let power = region.max_tx_power(antenna_gain) - 2 * tx_power as i8;
+    let max_power = device.max_tx_power();
+    let power = if power > max_power { max_power } else { power };
+    Ok(SetTxParams::new(power as u8, ramp_time))
+}
+
+/// `N` channels `step_hz` apart, from `first_hz`.
+#[allow(clippy::indexing_slicing)]
+const fn spaced<const N: usize>(first_hz: u32, step_hz: u32) -> [u32; N] {
+    let mut freqs = [0; N];
+    let mut i: usize = 0;
+    while i < N {
+        freqs[i] = first_hz + i as u32 * step_hz;
+        i += 1;
+    }
+    freqs
+}
+
+/// EU868 default (join) channels.
+pub const EU868_JOIN_CHANNELS: ChannelPlan<3> =
+    ChannelPlan::new([868_100_000, 868_300_000, 868_500_000]);
+/// EU868 RX2 channel.
+pub const EU868_RX2_FREQ: u32 = 869_525_000;
+/// US915 125 kHz upstream channels 0 - 63.
+pub const US915_UPLINK_125: ChannelPlan<64> = ChannelPlan::new(spaced(902_300_000, 200_000));
+/// US915 500 kHz upstream channels 64 - 71.
+pub const US915_UPLINK_500: ChannelPlan<8> = ChannelPlan::new(spaced(903_000_000, 1_600_000));
+/// US915 500 kHz downstream channels 0 - 7.
+pub const US915_DOWNLINK: ChannelPlan<8> = ChannelPlan::new(spaced(923_300_000, 600_000));
+/// AU915 125 kHz upstream channels 0 - 63.
+pub const AU915_UPLINK_125: ChannelPlan<64> = ChannelPlan::new(spaced(915_200_000, 200_000));
+/// AU915 500 kHz upstream channels 64 - 71.
+pub const AU915_UPLINK_500: ChannelPlan<8> = ChannelPlan::new(spaced(915_900_000, 1_600_000));
+/// AU915 500 kHz downstream channels 0 - 7.
+pub const AU915_DOWNLINK: ChannelPlan<8> = ChannelPlan::new(spaced(923_300_000, 600_000));
+/// AS923-1 default (join) channels.
+pub const AS923_JOIN_CHANNELS: ChannelPlan<2> = ChannelPlan::new([923_200_000, 923_400_000]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::SetRfFrequency;
+
+    #[test]
+    fn test_channel_plans() {
+        assert_eq!(
+            US915_UPLINK_125.channels[63].tx_buf,
+            SetRfFrequency::from_hz(914_900_000).tx_buf
+        );
+        assert_eq!(
+            AU915_UPLINK_500.channels[7].tx_buf,
+            SetRfFrequency::from_hz(927_100_000).tx_buf
+        );
+        assert_eq!(data_rate(Region::Au915, 6).unwrap().sf, Sf::Sf8);
+        assert_eq!(data_rate(Region::Kr920, 0), None);
+        assert_eq!(data_rate(Region::Eu868, 16), None);
+    }
+}
@@ -0,0 +1,1153 @@
+//! LR-FHSS transmission.
+//!
+//! The radio transmits an LR-FHSS frame from its physical layer bytes in the data buffer,
+//! hopping through a table of 16 hops held in registers. Longer hop sequences are refilled one
+//! hop at a time on the `LrFhssHop` interrupt.
+//!
+//! The frame - header replicas and coded, interleaved payload fragments - and the pseudo-random
+//! hop sequence are produced by an [`LrFhssEncoder`]: [`LrFhssV1`] builds them from
+//! [`LrFhssParams`], other encoders can e.g. bind Semtech's reference `lr_fhss` library.
+//! [`LrFhssTx::encode`] places them in the command buffers. The packet type must be set to
+//! LR-FHSS before transmitting.
+
+use super::commands::{
+    CapacityExceeded, ClearIrqStatus, Irq, SetRfFrequency, SpiDescriptor, WriteBuffer,
+    WriteRegisters, transfer_length,
+};
+use super::registers::{
+    LrFhssControl, LrFhssNumHops, LrFhssSyncWord, RawRegister, Register, RegisterBlock,
+};
+
+/// Number of hops in the hop table.
+pub const HOP_TABLE_SIZE: usize = 16;
+/// First hop table entry, each entry being a 2 byte symbol count and a 4 byte frequency.
+const HOP_TABLE_ADDRESS: u16 = LrFhssNumHops::ADDRESS + 1;
+
+/// One hop: a frequency held for a number of symbols.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Hop {
+    pub symbols: u16,
+    pub freq_hz: u32,
+}
+impl Hop {
+    /// Hop table entry.
+    #[inline]
+    const fn bytes(&self) -> [u8; 6] {
+        let freq = SetRfFrequency::from_hz(self.freq_hz).tx_buf;
+        [
+            (self.symbols >> 8) as u8,
+            self.symbols as u8,
+            freq[1],
+            freq[2],
+            freq[3],
+            freq[4],
+        ]
+    }
+}
+
+/// # LrFhssEncoder
+/// Encodes LR-FHSS frames and their hop sequences, e.g. [`LrFhssV1`].
+pub trait LrFhssEncoder {
+    type Error;
+    /// Writes the physical layer bytes of the frame carrying `payload` to the start of `frame`
+    /// and its hop sequence to the start of `hops`, returning the frame length and the number
+    /// of hops.
+    fn encode(
+        &mut self,
+        payload: &[u8],
+        frame: &mut [u8],
+        hops: &mut [Hop],
+    ) -> Result<(usize, usize), Self::Error>;
+}
+
+/// A failed [`LrFhssTx::encode`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EncodeError<E> {
+    Encoder(E),
+    /// The encoder returned more bytes or hops than fit.
+    CapacityExceeded(CapacityExceeded),
+}
+
+/// Bits of a header hop: the block preamble, and the coded header split around the sync word.
+pub const HEADER_BITS: u16 = 114;
+/// Coded payload bits of a full fragment hop, after its block preamble.
+pub const FRAGMENT_BITS: usize = 48;
+/// Zero bits starting each hop.
+const BLOCK_PREAMBLE_BITS: usize = 2;
+/// Header fields and CRC8.
+const HEADER_BYTES: usize = 5;
+/// Header bits after rate 1/2 coding.
+const HEADER_CODED_BITS: usize = 2 * 8 * HEADER_BYTES;
+/// Zero bits flushing the convolutional encoder after the payload CRC.
+const TAIL_BITS: usize = 6;
+/// Longest frame the radio transmits.
+const MAX_FRAME_LENGTH: usize = u8::MAX as usize;
+
+/// Outputs of the rate 1/3 convolutional encoder with constraint length 4, indexed by its state
+/// of the last 4 input bits.
+const CONVOLUTION: [u8; 16] = [0, 7, 3, 4, 5, 2, 6, 1, 5, 2, 6, 1, 0, 7, 3, 4];
+
+/// LR-FHSS coding rate of the payload. The header is always coded at rate 1/2.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LrFhssCr {
+    Cr5_6 = 0x00,
+    Cr2_3 = 0x01,
+    Cr1_2 = 0x02,
+    Cr1_3 = 0x03,
+}
+impl LrFhssCr {
+    /// Which outputs of the rate 1/3 encoder are transmitted, repeating.
+    #[inline]
+    const fn puncturing(self) -> &'static [bool] {
+        match self {
+            LrFhssCr::Cr5_6 => &[
+                true, true, true, true, false, false, false, false, false, true, false, false,
+                false, false, true,
+            ],
+            LrFhssCr::Cr2_3 => &[true, true, false, true, false, false],
+            LrFhssCr::Cr1_2 => &[true, true, false],
+            LrFhssCr::Cr1_3 => &[true],
+        }
+    }
+}
+
+/// Spacing of the LR-FHSS hop frequencies.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LrFhssGrid {
+    Grid25391 = 0x00,
+    Grid3906 = 0x01,
+}
+impl LrFhssGrid {
+    /// Spacing in mHz: 25.390625 kHz or 3.90625 kHz.
+    #[inline]
+    pub const fn spacing_mhz(self) -> u32 {
+        match self {
+            LrFhssGrid::Grid25391 => 25_390_625,
+            LrFhssGrid::Grid3906 => 3_906_250,
+        }
+    }
+}
+
+/// Bandwidth occupied by the LR-FHSS hops.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LrFhssBw {
+    Bw39063 = 0x00,
+    Bw85938 = 0x01,
+    Bw136719 = 0x02,
+    Bw183594 = 0x03,
+    Bw335938 = 0x04,
+    Bw386719 = 0x05,
+    Bw722656 = 0x06,
+    Bw773438 = 0x07,
+    Bw1523438 = 0x08,
+    Bw1574219 = 0x09,
+}
+impl LrFhssBw {
+    /// Bandwidth in Hz.
+    #[inline]
+    pub const fn hz(self) -> u32 {
+        match self {
+            LrFhssBw::Bw39063 => 39_063,
+            LrFhssBw::Bw85938 => 85_938,
+            LrFhssBw::Bw136719 => 136_719,
+            LrFhssBw::Bw183594 => 183_594,
+            LrFhssBw::Bw335938 => 335_938,
+            LrFhssBw::Bw386719 => 386_719,
+            LrFhssBw::Bw722656 => 722_656,
+            LrFhssBw::Bw773438 => 773_438,
+            LrFhssBw::Bw1523438 => 1_523_438,
+            LrFhssBw::Bw1574219 => 1_574_219,
+        }
+    }
+}
+
+/// Initial state, polynomial and seed of the LFSR of hop sequence `hop_sequence_id` over
+/// `channels` channels, and the number of hop sequences. `None` if there are too few channels or
+/// `hop_sequence_id` is not below the number of hop sequences.
+#[allow(clippy::indexing_slicing)]
+const fn hop_lfsr(channels: u16, hop_sequence_id: u16) -> Option<(u16, u16, u16, u16)> {
+    const POLY_6: [u16; 6] = [33, 45, 48, 51, 54, 57];
+    const POLY_7: [u16; 4] = [65, 68, 71, 72];
+    const POLY_8: [u16; 2] = [142, 149];
+    let (initial_state, polys, shift, count): (u16, &[u16], u32, u16) = match channels {
+        10 | 22 | 28 | 30 | 35 | 47 => (6, &POLY_6, 6, 384),
+        60 | 62 => (56, &POLY_6, 6, 384),
+        86 | 99 => (6, &POLY_7, 7, 512),
+        185 | 198 => (6, &POLY_8, 8, 512),
+        390 | 403 => (6, &[264], 9, 512),
+        _ => return None,
+    };
+    let index = (hop_sequence_id >> shift) as usize;
+    if index >= polys.len() {
+        return None;
+    }
+    Some((
+        initial_state,
+        polys[index],
+        hop_sequence_id & ((1 << shift) - 1),
+        count,
+    ))
+}
+
+/// # LrFhssParams
+/// Parameters of an LR-FHSS v1 frame and its hops.
+///
+/// `new()` uses the LoRaWAN sync word, hopping, and 3 header replicas at coding rate 1/3 or 2
+/// otherwise, as in the LoRaWAN regional parameters.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::lr_fhss::{LrFhssBw, LrFhssCr, LrFhssGrid, LrFhssParams};
+///
+/// // EU868 DR8
+/// let params = LrFhssParams::new(868_100_000, LrFhssCr::Cr1_3, LrFhssGrid::Grid3906, LrFhssBw::Bw136719);
+/// assert_eq!(params.channel_count(), 35);
+/// assert_eq!(params.hop_sequence_count(), 384);
+/// // 3 headers and (10 + 2 CRC bytes + 6 tail bits) * 3 = 306 coded bits in 7 fragments
+/// assert_eq!(params.hop_count(10), 10);
+/// assert_eq!(params.frame_length(10), 83);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LrFhssParams {
+    pub center_freq_hz: u32,
+    pub sync_word: [u8; 4],
+    pub cr: LrFhssCr,
+    pub grid: LrFhssGrid,
+    pub bw: LrFhssBw,
+    pub hopping: bool,
+    /// Header replicas, 1 to 4.
+    pub header_count: u8,
+}
+impl LrFhssParams {
+    #[inline]
+    pub const fn new(center_freq_hz: u32, cr: LrFhssCr, grid: LrFhssGrid, bw: LrFhssBw) -> Self {
+        Self {
+            center_freq_hz,
+            sync_word: LrFhssSyncWord::LORAWAN.0,
+            cr,
+            grid,
+            bw,
+            hopping: true,
+            header_count: match cr {
+                LrFhssCr::Cr1_3 => 3,
+                _ => 2,
+            },
+        }
+    }
+    #[inline]
+    pub const fn with_sync_word(mut self, sync_word: [u8; 4]) -> Self {
+        self.sync_word = sync_word;
+        self
+    }
+    #[inline]
+    pub const fn with_hopping(mut self, hopping: bool) -> Self {
+        self.hopping = hopping;
+        self
+    }
+    #[inline]
+    pub const fn with_header_count(mut self, header_count: u8) -> Self {
+        self.header_count = header_count;
+        self
+    }
+    /// Number of hop frequencies in the bandwidth.
+    #[inline]
+    pub const fn channel_count(&self) -> u16 {
+        (self.bw.hz() as u64 * 1000 / self.grid.spacing_mhz() as u64) as u16
+    }
+    /// Number of hop sequences, 0 if the bandwidth has too few channels on the grid.
+    #[inline]
+    pub const fn hop_sequence_count(&self) -> u16 {
+        match hop_lfsr(self.channel_count(), 0) {
+            Some((_, _, _, count)) => count,
+            None => 0,
+        }
+    }
+    /// Coded payload bits for a payload of `payload_length` bytes.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn coded_bits(&self, payload_length: usize) -> usize {
+        let puncturing = self.cr.puncturing();
+        let outputs = 3 * ((payload_length + 2) * 8 + TAIL_BITS);
+        let mut kept = 0;
+        let mut i = 0;
+        while i < puncturing.len() {
+            if puncturing[i] {
+                kept += 1;
+            }
+            i += 1;
+        }
+        let mut coded = outputs / puncturing.len() * kept;
+        let mut i = 0;
+        while i < outputs % puncturing.len() {
+            if puncturing[i] {
+                coded += 1;
+            }
+            i += 1;
+        }
+        coded
+    }
+    /// Number of hops for a payload of `payload_length` bytes: the headers and the fragments.
+    #[inline]
+    pub const fn hop_count(&self, payload_length: usize) -> usize {
+        self.header_count as usize + self.coded_bits(payload_length).div_ceil(FRAGMENT_BITS)
+    }
+    /// Frame length in bytes for a payload of `payload_length` bytes.
+    #[inline]
+    pub const fn frame_length(&self, payload_length: usize) -> usize {
+        let coded_bits = self.coded_bits(payload_length);
+        let bits = self.header_count as usize * HEADER_BITS as usize
+            + coded_bits.div_ceil(FRAGMENT_BITS) * BLOCK_PREAMBLE_BITS
+            + coded_bits;
+        bits.div_ceil(8)
+    }
+    /// Header fields and CRC8 of the replica with `remaining` replicas after it.
+    const fn header(&self, payload_length: u8, hop_sequence_id: u16, remaining: u8) -> [u8; 5] {
+        let bw = self.bw as u8;
+        let mut header = [
+            payload_length,
+            ((self.cr as u8) << 3)
+                | ((self.grid as u8) << 2)
+                | ((self.hopping as u8) << 1)
+                | (bw >> 3),
+            ((bw & 0x07) << 5) | ((hop_sequence_id >> 4) as u8 & 0x1F),
+            ((hop_sequence_id as u8 & 0x0F) << 4) | ((remaining & 0x03) << 2),
+            0,
+        ];
+        header[4] = crc8(&[header[0], header[1], header[2], header[3]]);
+        header
+    }
+}
+
+/// A failed [`LrFhssV1`] encoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LrFhssError {
+    /// The bandwidth has too few channels on the grid for hopping.
+    UnsupportedBandwidth,
+    /// The hop sequence ID is not below `LrFhssParams::hop_sequence_count()`.
+    InvalidHopSequenceId(u16),
+    /// The header count is not 1 to 4.
+    InvalidHeaderCount(u8),
+    /// The frame or hops do not fit the buffers, or the frame exceeds 255 bytes.
+    CapacityExceeded(CapacityExceeded),
+}
+
+/// # LrFhssV1
+/// LR-FHSS v1 encoder: builds the frame and hop sequence of a payload with the hop sequence
+/// `hop_sequence_id`, e.g. picked at random below `hop_sequence_count()` for each uplink.
+///
+/// Each of the `header_count` header hops carries 2 zero bits, then the header fields and CRC8,
+/// rate 1/2 coded and interleaved, with the sync word in the middle. The payload is whitened,
+/// followed by its CRC16 and 6 tail bits, coded at `cr` and interleaved over fragment hops of 2
+/// zero bits and up to 48 coded bits. The hop frequencies follow a maximal length LFSR.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::lr_fhss::{
+///     Hop, LrFhssBw, LrFhssCr, LrFhssGrid, LrFhssParams, LrFhssTx, LrFhssV1,
+/// };
+///
+/// let params = LrFhssParams::new(868_100_000, LrFhssCr::Cr1_3, LrFhssGrid::Grid3906, LrFhssBw::Bw136719);
+/// let mut encoder = LrFhssV1::new(params, 42);
+/// let mut hops = [Hop { symbols: 0, freq_hz: 0 }; 32];
+/// let mut tx: LrFhssTx<85> = LrFhssTx::new(0x00);
+/// tx.encode(&mut encoder, b"0123456789", &mut hops).unwrap();
+/// assert_eq!(tx.write_hop_config.tx_buf[3..6], [0x0C, 83, 10]);
+/// // Header hops of 114 bits, the last fragment with the remaining 18 coded bits
+/// assert_eq!(tx.write_hop_config.tx_buf[6..8], [0, 114]);
+/// assert_eq!(tx.write_hop_config.tx_buf[60..62], [0, 20]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LrFhssV1 {
+    pub params: LrFhssParams,
+    pub hop_sequence_id: u16,
+}
+impl LrFhssV1 {
+    #[inline]
+    pub const fn new(params: LrFhssParams, hop_sequence_id: u16) -> Self {
+        Self {
+            params,
+            hop_sequence_id,
+        }
+    }
+    /// Writes the header replicas, each with its block preamble and the sync word.
+    fn write_headers(&self, payload_length: u8, frame: &mut [u8]) {
+        let params = &self.params;
+        for replica in 0..params.header_count {
+            let header = params.header(
+                payload_length,
+                self.hop_sequence_id,
+                params.header_count - 1 - replica,
+            );
+            let mut coded = [0; HEADER_CODED_BITS / 8];
+            let mut encoder = ConvolutionalEncoder::new(LrFhssCr::Cr1_2);
+            let mut k = 0;
+            for i in 0..8 * HEADER_BYTES {
+                encoder.push(bit(&header, i), |b| {
+                    set_bit(&mut coded, k, b);
+                    k += 1;
+                });
+            }
+            let start = replica as usize * HEADER_BITS as usize + BLOCK_PREAMBLE_BITS;
+            let half = HEADER_CODED_BITS / 2;
+            for (k, i) in Interleaver::new(HEADER_CODED_BITS).enumerate() {
+                let position = if k < half { start + k } else { start + k + 32 };
+                set_bit(frame, position, bit(&coded, i));
+            }
+            for i in 0..32 {
+                set_bit(frame, start + half + i, bit(&params.sync_word, i));
+            }
+        }
+    }
+    /// Writes the coded payload fragments after the headers.
+    fn write_payload(&self, payload: &[u8], frame: &mut [u8]) {
+        let params = &self.params;
+        let mut whitening = Whitening::new();
+        let mut crc = Crc16::new();
+        for byte in payload {
+            crc.update(whitening.next(*byte));
+        }
+        let crc = crc.value().to_be_bytes();
+
+        // At most 8 * 255 coded bits fit in the frame.
+        let mut coded = [0; MAX_FRAME_LENGTH];
+        let mut encoder = ConvolutionalEncoder::new(params.cr);
+        let mut k = 0;
+        let mut push = |byte: u8, bits: usize| {
+            for i in 0..bits {
+                encoder.push((byte << i) & 0x80 != 0, |b| {
+                    set_bit(&mut coded, k, b);
+                    k += 1;
+                });
+            }
+        };
+        let mut whitening = Whitening::new();
+        for byte in payload {
+            push(whitening.next(*byte), 8);
+        }
+        push(crc[0], 8);
+        push(crc[1], 8);
+        push(0, TAIL_BITS);
+
+        let start = params.header_count as usize * HEADER_BITS as usize;
+        let coded_bits = params.coded_bits(payload.len());
+        for (k, i) in Interleaver::new(coded_bits).enumerate() {
+            let fragment = k / FRAGMENT_BITS;
+            let position = start + (fragment + 1) * BLOCK_PREAMBLE_BITS + k;
+            set_bit(frame, position, bit(&coded, i));
+        }
+    }
+    /// Writes the header hops and the fragment hops.
+    fn write_hops(&self, coded_bits: usize, hops: &mut [Hop]) {
+        let params = &self.params;
+        let channels = params.channel_count();
+        let Some((mut state, poly, seed, _)) = hop_lfsr(channels, self.hop_sequence_id) else {
+            return;
+        };
+        let fragments = coded_bits.div_ceil(FRAGMENT_BITS);
+        for (i, hop) in hops.iter_mut().enumerate() {
+            let symbols = if i < params.header_count as usize {
+                HEADER_BITS
+            } else if i + 1 < params.header_count as usize + fragments {
+                (BLOCK_PREAMBLE_BITS + FRAGMENT_BITS) as u16
+            } else {
+                (BLOCK_PREAMBLE_BITS + coded_bits - (fragments - 1) * FRAGMENT_BITS) as u16
+            };
+            let freq_hz = if params.hopping {
+                let channel = next_channel(&mut state, poly, seed, channels) as i64;
+                let offset_mhz = (channel - channels as i64 / 2) * params.grid.spacing_mhz() as i64;
+                (params.center_freq_hz as i64 + (offset_mhz + 500).div_euclid(1000)) as u32
+            } else {
+                params.center_freq_hz
+            };
+            *hop = Hop { symbols, freq_hz };
+        }
+    }
+}
+impl LrFhssEncoder for LrFhssV1 {
+    type Error = LrFhssError;
+    fn encode(
+        &mut self,
+        payload: &[u8],
+        frame: &mut [u8],
+        hops: &mut [Hop],
+    ) -> Result<(usize, usize), LrFhssError> {
+        let params = &self.params;
+        if params.header_count == 0 || params.header_count > 4 {
+            return Err(LrFhssError::InvalidHeaderCount(params.header_count));
+        }
+        let hop_sequence_count = params.hop_sequence_count();
+        if hop_sequence_count == 0 {
+            return Err(LrFhssError::UnsupportedBandwidth);
+        }
+        if self.hop_sequence_id >= hop_sequence_count {
+            return Err(LrFhssError::InvalidHopSequenceId(self.hop_sequence_id));
+        }
+        let frame_length = params.frame_length(payload.len());
+        let capacity = frame.len().min(MAX_FRAME_LENGTH);
+        // Payloads longer than 255 bytes exceed the 255 byte frame.
+        let (Some(frame), Ok(payload_length)) = (
+            frame
+                .get_mut(..frame_length)
+                .filter(|_| frame_length <= capacity),
+            u8::try_from(payload.len()),
+        ) else {
+            return Err(LrFhssError::CapacityExceeded(CapacityExceeded {
+                len: frame_length,
+                capacity,
+            }));
+        };
+        let hop_count = params.hop_count(payload.len());
+        let capacity = hops.len();
+        let Some(hops) = hops.get_mut(..hop_count) else {
+            return Err(LrFhssError::CapacityExceeded(CapacityExceeded {
+                len: hop_count,
+                capacity,
+            }));
+        };
+        frame.fill(0);
+        self.write_headers(payload_length, frame);
+        self.write_payload(payload, frame);
+        self.write_hops(params.coded_bits(payload.len()), hops);
+        Ok((frame_length, hop_count))
+    }
+}
+
+/// Next channel of the hop sequence, stepping the LFSR past the values outside of the
+/// `channels`.
+fn next_channel(state: &mut u16, poly: u16, seed: u16, channels: u16) -> u16 {
+    // The LFSRs have maximal length, so every value from 1 to `channels` comes up.
+    loop {
+        let lsb = *state & 1;
+        *state >>= 1;
+        if lsb != 0 {
+            *state ^= poly;
+        }
+        let hop = if seed != *state { seed ^ *state } else { seed };
+        if hop != 0 && hop <= channels {
+            return hop - 1;
+        }
+    }
+}
+
+/// Rate 1/3 convolutional encoder, punctured to the coding rate.
+struct ConvolutionalEncoder {
+    state: u8,
+    puncturing: &'static [bool],
+    output: usize,
+}
+impl ConvolutionalEncoder {
+    #[inline]
+    const fn new(cr: LrFhssCr) -> Self {
+        Self {
+            state: 0,
+            puncturing: cr.puncturing(),
+            output: 0,
+        }
+    }
+    /// Encodes `input`, passing the transmitted output bits to `emit`.
+    fn push(&mut self, input: bool, mut emit: impl FnMut(bool)) {
+        self.state = ((self.state << 1) | input as u8) & 0x0F;
+        let outputs = CONVOLUTION
+            .get(self.state as usize)
+            .copied()
+            .unwrap_or_default();
+        for i in (0..3).rev() {
+            if self
+                .puncturing
+                .get(self.output)
+                .copied()
+                .unwrap_or_default()
+            {
+                emit((outputs >> i) & 1 != 0);
+            }
+            self.output = (self.output + 1) % self.puncturing.len();
+        }
+    }
+}
+
+/// Order in which the coded bits are transmitted: column by column through a table of rows of
+/// `step` bits, `step` being the smallest with `step * step >= len`.
+struct Interleaver {
+    len: usize,
+    step: usize,
+    column: usize,
+    position: usize,
+}
+impl Interleaver {
+    #[inline]
+    fn new(len: usize) -> Self {
+        let mut step = 1;
+        while step * step < len {
+            step += 1;
+        }
+        Self {
+            len,
+            step,
+            column: 0,
+            position: 0,
+        }
+    }
+}
+impl Iterator for Interleaver {
+    type Item = usize;
+    fn next(&mut self) -> Option<usize> {
+        while self.position >= self.len {
+            self.column += 1;
+            if self.column >= self.step {
+                return None;
+            }
+            self.position = self.column;
+        }
+        let position = self.position;
+        self.position += self.step;
+        Some(position)
+    }
+}
+
+/// Payload whitening: XOR with an 8 bit LFSR sequence, then swapping the nibbles.
+struct Whitening {
+    lfsr: u8,
+}
+impl Whitening {
+    #[inline]
+    const fn new() -> Self {
+        Self { lfsr: 0xFF }
+    }
+    #[inline]
+    const fn next(&mut self, byte: u8) -> u8 {
+        let whitened = (byte ^ self.lfsr).rotate_left(4);
+        let feedback =
+            ((self.lfsr >> 7) ^ (self.lfsr >> 5) ^ (self.lfsr >> 4) ^ (self.lfsr >> 3)) & 1;
+        self.lfsr = (self.lfsr << 1) | feedback;
+        whitened
+    }
+}
+
+/// CRC-16/CCITT-FALSE of the whitened payload: polynomial 0x1021, initial value 0xFFFF.
+struct Crc16 {
+    crc: u16,
+}
+impl Crc16 {
+    #[inline]
+    const fn new() -> Self {
+        Self { crc: 0xFFFF }
+    }
+    #[inline]
+    const fn update(&mut self, byte: u8) {
+        self.crc ^= (byte as u16) << 8;
+        let mut i = 0;
+        while i < 8 {
+            self.crc = if self.crc & 0x8000 != 0 {
+                (self.crc << 1) ^ 0x1021
+            } else {
+                self.crc << 1
+            };
+            i += 1;
+        }
+    }
+    #[inline]
+    const fn value(&self) -> u16 {
+        self.crc
+    }
+}
+
+/// CRC8 of the header fields: polynomial 0x2F, initial value 0xFF.
+#[allow(clippy::indexing_slicing)]
+const fn crc8(data: &[u8]) -> u8 {
+    let mut crc: u8 = 0xFF;
+    let mut i = 0;
+    while i < data.len() {
+        crc ^= data[i];
+        let mut j = 0;
+        while j < 8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x2F
+            } else {
+                crc << 1
+            };
+            j += 1;
+        }
+        i += 1;
+    }
+    crc
+}
+
+/// Bit `i` of `bytes`, most significant bit first.
+#[inline]
+fn bit(bytes: &[u8], i: usize) -> bool {
+    bytes
+        .get(i / 8)
+        .is_some_and(|byte| (byte << (i % 8)) & 0x80 != 0)
+}
+
+/// Sets bit `i` of `bytes`, most significant bit first.
+#[inline]
+fn set_bit(bytes: &mut [u8], i: usize, value: bool) {
+    if let Some(byte) = bytes.get_mut(i / 8) {
+        let mask = 0x80 >> (i % 8);
+        if value {
+            *byte |= mask;
+        } else {
+            *byte &= !mask;
+        }
+    }
+}
+
+/// # LrFhssTx
+/// Writes an encoded LR-FHSS frame and its hop sequence to the radio, and refills the hop table
+/// while transmitting.
+///
+/// 1. Call `encode()` with the payload, or `prepare()` with an encoded frame and its hops, then
+///    transfer `descriptors()` and `SetTx`.
+/// 2. On each `LrFhssHop` interrupt, call `on_hop()` and, if it returns `true`, transfer
+///    `hop_descriptors()`.
+///
+/// #### Type Parameter `N`
+/// `N` = maximum frame length + 2
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::lr_fhss::{Hop, LrFhssTx};
+///
+/// let hops = [Hop { symbols: 40, freq_hz: 868_100_000 }; 18];
+/// let mut tx: LrFhssTx<34> = LrFhssTx::new(0x00);
+/// tx.prepare(&[0xA5; 32], &hops);
+/// assert_eq!(tx.write_hop_config.tx_buf[..6], [0x0D, 0x03, 0x85, 0x0C, 32, 18]);
+/// assert_eq!(tx.write_hop_config.tx_buf[6..12], [0, 40, 0x36, 0x41, 0x99, 0x99]);
+/// assert_eq!(tx.descriptors()[1].transfer_length, 34);
+///
+/// // The 17th and 18th hops replace the first two entries.
+/// assert!(tx.on_hop());
+/// assert_eq!(tx.write_hop.tx_buf[..3], [0x0D, 0x03, 0x88]);
+/// assert!(tx.on_hop());
+/// assert_eq!(tx.write_hop.tx_buf[..3], [0x0D, 0x03, 0x8E]);
+/// assert!(!tx.on_hop());
+/// ```
+///
+/// With an encoder:
+/// ```
+/// use sx126x_spi_buffers::lr_fhss::{Hop, LrFhssEncoder, LrFhssTx};
+///
+/// /// Stands in for a binding of an LR-FHSS encoder library.
+/// struct Encoder;
+/// impl LrFhssEncoder for Encoder {
+///     type Error = ();
+///     fn encode(
+///         &mut self,
+///         payload: &[u8],
+///         frame: &mut [u8],
+///         hops: &mut [Hop],
+///     ) -> Result<(usize, usize), ()> {
+///         frame[..payload.len()].copy_from_slice(payload);
+///         hops[..2].fill(Hop { symbols: 40, freq_hz: 868_100_000 });
+///         Ok((payload.len(), 2))
+///     }
+/// }
+///
+/// let mut hops = [Hop { symbols: 0, freq_hz: 0 }; 32];
+/// let mut tx: LrFhssTx<34> = LrFhssTx::new(0x00);
+/// tx.encode(&mut Encoder, b"hello", &mut hops).unwrap();
+/// assert_eq!(tx.write_hop_config.tx_buf[..6], [0x0D, 0x03, 0x85, 0x0C, 5, 2]);
+/// assert_eq!(tx.descriptors()[1].transfer_length, 7);
+/// ```
+pub struct LrFhssTx<'a, const N: usize> {
+    pub write_sync_word: WriteRegisters<7>,
+    pub write_buffer: WriteBuffer<N>,
+    /// Control, packet length, number of hops and the hop table.
+    pub write_hop_config: WriteRegisters<102>,
+    pub write_hop: WriteRegisters<9>,
+    pub clear_irq_status: ClearIrqStatus,
+    hops: &'a [Hop],
+    frame_length: u8,
+    completed_hops: usize,
+}
+impl<'a, const N: usize> LrFhssTx<'a, N> {
+    /// `offset` is the TX base address. The sync word defaults to the LoRaWAN one.
+    #[inline]
+    pub const fn new(offset: u8) -> Self {
+        Self {
+            write_sync_word: WriteRegisters::<7>::at_address(
+                LrFhssSyncWord::ADDRESS,
+                &LrFhssSyncWord::LORAWAN.0,
+            ),
+            write_buffer: WriteBuffer::with_offset(offset),
+            write_hop_config: WriteRegisters::<102>::from_slice::<LrFhssControl>(&[0; 99]),
+            write_hop: WriteRegisters::<9>::from_slice::<RawRegister<HOP_TABLE_ADDRESS>>(&[0; 6]),
+            clear_irq_status: ClearIrqStatus::new(Irq::new().with_lr_fhss_hop(true)),
+            hops: &[],
+            frame_length: 0,
+            completed_hops: 0,
+        }
+    }
+    /// Copies `frame` into the write buffer and the first hops into the hop table.
+    ///
+    /// ## Panics
+    /// If `frame` is longer than `N - 2` bytes or there are more than 255 hops.
+    pub const fn prepare(&mut self, frame: &[u8], hops: &'a [Hop]) {
+        assert!(frame.len() + 2 <= N, "frame exceeds buffer capacity");
+        assert!(hops.len() <= u8::MAX as usize, "too many hops");
+        self.prepare_unchecked(frame, hops);
+    }
+    /// Like `prepare()`, rejecting a `frame` longer than `N - 2` bytes or more than 255 `hops`.
+    pub const fn try_prepare(
+        &mut self,
+        frame: &[u8],
+        hops: &'a [Hop],
+    ) -> Result<(), CapacityExceeded> {
+        if frame.len() + 2 > N {
+            return Err(CapacityExceeded {
+                len: frame.len(),
+                capacity: N.saturating_sub(2),
+            });
+        }
+        if hops.len() > u8::MAX as usize {
+            return Err(CapacityExceeded {
+                len: hops.len(),
+                capacity: u8::MAX as usize,
+            });
+        }
+        self.prepare_unchecked(frame, hops);
+        Ok(())
+    }
+    /// Encodes `payload` with `encoder` into the write buffer and `hops`, then loads the first
+    /// hops into the hop table like `prepare()`.
+    pub fn encode<E: LrFhssEncoder>(
+        &mut self,
+        encoder: &mut E,
+        payload: &[u8],
+        hops: &'a mut [Hop],
+    ) -> Result<(), EncodeError<E::Error>> {
+        let capacity = N.saturating_sub(2);
+        let frame = self.write_buffer.tx_buf.get_mut(2..).unwrap_or_default();
+        let (frame_length, hop_count) = encoder
+            .encode(payload, frame, hops)
+            .map_err(EncodeError::Encoder)?;
+        if frame_length > capacity {
+            return Err(EncodeError::CapacityExceeded(CapacityExceeded {
+                len: frame_length,
+                capacity,
+            }));
+        }
+        let hops: &'a [Hop] = hops;
+        let hops = match hops.get(..hop_count) {
+            Some(hops) if hop_count <= u8::MAX as usize => hops,
+            _ => {
+                return Err(EncodeError::CapacityExceeded(CapacityExceeded {
+                    len: hop_count,
+                    capacity: hops.len().min(u8::MAX as usize),
+                }));
+            }
+        };
+        // At most `N - 2` bytes and 255 hops
+        self.load(frame_length as u8, hops);
+        Ok(())
+    }
+    /// `frame` must fit and there must be at most 255 `hops`.
+    #[allow(clippy::indexing_slicing)]
+    const fn prepare_unchecked(&mut self, frame: &[u8], hops: &'a [Hop]) {
+        let mut i: usize = 0;
+        while i < frame.len() {
+            self.write_buffer.tx_buf[i + 2] = frame[i];
+            i += 1;
+        }
+        self.load(frame.len() as u8, hops);
+    }
+    /// Sets up the hop configuration for a frame of `frame_length` bytes in the write buffer.
+    #[allow(clippy::indexing_slicing)]
+    const fn load(&mut self, frame_length: u8, hops: &'a [Hop]) {
+        self.frame_length = frame_length;
+        self.hops = hops;
+        self.completed_hops = 0;
+
+        let tx_buf = &mut self.write_hop_config.tx_buf;
+        tx_buf[3] = LrFhssControl::HOPPING_ENABLED.0;
+        tx_buf[4] = self.frame_length;
+        tx_buf[5] = hops.len() as u8;
+        let mut i: usize = 0;
+        while i < HOP_TABLE_SIZE {
+            let entry = if i < hops.len() {
+                hops[i].bytes()
+            } else {
+                [0; 6]
+            };
+            let mut j: usize = 0;
+            while j < 6 {
+                tx_buf[6 + 6 * i + j] = entry[j];
+                j += 1;
+            }
+            i += 1;
+        }
+    }
+    /// Descriptors for the sync word `WriteRegisters`, `WriteBuffer` and the hop configuration
+    /// `WriteRegisters`. Only the prepared frame is written.
+    #[inline]
+    pub const fn descriptors(&self) -> [SpiDescriptor; 3] {
+        [
+            self.write_sync_word.descriptor(),
+            SpiDescriptor {
+                transfer_length: transfer_length(self.frame_length as usize + 2),
+                ..self.write_buffer.descriptor()
+            },
+            self.write_hop_config.descriptor(),
+        ]
+    }
+    /// Records a completed hop and moves the next hop not yet in the table into the entry just
+    /// used. Returns `false` if all hops are already in the table.
+    #[allow(clippy::indexing_slicing)]
+    pub const fn on_hop(&mut self) -> bool {
+        let next = self.completed_hops + HOP_TABLE_SIZE;
+        let slot = self.completed_hops % HOP_TABLE_SIZE;
+        self.completed_hops += 1;
+        if next >= self.hops.len() {
+            return false;
+        }
+        let address = HOP_TABLE_ADDRESS + 6 * slot as u16;
+        let entry = self.hops[next].bytes();
+        self.write_hop.tx_buf[1] = (address >> 8) as u8;
+        self.write_hop.tx_buf[2] = address as u8;
+        let mut j: usize = 0;
+        while j < 6 {
+            self.write_hop.tx_buf[3 + j] = entry[j];
+            j += 1;
+        }
+        true
+    }
+    /// Descriptors for `ClearIrqStatus` and the hop `WriteRegisters`.
+    #[inline]
+    pub const fn hop_descriptors(&self) -> [SpiDescriptor; 2] {
+        [
+            self.clear_irq_status.descriptor(),
+            self.write_hop.descriptor(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(encoder: &mut LrFhssV1, payload: &[u8]) -> ([u8; 255], usize, [Hop; 32], usize) {
+        let mut frame = [0; 255];
+        let mut hops = [Hop {
+            symbols: 0,
+            freq_hz: 0,
+        }; 32];
+        let (frame_length, hop_count) = encoder.encode(payload, &mut frame, &mut hops).unwrap();
+        (frame, frame_length, hops, hop_count)
+    }
+
+    #[test]
+    fn test_crc() {
+        assert_eq!(crc8(b"123456789"), 0x20);
+        let mut crc = Crc16::new();
+        for byte in b"123456789" {
+            crc.update(*byte);
+        }
+        assert_eq!(crc.value(), 0x29B1);
+    }
+
+    #[test]
+    fn test_whitening() {
+        let mut whitening = Whitening::new();
+        let whitened = [0; 4].map(|byte| whitening.next(byte));
+        assert_eq!(whitened, [0xFF, 0xEF, 0xCF, 0x8F]);
+    }
+
+    #[test]
+    fn test_convolutional_encoder() {
+        let impulse = |cr| {
+            let mut encoder = ConvolutionalEncoder::new(cr);
+            let mut output = [false; 12];
+            let mut k = 0;
+            for input in [true, false, false, false] {
+                encoder.push(input, |b| {
+                    output[k] = b;
+                    k += 1;
+                });
+            }
+            output.map(u8::from)[..k].to_vec()
+        };
+        assert_eq!(
+            impulse(LrFhssCr::Cr1_3),
+            [1, 1, 1, 0, 1, 1, 1, 0, 1, 1, 0, 1]
+        );
+        assert_eq!(impulse(LrFhssCr::Cr1_2), [1, 1, 0, 1, 1, 0, 1, 0]);
+        assert_eq!(impulse(LrFhssCr::Cr2_3), [1, 1, 0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn test_interleaver() {
+        let order: [usize; 10] = core::array::from_fn({
+            let mut interleaver = Interleaver::new(10);
+            move |_| interleaver.next().unwrap()
+        });
+        assert_eq!(order, [0, 4, 8, 1, 5, 9, 2, 6, 3, 7]);
+        let mut seen = [false; HEADER_CODED_BITS];
+        for i in Interleaver::new(HEADER_CODED_BITS) {
+            assert!(!seen[i]);
+            seen[i] = true;
+        }
+        assert!(seen.iter().all(|seen| *seen));
+    }
+
+    #[test]
+    fn test_coded_bits() {
+        let params =
+            |cr| LrFhssParams::new(868_100_000, cr, LrFhssGrid::Grid3906, LrFhssBw::Bw136719);
+        // 10 byte payload, 2 CRC bytes and 6 tail bits
+        assert_eq!(params(LrFhssCr::Cr1_3).coded_bits(10), 306);
+        assert_eq!(params(LrFhssCr::Cr1_2).coded_bits(10), 204);
+        assert_eq!(params(LrFhssCr::Cr2_3).coded_bits(10), 153);
+        assert_eq!(params(LrFhssCr::Cr5_6).coded_bits(10), 124);
+    }
+
+    #[test]
+    fn test_hop_sequences() {
+        for bw in [
+            LrFhssBw::Bw39063,
+            LrFhssBw::Bw136719,
+            LrFhssBw::Bw335938,
+            LrFhssBw::Bw722656,
+            LrFhssBw::Bw1574219,
+        ] {
+            let params = LrFhssParams::new(868_100_000, LrFhssCr::Cr1_3, LrFhssGrid::Grid3906, bw);
+            let channels = params.channel_count();
+            for id in 0..params.hop_sequence_count() {
+                let (mut state, poly, seed, _) = hop_lfsr(channels, id).unwrap();
+                for _ in 0..32 {
+                    assert!(next_channel(&mut state, poly, seed, channels) < channels);
+                }
+            }
+            assert_eq!(hop_lfsr(channels, params.hop_sequence_count()), None);
+        }
+        let params = LrFhssParams::new(
+            902_300_000,
+            LrFhssCr::Cr1_3,
+            LrFhssGrid::Grid25391,
+            LrFhssBw::Bw136719,
+        );
+        assert_eq!(params.channel_count(), 5);
+        assert_eq!(params.hop_sequence_count(), 0);
+    }
+
+    #[test]
+    fn test_encode_eu868_dr8() {
+        let params = LrFhssParams::new(
+            868_100_000,
+            LrFhssCr::Cr1_3,
+            LrFhssGrid::Grid3906,
+            LrFhssBw::Bw136719,
+        );
+        let (frame, frame_length, hops, hop_count) =
+            encode(&mut LrFhssV1::new(params, 42), b"0123456789");
+        assert_eq!(frame_length, 83);
+        assert_eq!(
+            frame[..frame_length],
+            [
+                0x19, 0xA3, 0x74, 0xD0, 0x15, 0xCB, 0x03, 0xDE, 0x65, 0x7E, 0xCE, 0x66, 0x20, 0xE7,
+                0x86, 0x60, 0xD9, 0x3A, 0x02, 0x72, 0xC0, 0xF7, 0x99, 0x5D, 0xB3, 0xD9, 0x08, 0x79,
+                0xF1, 0x98, 0x36, 0x4F, 0x00, 0x9C, 0xB0, 0x3D, 0xE6, 0x57, 0xAC, 0xA6, 0x52, 0x0E,
+                0x74, 0xA0, 0x85, 0xE7, 0xDB, 0x35, 0x53, 0x1D, 0x1B, 0xD8, 0x62, 0x94, 0x32, 0x02,
+                0x85, 0xA7, 0x8F, 0x7E, 0x6B, 0x41, 0x15, 0x8B, 0x04, 0x04, 0x0F, 0x0C, 0x95, 0x49,
+                0x32, 0x31, 0xAF, 0xB9, 0x0E, 0x04, 0x77, 0xCF, 0x80, 0xD0, 0x00, 0x3A, 0x78,
+            ]
+        );
+        assert_eq!(hop_count, 10);
+        assert!(
+            hops[..hop_count]
+                .iter()
+                .map(|hop| (hop.symbols, hop.freq_hz))
+                .eq([
+                    (114, 868_068_750),
+                    (114, 868_162_500),
+                    (114, 868_072_656),
+                    (50, 868_135_156),
+                    (50, 868_103_906),
+                    (50, 868_119_531),
+                    (50, 868_111_719),
+                    (50, 868_107_813),
+                    (50, 868_045_313),
+                    (20, 868_033_594),
+                ])
+        );
+    }
+
+    #[test]
+    fn test_encode_us915_dr5() {
+        let params = LrFhssParams::new(
+            902_300_000,
+            LrFhssCr::Cr2_3,
+            LrFhssGrid::Grid25391,
+            LrFhssBw::Bw1523438,
+        );
+        assert_eq!(params.header_count, 2);
+        let (frame, frame_length, hops, hop_count) =
+            encode(&mut LrFhssV1::new(params, 300), b"hello");
+        assert_eq!(
+            frame[..frame_length],
+            [
+                0x0F, 0x28, 0x17, 0xF8, 0x49, 0x0B, 0x03, 0xDE, 0x65, 0x5A, 0x36, 0x79, 0xB3, 0xD1,
+                0xC3, 0xCA, 0x05, 0xF8, 0x12, 0x42, 0xC0, 0xF7, 0x99, 0x55, 0x8C, 0xDE, 0x2C, 0xB4,
+                0x50, 0x6A, 0x1D, 0xB0, 0x69, 0xEA, 0x2C, 0x6B, 0xF0, 0x8C, 0xE1, 0x21, 0x20,
+            ]
+        );
+        assert!(
+            hops[..hop_count]
+                .iter()
+                .map(|hop| (hop.symbols, hop.freq_hz))
+                .eq([
+                    (114, 902_731_641),
+                    (114, 902_376_172),
+                    (50, 902_604_688),
+                    (47, 902_147_656),
+                ])
+        );
+    }
+
+    #[test]
+    fn test_encode_errors() {
+        let params = LrFhssParams::new(
+            868_100_000,
+            LrFhssCr::Cr1_3,
+            LrFhssGrid::Grid3906,
+            LrFhssBw::Bw136719,
+        );
+        let mut frame = [0; 255];
+        let mut hops = [Hop {
+            symbols: 0,
+            freq_hz: 0,
+        }; 32];
+        assert_eq!(
+            LrFhssV1::new(params, 384).encode(b"hello", &mut frame, &mut hops),
+            Err(LrFhssError::InvalidHopSequenceId(384))
+        );
+        assert_eq!(
+            LrFhssV1::new(params.with_header_count(5), 0).encode(b"hello", &mut frame, &mut hops),
+            Err(LrFhssError::InvalidHeaderCount(5))
+        );
+        assert_eq!(
+            LrFhssV1::new(params, 0).encode(&[0; 100], &mut frame, &mut hops),
+            Err(LrFhssError::CapacityExceeded(CapacityExceeded {
+                len: params.frame_length(100),
+                capacity: 255
+            }))
+        );
+        assert_eq!(
+            LrFhssV1::new(params, 0).encode(b"0123456789", &mut frame, &mut hops[..9]),
+            Err(LrFhssError::CapacityExceeded(CapacityExceeded {
+                len: 10,
+                capacity: 9
+            }))
+        );
+        let unhopped = params.with_hopping(false);
+        let (_, _, hops, hop_count) = encode(&mut LrFhssV1::new(unhopped, 0), b"hello");
+        assert!(
+            hops[..hop_count]
+                .iter()
+                .all(|hop| hop.freq_hz == 868_100_000)
+        );
+    }
+}
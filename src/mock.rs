@@ -0,0 +1,144 @@
+//! SPI executor test double backed by `embedded-hal-mock`, for testing IRQ handlers and queue
+//! logic with `cargo test`.
+//!
+//! [`MockExecutor`] is created with the expected [`Exchange`]s, the TX bytes of each command
+//! and the RX bytes the radio answers. Executing a descriptor checks its TX buffer against the
+//! next exchange, writes the canned response to its RX buffer like the DMA would and records
+//! the descriptor. [`MockExecutor::done`] checks that all exchanges were executed.
+//!
+//! The RX buffer is written through the descriptor, so descriptors must come from
+//! [`Command::descriptor_mut`](super::commands::Command::descriptor_mut).
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::{
+//!     commands::{ClearIrqStatus, Command, GetIrqStatus, Irq},
+//!     mock::{Exchange, MockExecutor},
+//! };
+//!
+//! let mut get_irq_status = GetIrqStatus::new();
+//! let mut clear_irq_status = ClearIrqStatus::new(Irq::new().with_tx_done(true));
+//! let mut executor = MockExecutor::new(&[
+//!     Exchange { tx: &[0x12, 0, 0, 0], rx: &[0xA2, 0xA4, 0x00, 0x01] },
+//!     Exchange { tx: &[0x02, 0x00, 0x01], rx: &[0xA2, 0xA4, 0xA4] },
+//! ]);
+//!
+//! // The IRQ handler under test
+//! unsafe { executor.execute(get_irq_status.descriptor_mut()) }.unwrap();
+//! if get_irq_status.irq_status().tx_done() {
+//!     unsafe { executor.execute(clear_irq_status.descriptor_mut()) }.unwrap();
+//! }
+//!
+//! assert_eq!(executor.submitted(), [get_irq_status.descriptor(), clear_irq_status.descriptor()]);
+//! executor.done();
+//! ```
+
+use super::commands::SpiDescriptor;
+use super::timestamp::TransferHook;
+use alloc::vec::Vec;
+use embedded_hal::spi::{ErrorKind, SpiDevice};
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+/// A transfer expected by a [`MockExecutor`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Exchange<'a> {
+    /// Expected TX bytes.
+    pub tx: &'a [u8],
+    /// Canned RX bytes, as long as `tx`.
+    pub rx: &'a [u8],
+}
+
+/// # MockExecutor
+/// Executes descriptors on an `embedded-hal-mock` SPI device, one NSS transaction per
+/// descriptor.
+pub struct MockExecutor {
+    spi: Mock<u8>,
+    submitted: Vec<SpiDescriptor>,
+}
+impl MockExecutor {
+    /// Executor expecting `exchanges` in order.
+    pub fn new(exchanges: &[Exchange<'_>]) -> Self {
+        let transactions: Vec<Transaction<u8>> = exchanges
+            .iter()
+            .flat_map(|exchange| {
+                [
+                    Transaction::transaction_start(),
+                    Transaction::transfer(exchange.tx.to_vec(), exchange.rx.to_vec()),
+                    Transaction::transaction_end(),
+                ]
+            })
+            .collect();
+        Self {
+            spi: Mock::new(&transactions),
+            submitted: Vec::new(),
+        }
+    }
+    /// Transfers `descriptor`, writing the canned response to its RX buffer.
+    ///
+    /// ## Panics
+    /// If the TX bytes differ from the next exchange or no exchange is left.
+    ///
+    /// ## Safety
+    /// The TX buffer of `descriptor` must be valid for reads and its RX buffer valid for writes
+    /// of `transfer_length` bytes. The RX pointer must be derived from a mutable borrow, e.g.
+    /// with [`Command::descriptor_mut`](super::commands::Command::descriptor_mut), and no
+    /// reference to the buffers may be used until the call returns.
+    pub unsafe fn execute(&mut self, descriptor: SpiDescriptor) -> Result<(), ErrorKind> {
+        // SAFETY: guaranteed by the caller
+        unsafe { self.execute_with(descriptor, &mut ()) }
+    }
+    /// Transfers `descriptor` like [`execute`](Self::execute), calling `hook` at submission and
+    /// completion.
+    ///
+    /// ## Safety
+    /// See [`execute`](Self::execute).
+    pub unsafe fn execute_with(
+        &mut self,
+        descriptor: SpiDescriptor,
+        hook: &mut impl TransferHook,
+    ) -> Result<(), ErrorKind> {
+        self.submitted.push(descriptor);
+        hook.submitted(&descriptor);
+        if !descriptor.is_empty() {
+            // SAFETY: guaranteed by the caller
+            let (tx_buf, rx_buf) = unsafe {
+                (
+                    core::slice::from_raw_parts(descriptor.tx_buf_ptr, descriptor.len()),
+                    core::slice::from_raw_parts_mut(
+                        descriptor.rx_buf_ptr.cast_mut(),
+                        descriptor.len(),
+                    ),
+                )
+            };
+            self.spi.transfer(rx_buf, tx_buf)?;
+        }
+        hook.completed(&descriptor);
+        Ok(())
+    }
+    /// Transfers `descriptors` in order, stopping at the first error.
+    ///
+    /// ## Safety
+    /// Each descriptor must satisfy the requirements of [`execute`](Self::execute).
+    pub unsafe fn execute_all(
+        &mut self,
+        descriptors: impl IntoIterator<Item = SpiDescriptor>,
+    ) -> Result<(), ErrorKind> {
+        for descriptor in descriptors {
+            // SAFETY: guaranteed by the caller
+            unsafe { self.execute(descriptor) }?;
+        }
+        Ok(())
+    }
+    /// Descriptors executed so far, in order.
+    #[inline]
+    pub fn submitted(&self) -> &[SpiDescriptor] {
+        &self.submitted
+    }
+    /// Checks that all exchanges were executed.
+    ///
+    /// ## Panics
+    /// If an exchange was not executed.
+    pub fn done(mut self) {
+        self.spi.done();
+    }
+}
@@ -0,0 +1,198 @@
+//! Pool of reusable command storages, for transactions overlapping in time, e.g. telemetry
+//! reads while a TX is queued, without a `static` per concurrent command.
+//!
+//! Commands are checked out of a [`CommandPool`] as a [`Slot`], prepared, submitted and marked
+//! complete once their transfer is done, e.g. from the SPI/DMA transfer complete interrupt. A
+//! command can only be written and returned to the pool while its transfer is not in flight.
+//!
+//! A slot belongs to the pool it was checked out of: the pool identifies its slots by its
+//! address, so like the commands it holds, the pool must not move while commands are checked
+//! out, e.g. by being a `static`. Slots of other pools are rejected.
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::{commands::GetIrqStatus, pool::CommandPool};
+//!
+//! let mut pool: CommandPool<GetIrqStatus, 2> =
+//!     CommandPool::new([GetIrqStatus::new(), GetIrqStatus::new()]);
+//! let telemetry = pool.checkout().unwrap();
+//! let irq = pool.checkout().unwrap();
+//! assert!(pool.checkout().is_none());
+//!
+//! let descriptor = pool.submit(&irq).unwrap();
+//! assert_eq!(pool.in_flight(), 1);
+//! assert!(pool.get_mut(&irq).is_none());
+//! let irq = pool.release(irq).unwrap_err();
+//!
+//! // SPI/DMA transfer complete
+//! assert_eq!(pool.complete(&descriptor), Some(irq.index()));
+//! pool.get_mut(&irq).unwrap().rx_buf = [0xA2, 0xA4, 0x00, 0x01];
+//! assert!(pool.get(&irq).unwrap().irq_status().tx_done());
+//! pool.release(irq).unwrap();
+//! pool.release(telemetry).unwrap();
+//! assert_eq!(pool.available(), 2);
+//! ```
+
+use super::commands::{Command, SpiDescriptor};
+
+/// A command checked out of a [`CommandPool`] of `N` commands. Returned to the pool with
+/// [`CommandPool::release`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct Slot<const N: usize> {
+    /// Address of the pool.
+    pool: usize,
+    index: usize,
+}
+impl<const N: usize> Slot<N> {
+    /// Index of the command in the pool.
+    #[inline]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+}
+
+/// # CommandPool
+/// `N` storages of a command, checked out and returned as [`Slot`]s, with tracking of the
+/// transfers in flight.
+pub struct CommandPool<C, const N: usize> {
+    commands: [C; N],
+    checked_out: [bool; N],
+    in_flight: [bool; N],
+}
+impl<C: Command, const N: usize> CommandPool<C, N> {
+    const MIN_SIZE: () = assert!(N >= 1, "N must be at least 1");
+
+    #[inline]
+    pub const fn new(commands: [C; N]) -> Self {
+        let () = Self::MIN_SIZE;
+        Self {
+            commands,
+            checked_out: [false; N],
+            in_flight: [false; N],
+        }
+    }
+    #[inline]
+    fn address(&self) -> usize {
+        core::ptr::from_ref(self).addr()
+    }
+    /// Whether `slot` is checked out of this pool.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub fn owns(&self, slot: &Slot<N>) -> bool {
+        slot.pool == self.address() && self.checked_out[slot.index]
+    }
+    /// Checks out a free command, or `None` if all are checked out.
+    #[allow(clippy::indexing_slicing)]
+    pub fn checkout(&mut self) -> Option<Slot<N>> {
+        let index = self
+            .checked_out
+            .iter()
+            .position(|checked_out| !checked_out)?;
+        self.checked_out[index] = true;
+        Some(Slot {
+            pool: self.address(),
+            index,
+        })
+    }
+    /// The command of `slot`, or `None` if `slot` is not checked out of this pool.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub fn get(&self, slot: &Slot<N>) -> Option<&C> {
+        self.owns(slot).then(|| &self.commands[slot.index])
+    }
+    /// The command of `slot` for writing, or `None` if its transfer is in flight or `slot` is
+    /// not checked out of this pool.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub fn get_mut(&mut self, slot: &Slot<N>) -> Option<&mut C> {
+        if !self.owns(slot) || self.in_flight[slot.index] {
+            return None;
+        }
+        Some(&mut self.commands[slot.index])
+    }
+    /// Marks the command in flight, returning its descriptor, or `None` if it is already in
+    /// flight or `slot` is not checked out of this pool.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub fn submit(&mut self, slot: &Slot<N>) -> Option<SpiDescriptor> {
+        if !self.owns(slot) || self.in_flight[slot.index] {
+            return None;
+        }
+        self.in_flight[slot.index] = true;
+        Some(self.commands[slot.index].descriptor())
+    }
+    /// Marks the command of `descriptor` complete, returning its index, or `None` if it is not
+    /// a command of the pool in flight.
+    #[allow(clippy::indexing_slicing)]
+    pub fn complete(&mut self, descriptor: &SpiDescriptor) -> Option<usize> {
+        let index = self
+            .commands
+            .iter()
+            .zip(&self.in_flight)
+            .position(|(command, in_flight)| *in_flight && command.descriptor() == *descriptor)?;
+        self.in_flight[index] = false;
+        Some(index)
+    }
+    /// Returns the command to the pool, or gives `slot` back if its transfer is in flight or it
+    /// is not checked out of this pool.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub fn release(&mut self, slot: Slot<N>) -> Result<(), Slot<N>> {
+        if !self.owns(&slot) || self.in_flight[slot.index] {
+            return Err(slot);
+        }
+        self.checked_out[slot.index] = false;
+        Ok(())
+    }
+    /// Whether the transfer of the command is in flight, `false` if `slot` is not checked out of
+    /// this pool.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub fn is_in_flight(&self, slot: &Slot<N>) -> bool {
+        self.owns(slot) && self.in_flight[slot.index]
+    }
+    /// Number of transfers in flight.
+    #[inline]
+    pub fn in_flight(&self) -> usize {
+        self.in_flight
+            .iter()
+            .filter(|in_flight| **in_flight)
+            .count()
+    }
+    /// Number of commands which can be checked out.
+    #[inline]
+    pub fn available(&self) -> usize {
+        self.checked_out
+            .iter()
+            .filter(|checked_out| !**checked_out)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::GetStatus;
+
+    #[test]
+    fn test_pool_rejects_foreign_slot() {
+        let mut pool: CommandPool<GetStatus, 2> =
+            CommandPool::new([GetStatus::new(), GetStatus::new()]);
+        let mut other: CommandPool<GetStatus, 2> =
+            CommandPool::new([GetStatus::new(), GetStatus::new()]);
+        let slot = pool.checkout().unwrap();
+        let other_slot = other.checkout().unwrap();
+        assert_eq!(slot.index(), other_slot.index());
+
+        assert!(!other.owns(&slot));
+        assert!(other.get(&slot).is_none());
+        assert!(other.get_mut(&slot).is_none());
+        assert!(other.submit(&slot).is_none());
+        let slot = other.release(slot).unwrap_err();
+        assert!(other.owns(&other_slot));
+        assert_eq!(other.available(), 1);
+
+        pool.release(slot).unwrap();
+        other.release(other_slot).unwrap();
+    }
+}
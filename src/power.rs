@@ -0,0 +1,72 @@
+//! Typical supply current of the SX1262 per operating mode, from the datasheet, and average
+//! current estimation for battery lifetime budgeting. Currents are in nA, with the DC-DC
+//! regulator enabled.
+
+/// Sleep with cold start.
+pub const SLEEP_COLD: u32 = 160;
+/// Sleep with warm start, retaining the configuration.
+pub const SLEEP_WARM: u32 = 600;
+/// Standby with the RC oscillator.
+pub const STANDBY_RC: u32 = 600_000;
+/// Standby with the crystal oscillator.
+pub const STANDBY_XOSC: u32 = 800_000;
+/// LoRa RX at 125 kHz.
+pub const RX: u32 = 4_600_000;
+/// LoRa RX at 125 kHz with boosted gain.
+pub const RX_BOOSTED: u32 = 5_300_000;
+/// TX current by TX power in dBm, with the optimal PA settings for each power.
+pub const TX: [(i8, u32); 4] = [
+    (14, 90_000_000),
+    (17, 95_000_000),
+    (20, 102_000_000),
+    (22, 118_000_000),
+];
+
+/// TX current for `power` dBm, from the first entry of [`TX`] at or above `power`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::power;
+///
+/// assert_eq!(power::tx_current(10), 90_000_000);
+/// assert_eq!(power::tx_current(22), 118_000_000);
+/// ```
+#[allow(clippy::indexing_slicing)]
+pub const fn tx_current(power: i8) -> u32 {
+    let mut i: usize = 0;
+    while i < TX.len() - 1 {
+        if power <= TX[i].0 {
+            return TX[i].1;
+        }
+        i += 1;
+    }
+    TX[TX.len() - 1].1
+}
+
+/// Average current in nA of a node transmitting at `tx_power` dBm for `tx_us`, receiving for
+/// `rx_us` and sleeping with warm start for the rest of every `period_us`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{commands::{Bw, Cr, HeaderType, Sf}, power, timing};
+///
+/// // 10 byte uplink at SF7 every minute, with a 100 ms receive window
+/// let toa = timing::time_on_air_us(
+///     Sf::Sf7,
+///     Bw::Bw125,
+///     Cr::Cr4_5,
+///     false,
+///     8,
+///     HeaderType::VariableLength,
+///     10,
+///     true,
+/// );
+/// assert_eq!(power::average_current(14, toa, 100_000, 60_000_000), 70_089);
+/// ```
+pub const fn average_current(tx_power: i8, tx_us: u32, rx_us: u32, period_us: u32) -> u32 {
+    let sleep_us = period_us.saturating_sub(tx_us).saturating_sub(rx_us);
+    let charge = tx_current(tx_power) as u64 * tx_us as u64
+        + RX as u64 * rx_us as u64
+        + SLEEP_WARM as u64 * sleep_us as u64;
+    (charge / period_us as u64) as u32
+}
@@ -0,0 +1,282 @@
+//! Ready-made link configurations.
+//!
+//! The LoRa presets cover common regional channels and use an 8 symbol preamble, variable
+//! length header with up to 255 byte payloads, CRC on, standard IQ and the public (LoRaWAN) sync
+//! word.
+//! With the `llcc68` feature, the presets the LLCC68 does not support are not available.
+//!
+//! The GFSK presets follow the Semtech reference configurations: 40 bit preamble, 16 bit
+//! preamble detector, 24 bit sync word `0xC194C1`, variable length packets of up to 255 bytes,
+//! 2 byte inverted CCITT CRC and whitening.
+
+use super::commands::{
+    AddrComp, Bw, Cr, GfskBw, HeaderType, InvertIq, PreambleDetector, PulseShape,
+    SetModulationParamsGfsk, SetModulationParamsLora, SetPacketParams, SetPacketParamsGfsk,
+    SetRfFrequency, Sf, SpiDescriptor, WriteRegisters,
+};
+use super::registers::{
+    CrcInitialValue, CrcPolynomial, GfskSyncWord, NodeAddress, Register, RegisterBlock,
+};
+use super::sequences::LoraNetwork;
+
+/// # LoraPreset
+/// RF frequency, modulation parameters, packet parameters and sync word of a LoRa link.
+/// The packet type must be set to LoRa before transferring `descriptors()`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::presets::{self, LoraPreset};
+///
+/// static PRESET: LoraPreset = presets::EU868_SF7_BW125;
+/// assert_eq!(PRESET.set_rf_frequency.tx_buf, [0x86, 0x36, 0x41, 0x99, 0x99]);
+/// assert_eq!(PRESET.set_modulation_params.tx_buf, [0x8B, 0x07, 0x04, 0x01, 0]);
+/// assert_eq!(PRESET.set_packet_params.tx_buf, [0x8C, 0, 8, 0, 255, 1, 0]);
+/// assert_eq!(PRESET.write_sync_word.tx_buf, [0x0D, 0x07, 0x40, 0x34, 0x44]);
+/// assert_eq!(PRESET.descriptors().len(), 4);
+/// ```
+pub struct LoraPreset {
+    pub set_rf_frequency: SetRfFrequency,
+    pub set_modulation_params: SetModulationParamsLora,
+    pub set_packet_params: SetPacketParams,
+    pub write_sync_word: WriteRegisters<5>,
+}
+impl LoraPreset {
+    /// ## Panics
+    /// With the `llcc68` feature, if the LLCC68 does not support `sf` at `bw`.
+    #[inline]
+    pub const fn new(
+        freq_hz: u32,
+        sf: Sf,
+        bw: Bw,
+        cr: Cr,
+        low_data_rate_optimize: bool,
+        network: LoraNetwork,
+    ) -> Self {
+        Self {
+            set_rf_frequency: SetRfFrequency::from_hz(freq_hz),
+            set_modulation_params: SetModulationParamsLora::new(sf, bw, cr, low_data_rate_optimize),
+            set_packet_params: SetPacketParams::new(
+                8,
+                HeaderType::VariableLength,
+                255,
+                true,
+                InvertIq::Standard,
+            ),
+            write_sync_word: network.write_sync_word(),
+        }
+    }
+    #[inline]
+    pub const fn descriptors(&self) -> [SpiDescriptor; 4] {
+        [
+            self.set_rf_frequency.descriptor(),
+            self.set_modulation_params.descriptor(),
+            self.set_packet_params.descriptor(),
+            self.write_sync_word.descriptor(),
+        ]
+    }
+}
+
+/// EU868 channel 0 (868.1 MHz), SF7, 125 kHz.
+pub const EU868_SF7_BW125: LoraPreset = LoraPreset::new(
+    868_100_000,
+    Sf::Sf7,
+    Bw::Bw125,
+    Cr::Cr4_5,
+    false,
+    LoraNetwork::Public,
+);
+/// EU868 channel 0 (868.1 MHz), SF12, 125 kHz.
+#[cfg(not(feature = "llcc68"))]
+pub const EU868_SF12_BW125: LoraPreset = LoraPreset::new(
+    868_100_000,
+    Sf::Sf12,
+    Bw::Bw125,
+    Cr::Cr4_5,
+    true,
+    LoraNetwork::Public,
+);
+/// EU868 RX2 channel (869.525 MHz), SF12, 125 kHz.
+#[cfg(not(feature = "llcc68"))]
+pub const EU868_RX2: LoraPreset = LoraPreset::new(
+    869_525_000,
+    Sf::Sf12,
+    Bw::Bw125,
+    Cr::Cr4_5,
+    true,
+    LoraNetwork::Public,
+);
+/// US915 upstream channel 0 (902.3 MHz), SF10, 125 kHz.
+#[cfg(not(feature = "llcc68"))]
+pub const US915_SF10: LoraPreset = LoraPreset::new(
+    902_300_000,
+    Sf::Sf10,
+    Bw::Bw125,
+    Cr::Cr4_5,
+    false,
+    LoraNetwork::Public,
+);
+/// US915 upstream channel 64 (903.0 MHz), SF8, 500 kHz.
+pub const US915_SF8_BW500: LoraPreset = LoraPreset::new(
+    903_000_000,
+    Sf::Sf8,
+    Bw::Bw500,
+    Cr::Cr4_5,
+    false,
+    LoraNetwork::Public,
+);
+
+/// # GfskPreset
+/// Modulation parameters, packet parameters, sync word and CRC configuration of a GFSK link.
+/// The packet type must be set to GFSK before transferring `descriptors()`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::presets::{self, GfskPreset};
+///
+/// static PRESET: GfskPreset = presets::GFSK_50KBPS_FDEV25;
+/// assert_eq!(PRESET.set_modulation_params.tx_buf, [0x8B, 0, 0x50, 0, 0x0B, 0x0B, 0, 0x66, 0x66]);
+/// assert_eq!(PRESET.set_packet_params.tx_buf, [0x8C, 0, 40, 0x05, 24, 0, 1, 255, 0x06, 1]);
+/// assert_eq!(PRESET.write_sync_word.tx_buf[..6], [0x0D, 0x06, 0xC0, 0xC1, 0x94, 0xC1]);
+/// assert_eq!(PRESET.write_crc_initial_value.tx_buf, [0x0D, 0x06, 0xBC, 0x1D, 0x0F]);
+/// assert_eq!(PRESET.write_crc_polynomial.tx_buf, [0x0D, 0x06, 0xBE, 0x10, 0x21]);
+/// assert_eq!(PRESET.descriptors().len(), 5);
+/// ```
+pub struct GfskPreset {
+    pub set_modulation_params: SetModulationParamsGfsk,
+    pub set_packet_params: SetPacketParamsGfsk,
+    pub write_sync_word: WriteRegisters<11>,
+    pub write_crc_initial_value: WriteRegisters<5>,
+    pub write_crc_polynomial: WriteRegisters<5>,
+}
+impl GfskPreset {
+    /// Raw `br` and `fdev` as for [`SetModulationParamsGfsk`].
+    #[inline]
+    pub const fn new(br: u32, pulse_shape: PulseShape, bw: GfskBw, fdev: u32) -> Self {
+        Self {
+            set_modulation_params: SetModulationParamsGfsk::new(br, pulse_shape, bw, fdev),
+            set_packet_params: SetPacketParamsGfsk::new(
+                40,
+                PreambleDetector::Bits16,
+                24,
+                AddrComp::Off,
+                true,
+                255,
+                0x06,
+                true,
+            ),
+            write_sync_word: WriteRegisters::<11>::at_address(
+                GfskSyncWord::ADDRESS,
+                &[0xC1, 0x94, 0xC1, 0, 0, 0, 0, 0],
+            ),
+            write_crc_initial_value: WriteRegisters::<5>::at_address(
+                CrcInitialValue::ADDRESS,
+                &0x1D0F_u16.to_be_bytes(),
+            ),
+            write_crc_polynomial: WriteRegisters::<5>::at_address(
+                CrcPolynomial::ADDRESS,
+                &0x1021_u16.to_be_bytes(),
+            ),
+        }
+    }
+    #[inline]
+    pub const fn descriptors(&self) -> [SpiDescriptor; 5] {
+        [
+            self.set_modulation_params.descriptor(),
+            self.set_packet_params.descriptor(),
+            self.write_sync_word.descriptor(),
+            self.write_crc_initial_value.descriptor(),
+            self.write_crc_polynomial.descriptor(),
+        ]
+    }
+}
+
+/// # GfskAddressFilter
+/// GFSK packet parameters with address filtering, together with the node and broadcast
+/// addresses it filters on, so that the three are always configured together.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{
+///     commands::AddrComp,
+///     presets::{self, GfskAddressFilter},
+/// };
+///
+/// let filter = GfskAddressFilter::new(
+///     presets::GFSK_50KBPS_FDEV25.set_packet_params,
+///     AddrComp::NodeAndBroadcast,
+///     0x42,
+///     0xFF,
+/// );
+/// assert_eq!(filter.set_packet_params.tx_buf[5], 0x02);
+/// assert_eq!(filter.write_addresses.tx_buf, [0x0D, 0x06, 0xCD, 0x42, 0xFF]);
+/// assert_eq!(filter.descriptors().len(), 2);
+/// ```
+pub struct GfskAddressFilter {
+    pub set_packet_params: SetPacketParamsGfsk,
+    pub write_addresses: WriteRegisters<5>,
+}
+impl GfskAddressFilter {
+    /// Enables `addr_comp` filtering in `set_packet_params`.
+    #[inline]
+    pub const fn new(
+        mut set_packet_params: SetPacketParamsGfsk,
+        addr_comp: AddrComp,
+        node_address: u8,
+        broadcast_address: u8,
+    ) -> Self {
+        set_packet_params.tx_buf[5] = addr_comp as u8;
+        Self {
+            set_packet_params,
+            write_addresses: WriteRegisters::<5>::at_address(
+                NodeAddress::ADDRESS,
+                &[node_address, broadcast_address],
+            ),
+        }
+    }
+    #[inline]
+    pub const fn descriptors(&self) -> [SpiDescriptor; 2] {
+        [
+            self.set_packet_params.descriptor(),
+            self.write_addresses.descriptor(),
+        ]
+    }
+}
+
+/// 50 kbps, 25 kHz deviation, BT 1.0, 117.3 kHz RX bandwidth.
+pub const GFSK_50KBPS_FDEV25: GfskPreset =
+    GfskPreset::new(0x00_5000, PulseShape::Bt1, GfskBw::Bw117_3, 0x00_6666);
+/// 100 kbps, 50 kHz deviation, BT 1.0, 234.3 kHz RX bandwidth.
+pub const GFSK_100KBPS_FDEV50: GfskPreset =
+    GfskPreset::new(0x00_2800, PulseShape::Bt1, GfskBw::Bw234_3, 0x00_CCCC);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preset_frequencies() {
+        #[cfg(not(feature = "llcc68"))]
+        assert_eq!(
+            EU868_RX2.set_rf_frequency.tx_buf,
+            [0x86, 0x36, 0x58, 0x66, 0x66]
+        );
+        #[cfg(not(feature = "llcc68"))]
+        assert_eq!(
+            US915_SF10.set_rf_frequency.tx_buf,
+            [0x86, 0x38, 0x64, 0xCC, 0xCC]
+        );
+        assert_eq!(
+            US915_SF8_BW500.set_rf_frequency.tx_buf,
+            [0x86, 0x38, 0x70, 0, 0]
+        );
+        #[cfg(not(feature = "llcc68"))]
+        assert_eq!(
+            EU868_SF12_BW125.set_modulation_params.tx_buf,
+            [0x8B, 0x0C, 0x04, 0x01, 1]
+        );
+        assert_eq!(
+            GFSK_100KBPS_FDEV50.set_modulation_params.tx_buf,
+            [0x8B, 0, 0x28, 0, 0x0B, 0x0A, 0, 0xCC, 0xCC]
+        );
+    }
+}
@@ -0,0 +1,72 @@
+//! Descriptor queues built on `heapless`, for projects already using it instead of
+//! `arraydeque`.
+//!
+//! [`DescriptorQueue`] is a single producer single consumer queue: split it once, enqueue
+//! descriptors from the application with the [`DescriptorProducer`] and dequeue them from the
+//! SPI/DMA transfer complete interrupt with the [`DescriptorConsumer`]. [`DescriptorMpmcQueue`]
+//! accepts descriptors from several contexts, on targets with atomic compare and swap or with
+//! the `portable-atomic` feature.
+//!
+//! The buffers behind the descriptors must outlive their stay in the queue, e.g. by being
+//! `static`.
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::{
+//!     commands::{SetStandby, SetTx, StdbyConfig},
+//!     queue::{self, DescriptorQueue},
+//! };
+//!
+//! static SET_STANDBY: SetStandby = SetStandby::new(StdbyConfig::StdbyRc);
+//! static SET_TX: SetTx = SetTx::new(0);
+//!
+//! let mut queue: DescriptorQueue<4> = DescriptorQueue::new();
+//! let (mut producer, mut consumer) = queue.split();
+//! queue::enqueue_all(&mut producer, &[SET_STANDBY.descriptor(), SET_TX.descriptor()]).unwrap();
+//! assert!(queue::enqueue_all(&mut producer, &[SET_STANDBY.descriptor(); 2]).is_err());
+//!
+//! assert_eq!(consumer.dequeue(), Some(SET_STANDBY.descriptor()));
+//! assert_eq!(consumer.dequeue(), Some(SET_TX.descriptor()));
+//! assert_eq!(consumer.dequeue(), None);
+//! ```
+
+use super::commands::SpiDescriptor;
+use heapless::spsc::{Consumer, Producer, Queue};
+
+/// Single producer single consumer descriptor queue, holding up to `N - 1` descriptors.
+pub type DescriptorQueue<const N: usize> = Queue<SpiDescriptor, N>;
+/// Enqueuing half of a [`DescriptorQueue`].
+pub type DescriptorProducer<'a, const N: usize> = Producer<'a, SpiDescriptor, N>;
+/// Dequeuing half of a [`DescriptorQueue`].
+pub type DescriptorConsumer<'a, const N: usize> = Consumer<'a, SpiDescriptor, N>;
+
+/// Multiple producer multiple consumer descriptor queue. `N` must be a power of 2.
+#[cfg(any(target_has_atomic = "8", feature = "portable-atomic"))]
+pub type DescriptorMpmcQueue<const N: usize> = heapless::mpmc::MpMcQueue<SpiDescriptor, N>;
+
+/// Not enough free space in the queue for the descriptors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QueueFull {
+    pub needed: usize,
+    pub free: usize,
+}
+
+/// Enqueues all `descriptors`, or none if they do not all fit, so that the commands of a
+/// sequence are never partially queued.
+pub fn enqueue_all<const N: usize>(
+    producer: &mut DescriptorProducer<'_, N>,
+    descriptors: &[SpiDescriptor],
+) -> Result<(), QueueFull> {
+    let free = producer.capacity() - producer.len();
+    if descriptors.len() > free {
+        return Err(QueueFull {
+            needed: descriptors.len(),
+            free,
+        });
+    }
+    for &descriptor in descriptors {
+        // Only this producer enqueues, so the free space checked above cannot shrink
+        let _ = producer.enqueue(descriptor);
+    }
+    Ok(())
+}
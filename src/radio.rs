@@ -0,0 +1,122 @@
+//! Chip mode state machine. `Radio<Mode>` only offers the commands which are valid transitions
+//! from its mode (datasheet 9), and returns the same command buffers as the [`commands`]
+//! module.
+//!
+//! [`commands`]: super::commands
+#![allow(clippy::new_without_default)]
+
+use super::commands::{SetFs, SetRx, SetSleep, SetStandby, SetTx, StdbyConfig};
+use core::marker::PhantomData;
+
+/// Sleep mode.
+pub struct Sleep;
+/// STDBY_RC mode.
+pub struct StandbyRc;
+/// STDBY_XOSC mode.
+pub struct StandbyXosc;
+/// Frequency synthesis mode.
+pub struct Fs;
+/// TX mode.
+pub struct Tx;
+/// RX mode.
+pub struct Rx;
+
+/// Standby modes, from which every other mode can be entered.
+pub trait Standby {}
+impl Standby for StandbyRc {}
+impl Standby for StandbyXosc {}
+
+/// # Radio
+/// Tracks the chip mode in the type. Each transition consumes the radio and returns it in the
+/// new mode together with the command to transfer.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::radio::{Radio, Rx, StandbyRc};
+///
+/// let radio: Radio<StandbyRc> = Radio::new();
+/// let (radio, set_fs) = radio.set_fs();
+/// assert_eq!(set_fs.tx_buf, [0xC1]);
+/// let (radio, set_rx) = radio.set_rx(120);
+/// assert_eq!(set_rx.tx_buf, [0x82, 0, 0, 120]);
+/// let radio: Radio<StandbyRc> = radio.rx_done();
+/// let (_radio, set_sleep) = radio.set_sleep(true);
+/// assert_eq!(set_sleep.tx_buf, [0x84, 0x04]);
+/// ```
+///
+/// Starting a transmission while sleeping does not compile:
+/// ```compile_fail
+/// use sx126x_spi_buffers::radio::{Radio, StandbyRc};
+///
+/// let (radio, _) = Radio::<StandbyRc>::new().set_sleep(true);
+/// let (radio, _) = radio.set_tx(0);
+/// ```
+pub struct Radio<M> {
+    mode: PhantomData<M>,
+}
+impl<M> Radio<M> {
+    #[inline]
+    const fn into_mode<N>(self) -> Radio<N> {
+        Radio { mode: PhantomData }
+    }
+    /// Enters STDBY_RC - also wakes the device from sleep.
+    #[inline]
+    pub const fn set_standby_rc(self) -> (Radio<StandbyRc>, SetStandby) {
+        (self.into_mode(), SetStandby::new(StdbyConfig::StdbyRc))
+    }
+    /// Enters STDBY_XOSC - also wakes the device from sleep.
+    #[inline]
+    pub const fn set_standby_xosc(self) -> (Radio<StandbyXosc>, SetStandby) {
+        (self.into_mode(), SetStandby::new(StdbyConfig::StdbyXosc))
+    }
+}
+impl Radio<StandbyRc> {
+    /// The device is in STDBY_RC after power up or reset.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { mode: PhantomData }
+    }
+}
+impl<M: Standby> Radio<M> {
+    #[inline]
+    pub const fn set_sleep(self, warm_start: bool) -> (Radio<Sleep>, SetSleep) {
+        (self.into_mode(), SetSleep::new(warm_start))
+    }
+    #[inline]
+    pub const fn set_fs(self) -> (Radio<Fs>, SetFs) {
+        (self.into_mode(), SetFs::new())
+    }
+    #[inline]
+    pub const fn set_tx(self, timeout: u32) -> (Radio<Tx>, SetTx) {
+        (self.into_mode(), SetTx::new(timeout))
+    }
+    #[inline]
+    pub const fn set_rx(self, timeout: u32) -> (Radio<Rx>, SetRx) {
+        (self.into_mode(), SetRx::new(timeout))
+    }
+}
+impl Radio<Fs> {
+    #[inline]
+    pub const fn set_tx(self, timeout: u32) -> (Radio<Tx>, SetTx) {
+        (self.into_mode(), SetTx::new(timeout))
+    }
+    #[inline]
+    pub const fn set_rx(self, timeout: u32) -> (Radio<Rx>, SetRx) {
+        (self.into_mode(), SetRx::new(timeout))
+    }
+}
+impl Radio<Tx> {
+    /// The device returns to STDBY_RC on `TxDone` or `Timeout`, with the default fallback mode.
+    #[inline]
+    pub const fn tx_done(self) -> Radio<StandbyRc> {
+        self.into_mode()
+    }
+}
+impl Radio<Rx> {
+    /// The device returns to STDBY_RC on `RxDone` or `Timeout` in single mode, with the
+    /// default fallback mode.
+    #[inline]
+    pub const fn rx_done(self) -> Radio<StandbyRc> {
+        self.into_mode()
+    }
+}
@@ -0,0 +1,116 @@
+//! Regional regulatory limits.
+
+use super::commands::{RampTime, SetTxParams};
+
+/// TX power above the regional limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PowerLimitExceeded {
+    /// Requested TX power in dBm.
+    pub requested: i8,
+    /// Maximum TX power in dBm with the given antenna gain.
+    pub max: i8,
+}
+
+/// # Region
+/// Regulatory region, with the maximum EIRP of the LoRaWAN regional parameters.
+///
+/// The TX power set with `SetTxParams` is the power at the chip output, so the antenna gain
+/// in dBi is added before comparing it with the limit.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{
+///     commands::RampTime,
+///     regions::{PowerLimitExceeded, Region},
+/// };
+///
+/// assert_eq!(Region::Eu868.max_tx_power(2), 14);
+///
+/// let set_tx_params = Region::Eu868.tx_params(14, RampTime::Ramp200U, 2).unwrap();
+/// assert_eq!(set_tx_params.tx_buf, [0x8E, 14, 0x04]);
+/// assert_eq!(
+///     Region::Eu868.tx_params(22, RampTime::Ramp200U, 2).err(),
+///     Some(PowerLimitExceeded { requested: 22, max: 14 })
+/// );
+///
+/// let (set_tx_params, exceeded) = Region::Eu868.tx_params_clamped(22, RampTime::Ramp200U, 2);
+/// assert_eq!(set_tx_params.tx_buf, [0x8E, 14, 0x04]);
+/// assert_eq!(exceeded, Some(PowerLimitExceeded { requested: 22, max: 14 }));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Region {
+    Eu868,
+    Us915,
+    Au915,
+    As923,
+    Kr920,
+    In865,
+    Cn470,
+}
+impl Region {
+    /// Maximum EIRP in dBm.
+    #[inline]
+    pub const fn max_eirp(self) -> i8 {
+        match self {
+            Region::Eu868 => 16,
+            Region::Us915 => 30,
+            Region::Au915 => 30,
+            Region::As923 => 16,
+            Region::Kr920 => 14,
+            Region::In865 => 30,
+            Region::Cn470 => 19,
+        }
+    }
+    /// Maximum TX power in dBm with an antenna gain of `antenna_gain` dBi.
+    #[inline]
+    pub const fn max_tx_power(self, antenna_gain: i8) -> i8 {
+        self.max_eirp() - antenna_gain
+    }
+    /// Checks `power` in dBm against the regional limit.
+    #[inline]
+    pub const fn check_tx_power(
+        self,
+        power: i8,
+        antenna_gain: i8,
+    ) -> Result<i8, PowerLimitExceeded> {
+        let max = self.max_tx_power(antenna_gain);
+        if power > max {
+            Err(PowerLimitExceeded {
+                requested: power,
+                max,
+            })
+        } else {
+            Ok(power)
+        }
+    }
+    /// `SetTxParams`, rejecting a TX power above the regional limit.
+    #[inline]
+    pub const fn tx_params(
+        self,
+        power: i8,
+        ramp_time: RampTime,
+        antenna_gain: i8,
+    ) -> Result<SetTxParams, PowerLimitExceeded> {
+        match self.check_tx_power(power, antenna_gain) {
+            Ok(power) => Ok(SetTxParams::new(power as u8, ramp_time)),
+            Err(exceeded) => Err(exceeded),
+        }
+    }
+    /// `SetTxParams`, clamping a TX power above the regional limit. The limit that was exceeded
+    /// is returned alongside.
+    #[inline]
+    pub const fn tx_params_clamped(
+        self,
+        power: i8,
+        ramp_time: RampTime,
+        antenna_gain: i8,
+    ) -> (SetTxParams, Option<PowerLimitExceeded>) {
+        match self.check_tx_power(power, antenna_gain) {
+            Ok(power) => (SetTxParams::new(power as u8, ramp_time), None),
+            Err(exceeded) => (
+                SetTxParams::new(exceeded.max as u8, ramp_time),
+                Some(exceeded),
+            ),
+        }
+    }
+}
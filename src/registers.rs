@@ -1,67 +1,500 @@
 //! Register definitions
 
-#[const_trait]
+use super::commands::{Device, SpiDescriptor, TcxoVoltage, WriteRegister};
+use bitfield_struct::bitfield;
+
+#[cfg_attr(feature = "nightly", const_trait)]
 pub trait Register: Copy {
     const ADDRESS: u16;
     fn bits(&self) -> u8;
     fn from_bits(bits: u8) -> Self;
 }
 
+/// A group of `LEN` contiguous registers holding a single logical value,
+/// written with `WriteRegisters::from_block` and read with `ReadRegisters::for_block`.
+#[cfg_attr(feature = "nightly", const_trait)]
+pub trait RegisterBlock<const LEN: usize>: Copy {
+    const ADDRESS: u16;
+    fn bytes(&self) -> [u8; LEN];
+    fn from_bytes(bytes: [u8; LEN]) -> Self;
+}
+
+/// A logical 16 bit value in two contiguous registers, most significant byte first.
+/// Every `Register16` is a [`RegisterBlock`] of 2 registers.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{
+///     commands::{ReadRegisters, WriteRegisters},
+///     registers::{LoraSyncWord, Register16},
+/// };
+///
+/// let write_sync_word = WriteRegisters::<5>::from_block(LoraSyncWord::PUBLIC);
+/// assert_eq!(write_sync_word.tx_buf, [0x0D, 0x07, 0x40, 0x34, 0x44]);
+///
+/// let mut read_sync_word = ReadRegisters::<6>::at_address(<LoraSyncWord as Register16>::ADDRESS);
+/// read_sync_word.rx_buf = [0xA2, 0xA2, 0xA2, 0xA2, 0x14, 0x24];
+/// assert_eq!(read_sync_word.bytes::<2>(), LoraSyncWord::PRIVATE.value().to_be_bytes());
+/// ```
+#[cfg_attr(feature = "nightly", const_trait)]
+pub trait Register16: Copy {
+    const ADDRESS: u16;
+    fn value(&self) -> u16;
+    fn from_value(value: u16) -> Self;
+}
+maybe_const! {
+    impl<T: [const] Register16> [const] RegisterBlock<2> for T {
+        const ADDRESS: u16 = <T as Register16>::ADDRESS;
+        #[inline]
+        fn bytes(&self) -> [u8; 2] {
+            self.value().to_be_bytes()
+        }
+        #[inline]
+        fn from_bytes(bytes: [u8; 2]) -> Self {
+            Self::from_value(u16::from_be_bytes(bytes))
+        }
+    }
+}
+
+/// Tuples of registers at contiguous addresses form a register block.
+/// Non-contiguous addresses fail at compile time.
+macro_rules! impl_register_block_for_tuple {
+    ($len:literal; ($first:ident, $first_index:tt) $(, ($reg:ident, $index:tt))*) => {
+        maybe_const! {
+            impl<$first: [const] Register $(, $reg: [const] Register)*> [const] RegisterBlock<$len>
+                for ($first, $($reg,)*)
+            {
+                const ADDRESS: u16 = {
+                    let mut _address = $first::ADDRESS;
+                    $(
+                        _address += 1;
+                        assert!($reg::ADDRESS == _address, "registers are not contiguous");
+                    )*
+                    $first::ADDRESS
+                };
+                #[inline]
+                fn bytes(&self) -> [u8; $len] {
+                    [self.$first_index.bits(), $(self.$index.bits(),)*]
+                }
+                #[inline]
+                fn from_bytes(bytes: [u8; $len]) -> Self {
+                    ($first::from_bits(bytes[$first_index]), $($reg::from_bits(bytes[$index]),)*)
+                }
+            }
+        }
+    };
+}
+impl_register_block_for_tuple!(2; (A, 0), (B, 1));
+impl_register_block_for_tuple!(3; (A, 0), (B, 1), (C, 2));
+impl_register_block_for_tuple!(4; (A, 0), (B, 1), (C, 2), (D, 3));
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-pub struct LoraSyncWordMsb(pub u8);
-impl const Register for LoraSyncWordMsb {
-    const ADDRESS: u16 = 0x0740;
-    #[inline]
-    fn bits(&self) -> u8 {
-        self.0
+pub struct LoraSyncWord(pub u16);
+maybe_const! {
+    impl [const] Register16 for LoraSyncWord {
+        const ADDRESS: u16 = 0x0740;
+        #[inline]
+        fn value(&self) -> u16 {
+            self.0
+        }
+        #[inline]
+        fn from_value(value: u16) -> Self {
+            Self(value)
+        }
     }
-    #[inline]
-    fn from_bits(bits: u8) -> Self {
-        Self(bits)
+}
+impl LoraSyncWord {
+    pub const RESET: Self = Self::PRIVATE;
+    /// Sync word of public networks such as LoRaWAN.
+    pub const PUBLIC: Self = Self(0x3444);
+    /// Sync word of private networks.
+    pub const PRIVATE: Self = Self(0x1424);
+}
+
+/// GFSK CRC initial value, `0x06BC` - `0x06BD`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CrcInitialValue(pub u16);
+maybe_const! {
+    impl [const] Register16 for CrcInitialValue {
+        const ADDRESS: u16 = 0x06BC;
+        #[inline]
+        fn value(&self) -> u16 {
+            self.0
+        }
+        #[inline]
+        fn from_value(value: u16) -> Self {
+            Self(value)
+        }
+    }
+}
+impl CrcInitialValue {
+    pub const RESET: Self = Self(0x1D0F);
+}
+
+/// GFSK CRC polynomial, `0x06BE` - `0x06BF`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CrcPolynomial(pub u16);
+maybe_const! {
+    impl [const] Register16 for CrcPolynomial {
+        const ADDRESS: u16 = 0x06BE;
+        #[inline]
+        fn value(&self) -> u16 {
+            self.0
+        }
+        #[inline]
+        fn from_value(value: u16) -> Self {
+            Self(value)
+        }
+    }
+}
+impl CrcPolynomial {
+    pub const RESET: Self = Self(0x1021);
+}
+
+/// GFSK sync word, `0x06C0` - `0x06C7`. Only the first `sync_word_length` bits set in
+/// `SetPacketParamsGfsk` are used.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct GfskSyncWord(pub [u8; 8]);
+maybe_const! {
+    impl [const] RegisterBlock<8> for GfskSyncWord {
+        const ADDRESS: u16 = 0x06C0;
+        #[inline]
+        fn bytes(&self) -> [u8; 8] {
+            self.0
+        }
+        #[inline]
+        fn from_bytes(bytes: [u8; 8]) -> Self {
+            Self(bytes)
+        }
+    }
+}
+
+/// MSB of the 9 bit GFSK whitening initial value in bit 0. The other bits must be preserved.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WhiteningInitialValueMsb(pub u8);
+maybe_const! {
+    impl [const] Register for WhiteningInitialValueMsb {
+        const ADDRESS: u16 = 0x06B8;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+impl WhiteningInitialValueMsb {
+    pub const RESET: Self = Self(0x01);
+}
+
+/// LSB of the 9 bit GFSK whitening initial value.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WhiteningInitialValueLsb(pub u8);
+maybe_const! {
+    impl [const] Register for WhiteningInitialValueLsb {
+        const ADDRESS: u16 = 0x06B9;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+impl WhiteningInitialValueLsb {
+    pub const RESET: Self = Self(0xFF);
+}
+
+/// GFSK node address, compared to the first payload byte when address filtering is enabled.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NodeAddress(pub u8);
+maybe_const! {
+    impl [const] Register for NodeAddress {
+        const ADDRESS: u16 = 0x06CD;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
     }
 }
+impl NodeAddress {
+    pub const RESET: Self = Self(0x00);
+}
+
+/// GFSK broadcast address, compared to the first payload byte when filtering on node and
+/// broadcast addresses.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BroadcastAddress(pub u8);
+maybe_const! {
+    impl [const] Register for BroadcastAddress {
+        const ADDRESS: u16 = 0x06CE;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+impl BroadcastAddress {
+    pub const RESET: Self = Self(0x00);
+}
+
+/// LR-FHSS sync word, `0x06C0` - `0x06C3`, shared with the GFSK sync word.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LrFhssSyncWord(pub [u8; 4]);
+maybe_const! {
+    impl [const] RegisterBlock<4> for LrFhssSyncWord {
+        const ADDRESS: u16 = 0x06C0;
+        #[inline]
+        fn bytes(&self) -> [u8; 4] {
+            self.0
+        }
+        #[inline]
+        fn from_bytes(bytes: [u8; 4]) -> Self {
+            Self(bytes)
+        }
+    }
+}
+impl LrFhssSyncWord {
+    /// Sync word of LoRaWAN LR-FHSS uplinks.
+    pub const LORAWAN: Self = Self([0x2C, 0x0F, 0x79, 0x95]);
+}
+
+/// LR-FHSS hop control.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LrFhssControl(pub u8);
+maybe_const! {
+    impl [const] Register for LrFhssControl {
+        const ADDRESS: u16 = 0x0385;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+impl LrFhssControl {
+    pub const HOPPING_DISABLED: Self = Self(0x00);
+    pub const HOPPING_ENABLED: Self = Self(0x0C);
+}
+
+/// LR-FHSS physical payload length in bytes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LrFhssPacketLength(pub u8);
+maybe_const! {
+    impl [const] Register for LrFhssPacketLength {
+        const ADDRESS: u16 = 0x0386;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+/// Number of LR-FHSS hops, followed by the hop table at `0x0388` of 16 entries of a 2 byte symbol
+/// count and a 4 byte frequency.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LrFhssNumHops(pub u8);
+maybe_const! {
+    impl [const] Register for LrFhssNumHops {
+        const ADDRESS: u16 = 0x0387;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LoraSyncWordMsb(pub u8);
+maybe_const! {
+    impl [const] Register for LoraSyncWordMsb {
+        const ADDRESS: u16 = 0x0740;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+impl LoraSyncWordMsb {
+    pub const RESET: Self = Self(0x14);
+}
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct LoraSyncWordLsb(pub u8);
-impl const Register for LoraSyncWordLsb {
-    const ADDRESS: u16 = 0x0741;
-    #[inline]
-    fn bits(&self) -> u8 {
-        self.0
+maybe_const! {
+    impl [const] Register for LoraSyncWordLsb {
+        const ADDRESS: u16 = 0x0741;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
     }
-    #[inline]
-    fn from_bits(bits: u8) -> Self {
-        Self(bits)
+}
+impl LoraSyncWordLsb {
+    pub const RESET: Self = Self(0x24);
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FreqErrorIndicator0(pub u8);
+maybe_const! {
+    impl [const] Register for FreqErrorIndicator0 {
+        const ADDRESS: u16 = 0x076B;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FreqErrorIndicator1(pub u8);
+maybe_const! {
+    impl [const] Register for FreqErrorIndicator1 {
+        const ADDRESS: u16 = 0x076C;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FreqErrorIndicator2(pub u8);
+maybe_const! {
+    impl [const] Register for FreqErrorIndicator2 {
+        const ADDRESS: u16 = 0x076D;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
     }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct RandomNumberGen0(pub u8);
-impl const Register for RandomNumberGen0 {
-    const ADDRESS: u16 = 0x0819;
-    #[inline]
-    fn bits(&self) -> u8 {
-        self.0
+maybe_const! {
+    impl [const] Register for RandomNumberGen0 {
+        const ADDRESS: u16 = 0x0819;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
     }
-    #[inline]
-    fn from_bits(bits: u8) -> Self {
-        Self(bits)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RandomNumberGen1(pub u8);
+maybe_const! {
+    impl [const] Register for RandomNumberGen1 {
+        const ADDRESS: u16 = 0x081A;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
     }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-pub struct RxGain(pub RxGainSetting);
-impl const Register for RxGain {
-    const ADDRESS: u16 = 0x08AC;
-    #[inline]
-    fn bits(&self) -> u8 {
-        self.0 as u8
+pub struct RandomNumberGen2(pub u8);
+maybe_const! {
+    impl [const] Register for RandomNumberGen2 {
+        const ADDRESS: u16 = 0x081B;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
     }
-    #[inline]
-    fn from_bits(bits: u8) -> Self {
-        Self(RxGainSetting::from(bits))
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RandomNumberGen3(pub u8);
+maybe_const! {
+    impl [const] Register for RandomNumberGen3 {
+        const ADDRESS: u16 = 0x081C;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RxGain(pub RxGainSetting);
+maybe_const! {
+    impl [const] Register for RxGain {
+        const ADDRESS: u16 = 0x08AC;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0 as u8
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(RxGainSetting::from(bits))
+        }
     }
 }
+impl RxGain {
+    pub const RESET: Self = Self(RxGainSetting::PowerSaving);
+}
 #[repr(u8)]
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum RxGainSetting {
@@ -82,51 +515,692 @@ impl RxGainSetting {
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct RxGainRetention0(pub u8);
-impl const Register for RxGainRetention0 {
-    const ADDRESS: u16 = 0x029F;
+maybe_const! {
+    impl [const] Register for RxGainRetention0 {
+        const ADDRESS: u16 = 0x029F;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RxGainRetention1(pub u8);
+maybe_const! {
+    impl [const] Register for RxGainRetention1 {
+        const ADDRESS: u16 = 0x02A0;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RxGainRetention2(pub u8);
+maybe_const! {
+    impl [const] Register for RxGainRetention2 {
+        const ADDRESS: u16 = 0x02A1;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RtcControl(pub u8);
+maybe_const! {
+    impl [const] Register for RtcControl {
+        const ADDRESS: u16 = 0x0902;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct XtaTrim(pub u8);
+maybe_const! {
+    impl [const] Register for XtaTrim {
+        const ADDRESS: u16 = 0x0911;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+impl XtaTrim {
+    pub const RESET: Self = Self(0x05);
+
+    /// Trim value closest to a capacitance given in units of 0.01 pF.
     #[inline]
-    fn bits(&self) -> u8 {
-        self.0
+    pub const fn from_capacitance(centi_pf: u16) -> Self {
+        Self(xtal_trim_from_capacitance(centi_pf))
     }
+    /// Trim capacitance in units of 0.01 pF.
     #[inline]
-    fn from_bits(bits: u8) -> Self {
-        Self(bits)
+    pub const fn capacitance(&self) -> u16 {
+        xtal_trim_capacitance(self.0)
     }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-pub struct RxGainRetention1(pub u8);
-impl const Register for RxGainRetention1 {
-    const ADDRESS: u16 = 0x02A0;
+pub struct XtbTrim(pub u8);
+maybe_const! {
+    impl [const] Register for XtbTrim {
+        const ADDRESS: u16 = 0x0912;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+impl XtbTrim {
+    pub const RESET: Self = Self(0x05);
+
+    /// Trim value closest to a capacitance given in units of 0.01 pF.
     #[inline]
-    fn bits(&self) -> u8 {
-        self.0
+    pub const fn from_capacitance(centi_pf: u16) -> Self {
+        Self(xtal_trim_from_capacitance(centi_pf))
     }
+    /// Trim capacitance in units of 0.01 pF.
     #[inline]
-    fn from_bits(bits: u8) -> Self {
-        Self(bits)
+    pub const fn capacitance(&self) -> u16 {
+        xtal_trim_capacitance(self.0)
     }
 }
 
+/// Trim capacitance is 11.3 pF + 0.47 pF per step, up to 33.4 pF at 0x2F.
+const XTAL_TRIM_BASE: u16 = 1130;
+const XTAL_TRIM_STEP: u16 = 47;
+const XTAL_TRIM_MAX: u8 = 0x2F;
+
+#[inline]
+const fn xtal_trim_from_capacitance(centi_pf: u16) -> u8 {
+    if centi_pf <= XTAL_TRIM_BASE {
+        return 0;
+    }
+    let code = (centi_pf - XTAL_TRIM_BASE + XTAL_TRIM_STEP / 2) / XTAL_TRIM_STEP;
+    if code > XTAL_TRIM_MAX as u16 {
+        XTAL_TRIM_MAX
+    } else {
+        code as u8
+    }
+}
+#[inline]
+const fn xtal_trim_capacitance(code: u8) -> u16 {
+    let code = if code > XTAL_TRIM_MAX {
+        XTAL_TRIM_MAX
+    } else {
+        code
+    };
+    XTAL_TRIM_BASE + code as u16 * XTAL_TRIM_STEP
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-pub struct RxGainRetention2(pub u8);
-impl const Register for RxGainRetention2 {
-    const ADDRESS: u16 = 0x02A1;
-    #[inline]
-    fn bits(&self) -> u8 {
-        self.0
+pub struct Dio3OutputVoltageControl(pub TcxoVoltage);
+maybe_const! {
+    impl [const] Register for Dio3OutputVoltageControl {
+        const ADDRESS: u16 = 0x0920;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0 as u8
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(TcxoVoltage::from_bits(bits))
+        }
+    }
+}
+impl Dio3OutputVoltageControl {
+    pub const RESET: Self = Self(TcxoVoltage::V1_7);
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct EventMask(pub Events);
+maybe_const! {
+    impl [const] Register for EventMask {
+        const ADDRESS: u16 = 0x0944;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0.into_bits()
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(Events::from_bits(bits))
+        }
+    }
+}
+/// Event flags of the [`EventMask`] register. Writing 1 to a flag clears the event.
+/// Undocumented bits are preserved by `from_bits`/`into_bits`.
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+pub struct Events {
+    #[bits(1)]
+    __: bool,
+    #[bits(1)]
+    pub timeout: bool,
+    #[bits(6)]
+    __: u8,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DioOutputEnable(pub DioPins);
+maybe_const! {
+    impl [const] Register for DioOutputEnable {
+        const ADDRESS: u16 = 0x0580;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0.into_bits()
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(DioPins::from_bits(bits))
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DioInputEnable(pub DioPins);
+maybe_const! {
+    impl [const] Register for DioInputEnable {
+        const ADDRESS: u16 = 0x0583;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0.into_bits()
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(DioPins::from_bits(bits))
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DioPullUpControl(pub DioPins);
+maybe_const! {
+    impl [const] Register for DioPullUpControl {
+        const ADDRESS: u16 = 0x0584;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0.into_bits()
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(DioPins::from_bits(bits))
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DioPullDownControl(pub DioPins);
+maybe_const! {
+    impl [const] Register for DioPullDownControl {
+        const ADDRESS: u16 = 0x0585;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0.into_bits()
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(DioPins::from_bits(bits))
+        }
+    }
+}
+/// DIO pin selection of the DIO control registers.
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+pub struct DioPins {
+    #[bits(1)]
+    __: bool,
+    #[bits(1)]
+    pub dio1: bool,
+    #[bits(1)]
+    pub dio2: bool,
+    #[bits(1)]
+    pub dio3: bool,
+    #[bits(4)]
+    __: u8,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct IqPolaritySetup(pub IqPolarity);
+maybe_const! {
+    impl [const] Register for IqPolaritySetup {
+        const ADDRESS: u16 = 0x0736;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0.into_bits()
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(IqPolarity::from_bits(bits))
+        }
+    }
+}
+impl IqPolaritySetup {
+    pub const RESET: Self = Self(IqPolarity::from_bits(0x0D));
+}
+/// Contents of the [`IqPolaritySetup`] register.
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+pub struct IqPolarity {
+    #[bits(2)]
+    __: u8,
+    /// Must be set for standard IQ and cleared for inverted IQ (datasheet 15.4).
+    #[bits(1)]
+    pub standard_iq: bool,
+    #[bits(5)]
+    __: u8,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TxModulation(pub TxModulationConfig);
+maybe_const! {
+    impl [const] Register for TxModulation {
+        const ADDRESS: u16 = 0x0889;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0.into_bits()
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(TxModulationConfig::from_bits(bits))
+        }
+    }
+}
+/// Contents of the [`TxModulation`] register.
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+pub struct TxModulationConfig {
+    #[bits(2)]
+    __: u8,
+    /// Must be cleared for LoRa with 500 kHz bandwidth and set otherwise (datasheet 15.1).
+    #[bits(1)]
+    pub not_bw500: bool,
+    #[bits(5)]
+    __: u8,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TxClampConfig(pub TxClamp);
+maybe_const! {
+    impl [const] Register for TxClampConfig {
+        const ADDRESS: u16 = 0x08D8;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0.into_bits()
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(TxClamp::from_bits(bits))
+        }
+    }
+}
+impl TxClampConfig {
+    pub const RESET: Self = Self(TxClamp::from_bits(0xC8));
+}
+/// Contents of the [`TxClampConfig`] register.
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+pub struct TxClamp {
+    #[bits(1)]
+    __: bool,
+    /// PA clamping threshold, set to `0x0F` to protect the SX1262 PA from antenna mismatch
+    /// (datasheet 15.2).
+    #[bits(4)]
+    pub clamp: u8,
+    #[bits(3)]
+    __: u8,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct OcpConfiguration(pub Ocp);
+maybe_const! {
+    impl [const] Register for OcpConfiguration {
+        const ADDRESS: u16 = 0x08E7;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0.into_bits()
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(Ocp::from_bits(bits))
+        }
     }
+}
+impl OcpConfiguration {
+    /// Reset value of `device`: 60 mA for the SX1261, 140 mA for the others.
     #[inline]
-    fn from_bits(bits: u8) -> Self {
-        Self(bits)
+    pub const fn reset(device: Device) -> Self {
+        Self(Ocp::from_bits(match device {
+            Device::Sx1261 => 0x18,
+            Device::Sx1262 | Device::Sx1268 | Device::Llcc68 => 0x38,
+        }))
+    }
+}
+/// Contents of the [`OcpConfiguration`] register.
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+pub struct Ocp {
+    /// Over current protection level in 2.5 mA steps.
+    #[bits(6)]
+    pub current_limit: u8,
+    #[bits(2)]
+    __: u8,
+}
+
+/// A register address and value, independent of the register type.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RegisterValue {
+    pub address: u16,
+    pub value: u8,
+}
+impl RegisterValue {
+    maybe_const! {
+        #[inline]
+        pub [const] fn new<R: [const] Register>(register: R) -> Self {
+            Self {
+                address: R::ADDRESS,
+                value: register.bits(),
+            }
+        }
     }
 }
 
+/// Reset values of the registers with a documented reset value, for the SX1262, SX1268 and
+/// LLCC68. See [`reset_values`] for the SX1261.
+pub const RESET_VALUES: [RegisterValue; 17] = reset_values(Device::Sx1262);
+
+/// Reset values of the registers of `device` with a documented reset value.
+pub const fn reset_values(device: Device) -> [RegisterValue; 17] {
+    const fn value(address: u16, value: u8) -> RegisterValue {
+        RegisterValue { address, value }
+    }
+    let crc_initial_value = CrcInitialValue::RESET.0.to_be_bytes();
+    let crc_polynomial = CrcPolynomial::RESET.0.to_be_bytes();
+    [
+        value(
+            WhiteningInitialValueMsb::ADDRESS,
+            WhiteningInitialValueMsb::RESET.0,
+        ),
+        value(
+            WhiteningInitialValueLsb::ADDRESS,
+            WhiteningInitialValueLsb::RESET.0,
+        ),
+        value(
+            <CrcInitialValue as Register16>::ADDRESS,
+            crc_initial_value[0],
+        ),
+        value(
+            <CrcInitialValue as Register16>::ADDRESS + 1,
+            crc_initial_value[1],
+        ),
+        value(<CrcPolynomial as Register16>::ADDRESS, crc_polynomial[0]),
+        value(
+            <CrcPolynomial as Register16>::ADDRESS + 1,
+            crc_polynomial[1],
+        ),
+        value(NodeAddress::ADDRESS, NodeAddress::RESET.0),
+        value(BroadcastAddress::ADDRESS, BroadcastAddress::RESET.0),
+        value(
+            IqPolaritySetup::ADDRESS,
+            IqPolaritySetup::RESET.0.into_bits(),
+        ),
+        value(LoraSyncWordMsb::ADDRESS, LoraSyncWordMsb::RESET.0),
+        value(LoraSyncWordLsb::ADDRESS, LoraSyncWordLsb::RESET.0),
+        value(RxGain::ADDRESS, RxGain::RESET.0 as u8),
+        value(TxClampConfig::ADDRESS, TxClampConfig::RESET.0.into_bits()),
+        value(
+            OcpConfiguration::ADDRESS,
+            OcpConfiguration::reset(device).0.into_bits(),
+        ),
+        value(XtaTrim::ADDRESS, XtaTrim::RESET.0),
+        value(XtbTrim::ADDRESS, XtbTrim::RESET.0),
+        value(
+            Dio3OutputVoltageControl::ADDRESS,
+            Dio3OutputVoltageControl::RESET.0 as u8,
+        ),
+    ]
+}
+
+/// # Minimal register write set
+/// The `WriteRegister` commands needed to bring the registers from a `current` configuration
+/// (e.g. [`RESET_VALUES`] after a cold start) to a `desired` one.
+/// Registers whose current value is unknown are always written.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::registers::{
+///     RegisterValue, RegisterWrites, RESET_VALUES, LoraSyncWordMsb, LoraSyncWordLsb, RxGain, RxGainSetting,
+///     RxGainRetention0,
+/// };
+///
+/// let writes: RegisterWrites<4> = RegisterWrites::diff(
+///     [
+///         RegisterValue::new(LoraSyncWordMsb(0x34)),
+///         RegisterValue::new(LoraSyncWordLsb(0x24)),
+///         RegisterValue::new(RxGain(RxGainSetting::Boosted)),
+///         RegisterValue::new(RxGainRetention0(0x01)),
+///     ],
+///     &RESET_VALUES,
+/// );
+/// assert_eq!(writes.len, 3);
+/// assert_eq!(writes.writes[0].tx_buf, [0x0D, 0x07, 0x40, 0x34]);
+/// assert_eq!(writes.writes[1].tx_buf, [0x0D, 0x08, 0xAC, 0x96]);
+/// assert_eq!(writes.writes[2].tx_buf, [0x0D, 0x02, 0x9F, 0x01]);
+/// assert_eq!(writes.descriptors().count(), 3);
+/// ```
+pub struct RegisterWrites<const N: usize> {
+    pub writes: [WriteRegister; N],
+    pub len: usize,
+}
+impl<const N: usize> RegisterWrites<N> {
+    #[allow(clippy::indexing_slicing)]
+    pub const fn diff(desired: [RegisterValue; N], current: &[RegisterValue]) -> Self {
+        let mut writes = [const {
+            WriteRegister::from_value(RegisterValue {
+                address: 0,
+                value: 0,
+            })
+        }; N];
+        let mut len: usize = 0;
+        let mut i: usize = 0;
+        while i < N {
+            let mut unchanged = false;
+            let mut j: usize = 0;
+            while j < current.len() {
+                if current[j].address == desired[i].address {
+                    unchanged = current[j].value == desired[i].value;
+                    break;
+                }
+                j += 1;
+            }
+            if !unchanged {
+                writes[len] = WriteRegister::from_value(desired[i]);
+                len += 1;
+            }
+            i += 1;
+        }
+        Self { writes, len }
+    }
+    pub fn descriptors(&self) -> impl Iterator<Item = SpiDescriptor> + '_ {
+        self.writes[..self.len]
+            .iter()
+            .map(WriteRegister::descriptor)
+    }
+}
+
+/// Register at an arbitrary address, for registers without a named type.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers::RawRegister, commands::WriteRegister};
+///
+/// let write_register = WriteRegister::new(RawRegister::<0x0736>(0x0D));
+/// assert_eq!(write_register.tx_buf, [0x0D, 0x07, 0x36, 0x0D]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RawRegister<const A: u16>(pub u8);
+maybe_const! {
+    impl<const A: u16> [const] Register for RawRegister<A> {
+        const ADDRESS: u16 = A;
+        #[inline]
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        #[inline]
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+/// Declares a register type and its `Register` implementation.
+///
+/// - `struct Name: ADDRESS;` declares a plain `u8` register.
+/// - `struct Name(Inner): ADDRESS;` wraps a type with const `into_bits`/`from_bits` methods.
+/// - `struct Name(Inner): ADDRESS { ... }` also declares `Inner` as a `u8` bitfield with the
+///   given fields.
+///
+/// With the `nightly` feature the expansion contains an `impl const`, so the calling crate needs
+/// `#![feature(const_trait_impl)]`.
+///
+/// ## Example
+/// ```
+/// #![cfg_attr(feature = "nightly", feature(const_trait_impl))]
+/// use sx126x_spi_buffers::{declare_register, commands::WriteRegister};
+///
+/// declare_register! {
+///     pub struct TxModulation: 0x0889;
+/// }
+/// declare_register! {
+///     pub struct PaClamp(PaClampBits): 0x08D8 {
+///         #[bits(1)]
+///         __: bool,
+///         #[bits(4)]
+///         pub clamp: u8,
+///         #[bits(3)]
+///         __: u8,
+///     }
+/// }
+///
+/// # #[cfg(feature = "nightly")] {
+/// const WRITE_TX_MODULATION: WriteRegister = WriteRegister::new(TxModulation(0x04));
+/// assert_eq!(WRITE_TX_MODULATION.tx_buf, [0x0D, 0x08, 0x89, 0x04]);
+///
+/// const WRITE_PA_CLAMP: WriteRegister =
+///     WriteRegister::new(PaClamp(PaClampBits::new().with_clamp(0x0F)));
+/// assert_eq!(WRITE_PA_CLAMP.tx_buf, [0x0D, 0x08, 0xD8, 0x1E]);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! declare_register {
+    ($(#[$meta:meta])* $vis:vis struct $name:ident: $address:literal;) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+        $vis struct $name(pub u8);
+        $crate::__impl_register! {
+            $name {
+                const ADDRESS: u16 = $address;
+                #[inline]
+                fn bits(&self) -> u8 {
+                    self.0
+                }
+                #[inline]
+                fn from_bits(bits: u8) -> Self {
+                    Self(bits)
+                }
+            }
+        }
+    };
+    ($(#[$meta:meta])* $vis:vis struct $name:ident($inner:ty): $address:literal;) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+        $vis struct $name(pub $inner);
+        $crate::__impl_register! {
+            $name {
+                const ADDRESS: u16 = $address;
+                #[inline]
+                fn bits(&self) -> u8 {
+                    self.0.into_bits()
+                }
+                #[inline]
+                fn from_bits(bits: u8) -> Self {
+                    Self(<$inner>::from_bits(bits))
+                }
+            }
+        }
+    };
+    ($(#[$meta:meta])* $vis:vis struct $name:ident($inner:ident): $address:literal { $($fields:tt)* }) => {
+        #[$crate::registers::__bitfield(u8)]
+        #[derive(PartialEq, Eq)]
+        $vis struct $inner {
+            $($fields)*
+        }
+        $crate::declare_register! {
+            $(#[$meta])* $vis struct $name($inner): $address;
+        }
+    };
+}
+/// `Register` implementation of `declare_register!`, `const` with the `nightly` feature. Defined
+/// twice because the crate-local `maybe_const!` is not available to calling crates.
+#[cfg(feature = "nightly")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_register {
+    ($name:ident { $($body:tt)* }) => {
+        impl const $crate::registers::Register for $name {
+            $($body)*
+        }
+    };
+}
+#[cfg(not(feature = "nightly"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_register {
+    ($name:ident { $($body:tt)* }) => {
+        impl $crate::registers::Register for $name {
+            $($body)*
+        }
+    };
+}
+#[doc(hidden)]
+pub use bitfield_struct::bitfield as __bitfield;
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::commands::{ReadRegisters, WriteRegister, WriteRegisters};
+    use crate::commands::{ReadRegister, ReadRegisters, WriteRegister, WriteRegisters};
 
+    #[cfg(feature = "nightly")]
     #[test]
     fn test_write_lora_sync_word() {
         let write_registers: WriteRegisters<5> =
@@ -152,10 +1226,198 @@ mod tests {
         assert_eq!(write_register.tx_buf, [0x0D, 0x02, 0x9F, 0x01]);
     }
 
+    #[cfg(feature = "nightly")]
     #[test]
     fn test_write_rx_gain_retention_1_2() {
         let write_registers: WriteRegisters<5> =
             WriteRegisters::<5>::new::<RxGainRetention1>([0x08, 0xAC]);
         assert_eq!(write_registers.tx_buf, [0x0D, 0x02, 0xA0, 0x08, 0xAC]);
     }
+
+    #[test]
+    fn test_write_rtc_control() {
+        let write_register: WriteRegister = WriteRegister::new(RtcControl(0x00));
+        assert_eq!(write_register.tx_buf, [0x0D, 0x09, 0x02, 0x00]);
+    }
+
+    #[cfg(feature = "nightly")]
+    #[test]
+    fn test_xtal_trim_capacitance() {
+        assert_eq!(XtaTrim::from_capacitance(1130), XtaTrim(0x00));
+        assert_eq!(XtaTrim::from_capacitance(1600), XtaTrim(0x0A));
+        assert_eq!(XtbTrim::from_capacitance(5000), XtbTrim(0x2F));
+        assert_eq!(XtbTrim(0x05).capacitance(), 1365);
+        assert_eq!(XtaTrim(0xFF).capacitance(), 3339);
+
+        let write_registers: WriteRegisters<5> = WriteRegisters::<5>::new::<XtaTrim>([0x12, 0x12]);
+        assert_eq!(write_registers.tx_buf, [0x0D, 0x09, 0x11, 0x12, 0x12]);
+    }
+
+    #[test]
+    fn test_dio3_output_voltage_control() {
+        let write_register: WriteRegister =
+            WriteRegister::new(Dio3OutputVoltageControl(TcxoVoltage::V1_8));
+        assert_eq!(write_register.tx_buf, [0x0D, 0x09, 0x20, 0x02]);
+
+        let mut read_register: ReadRegister<Dio3OutputVoltageControl> = ReadRegister::new();
+        read_register.rx_buf[4] = 0x07;
+        assert_eq!(
+            read_register.register(),
+            Dio3OutputVoltageControl(TcxoVoltage::V3_3)
+        );
+    }
+
+    #[test]
+    fn test_event_mask() {
+        let write_register: WriteRegister =
+            WriteRegister::new(EventMask(Events::new().with_timeout(true)));
+        assert_eq!(write_register.tx_buf, [0x0D, 0x09, 0x44, 0x02]);
+
+        let mut read_register: ReadRegister<EventMask> = ReadRegister::new();
+        read_register.rx_buf[4] = 0x82;
+        assert!(read_register.register().0.timeout());
+        assert_eq!(read_register.register().0.into_bits(), 0x82);
+    }
+
+    #[test]
+    fn test_raw_register() {
+        let mut read_register: ReadRegister<RawRegister<0x08E7>> = ReadRegister::new();
+        assert_eq!(read_register.tx_buf, [0x1D, 0x08, 0xE7, 0, 0]);
+        read_register.rx_buf[4] = 0x18;
+        assert_eq!(read_register.register(), RawRegister::<0x08E7>(0x18));
+    }
+
+    #[test]
+    fn test_write_register_tuple() {
+        let write_registers: WriteRegisters<6> = WriteRegisters::<6>::from_block((
+            RxGainRetention0(0x01),
+            RxGainRetention1(0x08),
+            RxGainRetention2(0xAC),
+        ));
+        assert_eq!(write_registers.tx_buf, [0x0D, 0x02, 0x9F, 0x01, 0x08, 0xAC]);
+    }
+
+    #[cfg(feature = "nightly")]
+    #[test]
+    fn test_read_register_tuple() {
+        let mut read_registers: ReadRegisters<8> = ReadRegisters::<8>::for_block::<(
+            RandomNumberGen0,
+            RandomNumberGen1,
+            RandomNumberGen2,
+            RandomNumberGen3,
+        )>();
+        assert_eq!(read_registers.tx_buf, [0x1D, 0x08, 0x19, 0, 0, 0, 0, 0]);
+        read_registers.rx_buf[4..8].copy_from_slice(&[1, 2, 3, 4]);
+        let values: (
+            RandomNumberGen0,
+            RandomNumberGen1,
+            RandomNumberGen2,
+            RandomNumberGen3,
+        ) = read_registers.block();
+        assert_eq!(
+            values,
+            (
+                RandomNumberGen0(1),
+                RandomNumberGen1(2),
+                RandomNumberGen2(3),
+                RandomNumberGen3(4)
+            )
+        );
+    }
+
+    #[test]
+    fn test_register_bitfields() {
+        let write_register: WriteRegister =
+            WriteRegister::new(OcpConfiguration(Ocp::new().with_current_limit(0x38)));
+        assert_eq!(write_register.tx_buf, [0x0D, 0x08, 0xE7, 0x38]);
+
+        let write_register: WriteRegister =
+            WriteRegister::new(TxClampConfig(TxClamp::from_bits(0xC8).with_clamp(0x0F)));
+        assert_eq!(write_register.tx_buf, [0x0D, 0x08, 0xD8, 0xDE]);
+
+        let mut read_register: ReadRegister<IqPolaritySetup> = ReadRegister::new();
+        read_register.rx_buf[4] = 0x0D;
+        assert!(read_register.register().0.standard_iq());
+        assert_eq!(
+            read_register
+                .register()
+                .0
+                .with_standard_iq(false)
+                .into_bits(),
+            0x09
+        );
+
+        let write_register: WriteRegister =
+            WriteRegister::new(TxModulation(TxModulationConfig::new().with_not_bw500(true)));
+        assert_eq!(write_register.tx_buf, [0x0D, 0x08, 0x89, 0x04]);
+    }
+
+    #[test]
+    fn test_dio_control() {
+        let write_register: WriteRegister =
+            WriteRegister::new(DioOutputEnable(DioPins::new().with_dio3(true)));
+        assert_eq!(write_register.tx_buf, [0x0D, 0x05, 0x80, 0x08]);
+
+        let write_registers: WriteRegisters<6> = WriteRegisters::<6>::from_block((
+            DioInputEnable(DioPins::new().with_dio1(true)),
+            DioPullUpControl(DioPins::new().with_dio2(true)),
+            DioPullDownControl(DioPins::new().with_dio1(true).with_dio3(true)),
+        ));
+        assert_eq!(write_registers.tx_buf, [0x0D, 0x05, 0x83, 0x02, 0x04, 0x0A]);
+    }
+
+    #[cfg(feature = "nightly")]
+    #[test]
+    fn test_gfsk_blocks() {
+        let write_crc: WriteRegisters<5> = WriteRegisters::<5>::from_block(CrcPolynomial(0x8005));
+        assert_eq!(write_crc.tx_buf, [0x0D, 0x06, 0xBE, 0x80, 0x05]);
+
+        let mut read_sync_word: ReadRegisters<12> =
+            ReadRegisters::<12>::for_block::<GfskSyncWord>();
+        assert_eq!(read_sync_word.tx_buf[..3], [0x1D, 0x06, 0xC0]);
+        read_sync_word.rx_buf[4..12].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(
+            read_sync_word.block::<GfskSyncWord>(),
+            GfskSyncWord([1, 2, 3, 4, 5, 6, 7, 8])
+        );
+    }
+
+    #[test]
+    fn test_lr_fhss_registers() {
+        let write_sync_word: WriteRegisters<7> =
+            WriteRegisters::<7>::from_block(LrFhssSyncWord::LORAWAN);
+        assert_eq!(
+            write_sync_word.tx_buf,
+            [0x0D, 0x06, 0xC0, 0x2C, 0x0F, 0x79, 0x95]
+        );
+
+        let write_control: WriteRegisters<6> = WriteRegisters::<6>::from_block((
+            LrFhssControl::HOPPING_ENABLED,
+            LrFhssPacketLength(32),
+            LrFhssNumHops(18),
+        ));
+        assert_eq!(write_control.tx_buf, [0x0D, 0x03, 0x85, 0x0C, 32, 18]);
+    }
+
+    #[test]
+    fn test_reset_values() {
+        for (i, reset) in RESET_VALUES.iter().enumerate() {
+            assert!(
+                RESET_VALUES[i + 1..]
+                    .iter()
+                    .all(|other| other.address != reset.address)
+            );
+        }
+        assert!(RESET_VALUES.contains(&RegisterValue {
+            address: 0x06BD,
+            value: 0x0F,
+        }));
+
+        let sx1261 = reset_values(Device::Sx1261);
+        let ocp = sx1261
+            .iter()
+            .find(|reset| reset.address == OcpConfiguration::ADDRESS)
+            .unwrap();
+        assert_eq!(ocp.value, 0x18);
+    }
 }
@@ -0,0 +1,736 @@
+//! Multi-command sequences for common configuration steps.
+#![allow(clippy::new_without_default)]
+
+use super::attributes::{AttributedDescriptor, TransferAttributes};
+use super::commands::{
+    Calibrate, CalibrateImage, CalibrationSetting, ClearDeviceErrors, ClearIrqStatus, Command,
+    GetDeviceErrors, GetIrqStatus, GetRxBufferStatus, GetStatus, ImageCalibrationBand, Irq,
+    MaxReadBuffer, MaxWriteBuffer, OpError, ReadBuffer, ReadRegisters, Rmw, SetDio3AsTcxoCtrl,
+    SetRegulatorMode, SetRx, SetSleep, SetStandby, SetTx, SpiDescriptor, StdbyConfig, TcxoVoltage,
+    WriteBuffer, WriteRegister, WriteRegisters,
+};
+use super::config::RadioInit;
+use super::registers::{
+    IqPolaritySetup, LoraSyncWord, Register, RegisterBlock, RegisterValue, RxGain,
+    RxGainRetention0, RxGainSetting, TxModulation, WhiteningInitialValueLsb,
+    WhiteningInitialValueMsb,
+};
+
+/// # RxBoostedGain sequence
+/// Sets the RX boosted gain and adds the `RxGain` register to the warm start retention list,
+/// so the boosted gain survives a warm start sleep.
+///
+/// Writes `0x08AC = 0x96` followed by `0x029F = 0x01`, `0x02A0 = 0x08`, `0x02A1 = 0xAC`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::sequences::RxBoostedGain;
+///
+/// static RX_BOOSTED_GAIN: RxBoostedGain = RxBoostedGain::new();
+/// assert_eq!(RX_BOOSTED_GAIN.rx_gain.tx_buf, [0x0D, 0x08, 0xAC, 0x96]);
+/// assert_eq!(RX_BOOSTED_GAIN.retention.tx_buf, [0x0D, 0x02, 0x9F, 0x01, 0x08, 0xAC]);
+/// assert_eq!(RX_BOOSTED_GAIN.descriptors()[1].transfer_length, 6);
+/// ```
+pub struct RxBoostedGain {
+    pub rx_gain: WriteRegister,
+    pub retention: WriteRegisters<6>,
+}
+impl RxBoostedGain {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            rx_gain: WriteRegister::at_address(RxGain::ADDRESS, RxGainSetting::Boosted as u8),
+            retention: WriteRegisters::<6>::from_slice::<RxGainRetention0>(&[
+                0x01,
+                (RxGain::ADDRESS >> 8) as u8,
+                RxGain::ADDRESS as u8,
+            ]),
+        }
+    }
+    #[inline]
+    pub const fn descriptors(&self) -> [SpiDescriptor; 2] {
+        [self.rx_gain.descriptor(), self.retention.descriptor()]
+    }
+}
+
+/// # CheckDeviceErrors sequence
+/// Reads the device errors and clears them in the next transfer, so each error is reported once.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{commands::OpErrorFlag, sequences::CheckDeviceErrors};
+///
+/// let mut check_device_errors = CheckDeviceErrors::new();
+/// assert_eq!(check_device_errors.get_device_errors.tx_buf, [0x17, 0, 0, 0]);
+/// assert_eq!(check_device_errors.clear_device_errors.tx_buf, [0x07, 0, 0]);
+/// assert_eq!(check_device_errors.descriptors()[1].transfer_length, 3);
+///
+/// check_device_errors.get_device_errors.rx_buf[3] = 0x20;
+/// assert!(check_device_errors.op_error().is_only_xosc_start_err());
+/// assert_eq!(check_device_errors.op_error().iter().next(), Some(OpErrorFlag::XoscStart));
+/// ```
+pub struct CheckDeviceErrors {
+    pub get_device_errors: GetDeviceErrors,
+    pub clear_device_errors: ClearDeviceErrors,
+}
+impl CheckDeviceErrors {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            get_device_errors: GetDeviceErrors::new(),
+            clear_device_errors: ClearDeviceErrors::new(),
+        }
+    }
+    #[inline]
+    pub const fn descriptors(&self) -> [SpiDescriptor; 2] {
+        [
+            self.get_device_errors.descriptor(),
+            self.clear_device_errors.descriptor(),
+        ]
+    }
+    /// Errors read before clearing.
+    #[inline]
+    pub const fn op_error(&self) -> OpError {
+        self.get_device_errors.op_error()
+    }
+}
+
+/// # WakeCalibration sequence
+/// Recalibrates the radio after a cold start sleep or a power-on reset, which lose the
+/// calibration: `SetStandby(STDBY_RC)`, `SetRegulatorMode`, `SetDio3AsTcxoCtrl` on boards with a
+/// TCXO, `Calibrate` of all blocks and `CalibrateImage` for the operating band.
+///
+/// The regulator and the TCXO are configured before `Calibrate`, which calibrates with them: a
+/// calibration without the TCXO running fails with `XoscStartErr`.
+///
+/// BUSY stays high during the calibration, up to [`CALIBRATE_TIME_US`](Self::CALIBRATE_TIME_US)
+/// after `calibrate`: wait for BUSY low before transferring `calibrate_image`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{
+///     commands::{ImageCalibrationBand, TcxoVoltage},
+///     sequences::WakeCalibration,
+/// };
+///
+/// static WAKE_CALIBRATION: WakeCalibration = WakeCalibration::new(
+///     ImageCalibrationBand::Mhz863_870,
+///     true,
+///     Some((TcxoVoltage::V1_8, 320)),
+/// );
+/// assert_eq!(WAKE_CALIBRATION.set_standby.tx_buf, [0x80, 0x00]);
+/// assert_eq!(WAKE_CALIBRATION.set_regulator_mode.tx_buf, [0x96, 0x01]);
+/// assert_eq!(WAKE_CALIBRATION.calibrate.tx_buf, [0x89, 0x7F]);
+/// assert_eq!(WAKE_CALIBRATION.calibrate_image.tx_buf, [0x98, 0xD7, 0xDB]);
+///
+/// let opcodes: Vec<u8> = WAKE_CALIBRATION
+///     .descriptors()
+///     .map(|descriptor| unsafe { *descriptor.tx_buf_ptr })
+///     .collect();
+/// assert_eq!(opcodes, [0x80, 0x96, 0x97, 0x89, 0x98]);
+///
+/// let without_tcxo = WakeCalibration::new(ImageCalibrationBand::Mhz863_870, true, None);
+/// assert_eq!(without_tcxo.descriptors().count(), 4);
+/// ```
+pub struct WakeCalibration {
+    pub set_standby: SetStandby,
+    pub set_regulator_mode: SetRegulatorMode,
+    pub set_dio3_as_tcxo_ctrl: Option<SetDio3AsTcxoCtrl>,
+    pub calibrate: Calibrate,
+    pub calibrate_image: CalibrateImage,
+}
+impl WakeCalibration {
+    /// Longest duration of the calibration of all blocks, in us.
+    pub const CALIBRATE_TIME_US: u32 = 3_500;
+
+    /// Calibration for `band`, with the DC-DC regulator if `dc_dc` and the TCXO voltage and
+    /// startup delay of `tcxo`, as in [`RadioConfig::tcxo`](super::config::RadioConfig::tcxo).
+    #[inline]
+    pub const fn new(
+        band: ImageCalibrationBand,
+        dc_dc: bool,
+        tcxo: Option<(TcxoVoltage, u32)>,
+    ) -> Self {
+        Self {
+            set_standby: SetStandby::new(StdbyConfig::StdbyRc),
+            set_regulator_mode: SetRegulatorMode::new(dc_dc),
+            set_dio3_as_tcxo_ctrl: match tcxo {
+                Some((voltage, delay)) => Some(SetDio3AsTcxoCtrl::new(voltage, delay)),
+                None => None,
+            },
+            calibrate: Calibrate::new(CalibrationSetting::from_bits(0x7F)),
+            calibrate_image: CalibrateImage::for_band(band),
+        }
+    }
+    /// Descriptors in transfer order.
+    pub fn descriptors(&self) -> impl Iterator<Item = SpiDescriptor> + '_ {
+        [
+            Some(self.set_standby.descriptor()),
+            Some(self.set_regulator_mode.descriptor()),
+            self.set_dio3_as_tcxo_ctrl
+                .as_ref()
+                .map(SetDio3AsTcxoCtrl::descriptor),
+            Some(self.calibrate.descriptor()),
+            Some(self.calibrate_image.descriptor()),
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+/// # LoRa network type
+/// Selects the LoRa sync word, so the MSB/LSB bytes of the sync word don't have to be spelled
+/// out by hand.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{sequences::LoraNetwork, commands::WriteRegisters};
+///
+/// const WRITE_PUBLIC_SYNC_WORD: WriteRegisters<5> = LoraNetwork::Public.write_sync_word();
+/// assert_eq!(WRITE_PUBLIC_SYNC_WORD.tx_buf, [0x0D, 0x07, 0x40, 0x34, 0x44]);
+///
+/// const WRITE_PRIVATE_SYNC_WORD: WriteRegisters<5> = LoraNetwork::Private.write_sync_word();
+/// assert_eq!(WRITE_PRIVATE_SYNC_WORD.tx_buf, [0x0D, 0x07, 0x40, 0x14, 0x24]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LoraNetwork {
+    Public,
+    Private,
+}
+impl LoraNetwork {
+    #[inline]
+    pub const fn sync_word(self) -> LoraSyncWord {
+        match self {
+            LoraNetwork::Public => LoraSyncWord::PUBLIC,
+            LoraNetwork::Private => LoraSyncWord::PRIVATE,
+        }
+    }
+    #[inline]
+    pub const fn write_sync_word(self) -> WriteRegisters<5> {
+        WriteRegisters::<5>::at_address(LoraSyncWord::ADDRESS, &self.sync_word().0.to_be_bytes())
+    }
+}
+
+/// # SleepCycle
+/// Enters sleep and wakes the device up again:
+/// 1. `set_sleep`, then wait [`SLEEP_US`](Self::SLEEP_US) before waking the device.
+/// 2. `wake`, a `GetStatus` whose NSS falling edge wakes the device. The device does not
+///    answer it; wait [`wake_us()`](Self::wake_us) or for BUSY low.
+/// 3. Optionally the configuration replay of [`wake_descriptors()`](Self::wake_descriptors).
+///
+/// `steps()` gives the first two steps with their delays as
+/// [`post_delay_us`](TransferAttributes::post_delay_us), for executors handling the delays.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{config::RadioConfig, sequences::SleepCycle};
+///
+/// static SLEEP_CYCLE: SleepCycle = SleepCycle::new(true);
+/// assert_eq!(SLEEP_CYCLE.set_sleep.tx_buf, [0x84, 0x04]);
+/// assert_eq!(SLEEP_CYCLE.wake.tx_buf, [0xC0, 0x00]);
+///
+/// let [sleep, wake] = SLEEP_CYCLE.steps();
+/// assert_eq!(sleep.attributes.post_delay_us, SleepCycle::SLEEP_US);
+/// assert_eq!(wake.attributes.post_delay_us, SleepCycle::WAKE_WARM_US);
+///
+/// let init = RadioConfig::new(0x3640_0000).init();
+/// assert_eq!(SLEEP_CYCLE.wake_descriptors(Some(&init)).count(), 2);
+/// assert_eq!(SleepCycle::new(false).wake_descriptors(Some(&init)).count(), 15);
+/// ```
+pub struct SleepCycle {
+    pub set_sleep: SetSleep,
+    pub wake: GetStatus,
+    warm_start: bool,
+}
+impl SleepCycle {
+    /// Time from `SetSleep` to the sleep mode, before which the device must not be woken up.
+    pub const SLEEP_US: u32 = 500;
+    /// Wake-up time from cold start sleep to `STDBY_RC`.
+    pub const WAKE_COLD_US: u32 = 3_500;
+    /// Wake-up time from warm start sleep to `STDBY_RC`.
+    pub const WAKE_WARM_US: u32 = 340;
+
+    /// Sleep cycle with warm start if `warm_start`, retaining the configuration.
+    #[inline]
+    pub const fn new(warm_start: bool) -> Self {
+        Self {
+            set_sleep: SetSleep::new(warm_start),
+            wake: GetStatus::new(),
+            warm_start,
+        }
+    }
+    #[inline]
+    pub const fn is_warm_start(&self) -> bool {
+        self.warm_start
+    }
+    /// Wake-up time of the sleep mode.
+    #[inline]
+    pub const fn wake_us(&self) -> u32 {
+        if self.warm_start {
+            Self::WAKE_WARM_US
+        } else {
+            Self::WAKE_COLD_US
+        }
+    }
+    #[inline]
+    pub const fn steps(&self) -> [AttributedDescriptor; 2] {
+        [
+            AttributedDescriptor::new(
+                self.set_sleep.descriptor(),
+                TransferAttributes::new().with_post_delay_us(Self::SLEEP_US),
+            ),
+            AttributedDescriptor::new(
+                self.wake.descriptor(),
+                TransferAttributes::new().with_post_delay_us(self.wake_us()),
+            ),
+        ]
+    }
+    /// `wake` followed by the restart descriptors of `init` for the sleep mode, see
+    /// [`RadioInit::restart_descriptors`].
+    pub fn wake_descriptors<'a>(
+        &'a self,
+        init: Option<&'a RadioInit>,
+    ) -> impl Iterator<Item = SpiDescriptor> + 'a {
+        core::iter::once(self.wake.descriptor()).chain(
+            init.into_iter()
+                .flat_map(|init| init.restart_descriptors(self.warm_start)),
+        )
+    }
+}
+
+/// Registers set by the crate which warm start sleep does not retain, unless added to the
+/// retention list like by [`RxBoostedGain`]: `RxGain` (datasheet 9.6), and `TxModulation` and
+/// `IqPolaritySetup` of the workarounds of datasheet 15.1 and 15.4.
+pub const NOT_RETAINED: [u16; 3] = [
+    RxGain::ADDRESS,
+    TxModulation::ADDRESS,
+    IqPolaritySetup::ADDRESS,
+];
+
+/// # SleepRetention
+/// Saves registers which are not retained in warm start sleep and restores them after wake-up.
+///
+/// 1. Before `SetSleep`, transfer `save_descriptors()` and call `update()`.
+/// 2. After wake-up, transfer `restore_descriptors()`.
+///
+/// [`SleepRetention::DEFAULT`] saves the registers of [`NOT_RETAINED`]; `new()` takes other
+/// addresses, e.g. of registers set by the application.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers::{Register, RxGain, TxClampConfig}, sequences::SleepRetention};
+///
+/// static RETENTION: SleepRetention<3> = SleepRetention::DEFAULT;
+/// assert_eq!(RETENTION.save_descriptors().len(), 3);
+///
+/// let mut retention: SleepRetention<2> = SleepRetention::new([RxGain::ADDRESS, TxClampConfig::ADDRESS]);
+/// assert_eq!(retention.save[0].tx_buf, [0x1D, 0x08, 0xAC, 0, 0]);
+/// assert_eq!(retention.save_descriptors().len(), 2);
+///
+/// retention.save[0].rx_buf[4] = 0x96;
+/// retention.save[1].rx_buf[4] = 0xDE;
+/// retention.update();
+/// assert_eq!(retention.restore[0].tx_buf, [0x0D, 0x08, 0xAC, 0x96]);
+/// assert_eq!(retention.restore[1].tx_buf, [0x0D, 0x08, 0xD8, 0xDE]);
+/// ```
+pub struct SleepRetention<const N: usize> {
+    pub save: [ReadRegisters<5>; N],
+    pub restore: [WriteRegister; N],
+}
+impl SleepRetention<3> {
+    /// Saves the registers of [`NOT_RETAINED`].
+    pub const DEFAULT: Self = Self::new(NOT_RETAINED);
+}
+impl<const N: usize> SleepRetention<N> {
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn new(addresses: [u16; N]) -> Self {
+        let mut save = [const { ReadRegisters::<5>::at_address(0) }; N];
+        let mut restore = [const {
+            WriteRegister::from_value(RegisterValue {
+                address: 0,
+                value: 0,
+            })
+        }; N];
+        let mut i: usize = 0;
+        while i < N {
+            save[i] = ReadRegisters::<5>::at_address(addresses[i]);
+            restore[i] = WriteRegister::from_value(RegisterValue {
+                address: addresses[i],
+                value: 0,
+            });
+            i += 1;
+        }
+        Self { save, restore }
+    }
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn save_descriptors(&self) -> [SpiDescriptor; N] {
+        let mut descriptors = [SpiDescriptor::EMPTY; N];
+        let mut i: usize = 0;
+        while i < N {
+            descriptors[i] = self.save[i].descriptor();
+            i += 1;
+        }
+        descriptors
+    }
+    /// Prepares `restore` from the values read by `save`.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn update(&mut self) {
+        let mut i: usize = 0;
+        while i < N {
+            self.restore[i].tx_buf[3] = self.save[i].rx_buf[4];
+            i += 1;
+        }
+    }
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn restore_descriptors(&self) -> [SpiDescriptor; N] {
+        let mut descriptors = [SpiDescriptor::EMPTY; N];
+        let mut i: usize = 0;
+        while i < N {
+            descriptors[i] = self.restore[i].descriptor();
+            i += 1;
+        }
+        descriptors
+    }
+}
+
+/// # WhiteningSeed sequence
+/// Sets the 9 bit GFSK whitening initial value. Its MSB shares a register with other settings,
+/// so it is read-modify-written:
+/// 1. Transfer `read_descriptor()` and call `update()`.
+/// 2. Transfer `write_descriptors()`.
+///
+/// The whitening is the PN9 sequence `x^9 + x^5 + 1` of the SX127x, so a seed of
+/// [`WhiteningSeed::SX127X`] interoperates with SX127x FSK modems.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::sequences::WhiteningSeed;
+///
+/// let mut seed: WhiteningSeed = WhiteningSeed::new(0x0100);
+/// assert_eq!(seed.msb.read.tx_buf, [0x1D, 0x06, 0xB8, 0, 0]);
+/// seed.msb.read.rx_buf[4] = 0xF0;
+/// seed.update();
+/// assert_eq!(seed.msb.write.tx_buf, [0x0D, 0x06, 0xB8, 0xF1]);
+/// assert_eq!(seed.lsb.tx_buf, [0x0D, 0x06, 0xB9, 0x00]);
+/// assert_eq!(seed.seed(), 0x0100);
+/// ```
+pub struct WhiteningSeed {
+    pub msb: Rmw<WhiteningInitialValueMsb>,
+    pub lsb: WriteRegister,
+}
+impl WhiteningSeed {
+    /// Seed of the SX127x whitening, and the reset value.
+    pub const SX127X: u16 = 0x01FF;
+
+    /// Only the 9 low bits of `seed` are used.
+    #[inline]
+    pub const fn new(seed: u16) -> Self {
+        Self {
+            msb: Rmw::new(0x01, (seed >> 8) as u8 & 0x01),
+            lsb: WriteRegister::at_address(WhiteningInitialValueLsb::ADDRESS, seed as u8),
+        }
+    }
+    /// Seed written by `write_descriptors()`.
+    #[inline]
+    pub const fn seed(&self) -> u16 {
+        (((self.msb.write.tx_buf[3] & 0x01) as u16) << 8) | self.lsb.tx_buf[3] as u16
+    }
+    #[inline]
+    pub const fn read_descriptor(&self) -> SpiDescriptor {
+        self.msb.read_descriptor()
+    }
+    /// Prepares the MSB write, preserving the other bits read.
+    #[inline]
+    pub const fn update(&mut self) {
+        self.msb.update();
+    }
+    #[inline]
+    pub const fn write_descriptors(&self) -> [SpiDescriptor; 2] {
+        [self.msb.write_descriptor(), self.lsb.descriptor()]
+    }
+}
+
+/// # DescriptorTable
+/// Descriptors of statically allocated commands, which can be held in a `static`, e.g. by
+/// [`command_sequence!`](crate::command_sequence).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DescriptorTable<const N: usize>([SpiDescriptor; N]);
+// SAFETY: like `Send` for `SpiDescriptor`, the pointers are only dereferenced by the DMA or in
+// unsafe code of the user.
+unsafe impl<const N: usize> Sync for DescriptorTable<N> {}
+impl<const N: usize> DescriptorTable<N> {
+    #[inline]
+    pub const fn new(descriptors: [SpiDescriptor; N]) -> Self {
+        Self(descriptors)
+    }
+    #[inline]
+    pub const fn descriptors(&self) -> &[SpiDescriptor; N] {
+        &self.0
+    }
+}
+impl<const N: usize> core::ops::Deref for DescriptorTable<N> {
+    type Target = [SpiDescriptor; N];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Declares a `static` for each command and a `static` [`DescriptorTable`] of their
+/// descriptors, in order.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{
+///     command_sequence,
+///     commands::{PacketType, SetPacketType, SetStandby, StdbyConfig, WriteBuffer},
+/// };
+///
+/// command_sequence! {
+///     static PREPARE_TX = {
+///         SET_STANDBY: SetStandby = SetStandby::new(StdbyConfig::StdbyRc);
+///         SET_PACKET_TYPE: SetPacketType = SetPacketType::new(PacketType::Lora);
+///         WRITE_BUFFER: WriteBuffer<5> = WriteBuffer::<5>::from_slice(0, b"abc");
+///     };
+/// }
+///
+/// assert_eq!(PREPARE_TX.len(), 3);
+/// assert_eq!(PREPARE_TX[0], SET_STANDBY.descriptor());
+/// assert_eq!(PREPARE_TX[2].transfer_length, 5);
+/// ```
+#[macro_export]
+macro_rules! command_sequence {
+    ($(
+        $vis:vis static $table:ident = {
+            $($name:ident: $ty:ty = $init:expr;)+
+        };
+    )+) => {$(
+        $($vis static $name: $ty = $init;)+
+        $vis static $table: $crate::sequences::DescriptorTable<
+            { [$(stringify!($name)),+].len() },
+        > = $crate::sequences::DescriptorTable::new([$($name.descriptor()),+]);
+    )+};
+}
+
+/// Tuples of up to 12 [`Command`]s, transferred in order by a [`Sequence`].
+pub trait CommandTuple {
+    /// Number of commands.
+    const COUNT: usize;
+    /// Sum of the buffer sizes of the commands in bytes.
+    const LEN: usize;
+    /// RAM used by the TX and RX buffers of the commands in bytes.
+    const FOOTPRINT: usize;
+    /// Descriptor of the command at `index`.
+    fn descriptor(&self, index: usize) -> Option<SpiDescriptor>;
+}
+macro_rules! impl_command_tuple {
+    ($count:literal; $(($command:ident, $index:tt)),+) => {
+        impl<$($command: Command),+> CommandTuple for ($($command,)+) {
+            const COUNT: usize = $count;
+            const LEN: usize = 0 $(+ $command::LEN)+;
+            const FOOTPRINT: usize = 0 $(+ $command::FOOTPRINT)+;
+            #[inline]
+            fn descriptor(&self, index: usize) -> Option<SpiDescriptor> {
+                match index {
+                    $($index => Some(self.$index.descriptor()),)+
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+impl_command_tuple!(1; (A, 0));
+impl_command_tuple!(2; (A, 0), (B, 1));
+impl_command_tuple!(3; (A, 0), (B, 1), (C, 2));
+impl_command_tuple!(4; (A, 0), (B, 1), (C, 2), (D, 3));
+impl_command_tuple!(5; (A, 0), (B, 1), (C, 2), (D, 3), (E, 4));
+impl_command_tuple!(6; (A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5));
+impl_command_tuple!(7; (A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5), (G, 6));
+impl_command_tuple!(8; (A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5), (G, 6), (H, 7));
+impl_command_tuple!(9; (A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5), (G, 6), (H, 7), (I, 8));
+impl_command_tuple!(
+    10; (A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5), (G, 6), (H, 7), (I, 8), (J, 9)
+);
+impl_command_tuple!(
+    11; (A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5), (G, 6), (H, 7), (I, 8), (J, 9), (K, 10)
+);
+impl_command_tuple!(
+    12; (A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5), (G, 6), (H, 7), (I, 8), (J, 9), (K, 10),
+    (L, 11)
+);
+
+/// # Sequence
+/// Commands of different types owned together, e.g. as a single `static`, and transferred in
+/// order. [`LEN`](Self::LEN), the total size of the command buffers, sizes DMA buffers and
+/// [`FOOTPRINT`](Self::FOOTPRINT) budgets their RAM at compile time.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{
+///     commands::{GetIrqStatus, ReadBuffer, SetStandby, StdbyConfig},
+///     sequences::Sequence,
+/// };
+///
+/// type Receive = Sequence<(SetStandby, GetIrqStatus, ReadBuffer<19>)>;
+/// const _: () = assert!(Receive::LEN == 2 + 4 + 19);
+/// const _: () = assert!(Receive::FOOTPRINT <= 64, "radio buffers exceed the RAM budget");
+///
+/// let mut receive: Receive =
+///     Sequence::new((SetStandby::new(StdbyConfig::StdbyRc), GetIrqStatus::new(), ReadBuffer::new(0)));
+/// assert_eq!(Receive::COUNT, 3);
+/// assert!(receive.descriptors().eq([
+///     receive.commands.0.descriptor(),
+///     receive.commands.1.descriptor(),
+///     receive.commands.2.descriptor(),
+/// ]));
+/// receive.commands.2.rx_buf[3] = 0x42;
+/// assert_eq!(receive.commands.2.data()[0], 0x42);
+/// ```
+pub struct Sequence<T> {
+    pub commands: T,
+}
+impl<T: CommandTuple> Sequence<T> {
+    /// Number of commands.
+    pub const COUNT: usize = T::COUNT;
+    /// Total size of the command buffers in bytes.
+    pub const LEN: usize = T::LEN;
+    /// RAM used by the TX and RX buffers in bytes, e.g. to fail the build above a RAM budget.
+    pub const FOOTPRINT: usize = T::FOOTPRINT;
+
+    #[inline]
+    pub const fn new(commands: T) -> Self {
+        Self { commands }
+    }
+    /// Descriptors of the commands, in order.
+    pub fn descriptors(&self) -> impl Iterator<Item = SpiDescriptor> + '_ {
+        (0..T::COUNT).map_while(|index| self.commands.descriptor(index))
+    }
+}
+
+/// # CommandSet
+/// One of each frequently reused command, to allocate them in a single `static`:
+/// - `GetIrqStatus`, `ClearIrqStatus` of all IRQs and `GetRxBufferStatus`
+/// - `SetStandby(STDBY_RC)`, `SetRx` and `SetTx` without timeout
+/// - `ReadBuffer` and `WriteBuffer` of [`MAX_PAYLOAD_LEN`](super::commands::MAX_PAYLOAD_LEN)
+///   bytes of data at offset 0
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{
+///     commands::{self, SetTx},
+///     sequences::CommandSet,
+/// };
+///
+/// let mut commands = CommandSet::new();
+/// assert_eq!(commands.get_irq_status().tx_buf, [0x12, 0, 0, 0]);
+/// assert_eq!(commands.read_buffer().data().len(), commands::MAX_PAYLOAD_LEN);
+///
+/// const _: () = assert!(CommandSet::FOOTPRINT <= 1100, "radio buffers exceed the RAM budget");
+///
+/// *commands.set_tx_mut() = SetTx::new(0x00_0FA0);
+/// assert_eq!(commands.set_tx().tx_buf, [0x83, 0x00, 0x0F, 0xA0]);
+/// ```
+pub struct CommandSet {
+    get_irq_status: GetIrqStatus,
+    clear_irq_status: ClearIrqStatus,
+    get_rx_buffer_status: GetRxBufferStatus,
+    set_standby: SetStandby,
+    set_rx: SetRx,
+    set_tx: SetTx,
+    read_buffer: MaxReadBuffer,
+    write_buffer: MaxWriteBuffer,
+}
+impl CommandSet {
+    /// RAM used by the TX and RX buffers of the commands in bytes.
+    pub const FOOTPRINT: usize = GetIrqStatus::FOOTPRINT
+        + ClearIrqStatus::FOOTPRINT
+        + GetRxBufferStatus::FOOTPRINT
+        + SetStandby::FOOTPRINT
+        + SetRx::FOOTPRINT
+        + SetTx::FOOTPRINT
+        + MaxReadBuffer::FOOTPRINT
+        + MaxWriteBuffer::FOOTPRINT;
+
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            get_irq_status: GetIrqStatus::new(),
+            clear_irq_status: ClearIrqStatus::new(Irq::ALL),
+            get_rx_buffer_status: GetRxBufferStatus::new(),
+            set_standby: SetStandby::new(StdbyConfig::StdbyRc),
+            set_rx: SetRx::new(0),
+            set_tx: SetTx::new(0),
+            read_buffer: ReadBuffer::new(0),
+            write_buffer: WriteBuffer::from_slice(0, &[]),
+        }
+    }
+    #[inline]
+    pub const fn get_irq_status(&self) -> &GetIrqStatus {
+        &self.get_irq_status
+    }
+    #[inline]
+    pub const fn get_irq_status_mut(&mut self) -> &mut GetIrqStatus {
+        &mut self.get_irq_status
+    }
+    #[inline]
+    pub const fn clear_irq_status(&self) -> &ClearIrqStatus {
+        &self.clear_irq_status
+    }
+    #[inline]
+    pub const fn clear_irq_status_mut(&mut self) -> &mut ClearIrqStatus {
+        &mut self.clear_irq_status
+    }
+    #[inline]
+    pub const fn get_rx_buffer_status(&self) -> &GetRxBufferStatus {
+        &self.get_rx_buffer_status
+    }
+    #[inline]
+    pub const fn get_rx_buffer_status_mut(&mut self) -> &mut GetRxBufferStatus {
+        &mut self.get_rx_buffer_status
+    }
+    #[inline]
+    pub const fn set_standby(&self) -> &SetStandby {
+        &self.set_standby
+    }
+    #[inline]
+    pub const fn set_standby_mut(&mut self) -> &mut SetStandby {
+        &mut self.set_standby
+    }
+    #[inline]
+    pub const fn set_rx(&self) -> &SetRx {
+        &self.set_rx
+    }
+    #[inline]
+    pub const fn set_rx_mut(&mut self) -> &mut SetRx {
+        &mut self.set_rx
+    }
+    #[inline]
+    pub const fn set_tx(&self) -> &SetTx {
+        &self.set_tx
+    }
+    #[inline]
+    pub const fn set_tx_mut(&mut self) -> &mut SetTx {
+        &mut self.set_tx
+    }
+    #[inline]
+    pub const fn read_buffer(&self) -> &MaxReadBuffer {
+        &self.read_buffer
+    }
+    #[inline]
+    pub const fn read_buffer_mut(&mut self) -> &mut MaxReadBuffer {
+        &mut self.read_buffer
+    }
+    #[inline]
+    pub const fn write_buffer(&self) -> &MaxWriteBuffer {
+        &self.write_buffer
+    }
+    #[inline]
+    pub const fn write_buffer_mut(&mut self) -> &mut MaxWriteBuffer {
+        &mut self.write_buffer
+    }
+}
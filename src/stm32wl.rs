@@ -0,0 +1,133 @@
+//! STM32WL sub-GHz radio.
+//!
+//! The STM32WL embeds an SX126x compatible radio driven over the internal SUBGHZSPI, so the
+//! command and register buffers of this crate apply unchanged. The differences are:
+//! - NSS is `PWR_SUBGHZSPICR.NSS` and BUSY is `PWR_SR2.RFBUSYS`, instead of GPIOs.
+//! - DIO1-3 are not pins: the radio IRQs are combined into the `SUBGHZ_Radio` interrupt.
+//! - There is no DIO2 output, so `SetDio2AsRfSwitchCtrl` has no effect and the RF switch is
+//!   driven by board GPIOs. DIO3 TCXO control drives the `PB0-VDDTCXO` pin.
+//! - Both the low power (up to +15 dBm) and high power (up to +22 dBm) PAs are present and
+//!   selected with `pa_sel` in `SetPaConfig`, see [`pa_config`].
+//! - The SMPS drive capability and clock detection are set through [`SmpsControl2`] and
+//!   [`SmpsControl0`].
+//!
+//! ## SUBGHZSPI DMA
+//! Each [`SpiDescriptor`](crate::commands::SpiDescriptor) is one transfer with NSS low: point
+//! the `SUBGHZSPI_TX` DMA channel at `tx_buf_ptr` and the `SUBGHZSPI_RX` channel at `rx_buf_ptr`,
+//! both with `transfer_length` bytes, clear `PWR_SUBGHZSPICR.NSS`, and set it again on the RX
+//! transfer complete. Wait for `PWR_SR2.RFBUSYS` to clear before the next descriptor.
+
+use super::commands::{SetPaConfig, SetRfFrequency};
+
+pub use super::commands::FrequencyOutOfRange;
+
+/// Minimum RF frequency in Hz.
+pub const FREQ_MIN_HZ: u32 = 150_000_000;
+/// Maximum RF frequency in Hz.
+pub const FREQ_MAX_HZ: u32 = 960_000_000;
+
+/// `SetRfFrequency` for `freq_hz`, checked against the STM32WL frequency range.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::stm32wl::{self, FrequencyOutOfRange};
+///
+/// let set_rf_frequency = stm32wl::rf_frequency(868_100_000).unwrap();
+/// assert_eq!(set_rf_frequency.tx_buf, [0x86, 0x36, 0x41, 0x99, 0x99]);
+/// assert_eq!(
+///     stm32wl::rf_frequency(2_400_000_000).err(),
+///     Some(FrequencyOutOfRange { freq_hz: 2_400_000_000 })
+/// );
+/// ```
+#[inline]
+pub const fn rf_frequency(freq_hz: u32) -> Result<SetRfFrequency, FrequencyOutOfRange> {
+    if freq_hz < FREQ_MIN_HZ || freq_hz > FREQ_MAX_HZ {
+        Err(FrequencyOutOfRange { freq_hz })
+    } else {
+        Ok(SetRfFrequency::from_hz(freq_hz))
+    }
+}
+
+/// Power amplifier, `pa_sel` in `SetPaConfig`.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaSel {
+    /// High power PA, RFO_HP, up to +22 dBm.
+    Hp = 0,
+    /// Low power PA, RFO_LP, up to +15 dBm.
+    Lp = 1,
+}
+
+/// Optimal `SetPaConfig` for an output power of `power` dBm on `pa`, with the power to set with
+/// `SetTxParams`, per the STM32WL reference manual. Other output powers are reached by lowering
+/// the `SetTxParams` power from the next higher setting.
+///
+/// Returns `None` for output powers without an optimal setting.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::stm32wl::{self, PaSel};
+///
+/// let (set_pa_config, tx_power) = stm32wl::pa_config(PaSel::Lp, 14).unwrap();
+/// assert_eq!(set_pa_config.tx_buf, [0x95, 0x04, 0x00, 0x01, 0x01]);
+/// assert_eq!(tx_power, 14);
+///
+/// let (set_pa_config, tx_power) = stm32wl::pa_config(PaSel::Hp, 17).unwrap();
+/// assert_eq!(set_pa_config.tx_buf, [0x95, 0x02, 0x03, 0x00, 0x01]);
+/// assert_eq!(tx_power, 22);
+///
+/// assert!(stm32wl::pa_config(PaSel::Lp, 22).is_none());
+/// ```
+pub const fn pa_config(pa: PaSel, power: i8) -> Option<(SetPaConfig, i8)> {
+    let (pa_duty_cycle, hp_max, tx_power) = match (pa, power) {
+        (PaSel::Lp, 15) => (0x07, 0x00, 14),
+        (PaSel::Lp, 14) => (0x04, 0x00, 14),
+        (PaSel::Lp, 10) => (0x01, 0x00, 13),
+        (PaSel::Hp, 22) => (0x04, 0x07, 22),
+        (PaSel::Hp, 20) => (0x03, 0x05, 22),
+        (PaSel::Hp, 17) => (0x02, 0x03, 22),
+        (PaSel::Hp, 14) => (0x02, 0x02, 22),
+        _ => return None,
+    };
+    Some((SetPaConfig::new(pa_duty_cycle, hp_max, pa as u8), tx_power))
+}
+
+crate::declare_register! {
+    /// SMPS control 0, `SUBGHZ_SMPSC0R`. Clock detection should be enabled before the SMPS.
+    pub struct SmpsControl0(SmpsControl0Bits): 0x0916 {
+        #[bits(6)]
+        __: u8,
+        pub clock_detect: bool,
+        __: bool,
+    }
+}
+
+crate::declare_register! {
+    /// SMPS control 2, `SUBGHZ_SMPSC2R`.
+    pub struct SmpsControl2(SmpsControl2Bits): 0x0923 {
+        __: bool,
+        /// Maximum drive capability: 0 = 20 mA, 1 = 40 mA, 2 = 60 mA, 3 = 100 mA (reset).
+        #[bits(2)]
+        pub drive: u8,
+        #[bits(5)]
+        __: u8,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::WriteRegister;
+
+    #[test]
+    fn test_smps_registers() {
+        let write_register: WriteRegister =
+            WriteRegister::new(SmpsControl2(SmpsControl2Bits::new().with_drive(3)));
+        assert_eq!(write_register.tx_buf, [0x0D, 0x09, 0x23, 0x06]);
+
+        let write_register: WriteRegister = WriteRegister::new(SmpsControl0(
+            SmpsControl0Bits::new().with_clock_detect(true),
+        ));
+        assert_eq!(write_register.tx_buf, [0x0D, 0x09, 0x16, 0x40]);
+    }
+}
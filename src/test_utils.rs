@@ -0,0 +1,149 @@
+//! Test helpers comparing command buffers with hex strings, e.g. `"8E 16 04"`. Hex strings may
+//! contain whitespace between bytes.
+//!
+//! [`assert_spi_bytes!`](crate::assert_spi_bytes) asserts that a buffer matches a hex string,
+//! showing both in hex with the differing bytes marked on failure:
+//! ```text
+//! SPI bytes differ at byte 1
+//! expected: 8E 16 04
+//!   actual: 8E 0E 04
+//!              ^^
+//! ```
+//! [`fill_rx_hex!`](crate::fill_rx_hex) writes a hex string to the start of a buffer, to
+//! populate `rx_buf` with a radio response.
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::{
+//!     assert_spi_bytes,
+//!     commands::{GetIrqStatus, Irq, RampTime, SetTxParams},
+//!     fill_rx_hex,
+//! };
+//!
+//! assert_spi_bytes!(SetTxParams::new(22, RampTime::Ramp200U).tx_buf, "8E 16 04");
+//!
+//! let mut get_irq_status = GetIrqStatus::new();
+//! fill_rx_hex!(get_irq_status.rx_buf, "A2 A4 0002");
+//! assert_eq!(get_irq_status.irq_status(), Irq::new().with_rx_done(true));
+//! ```
+
+use super::fmt::Hex;
+use core::fmt;
+
+/// Longest hex string in bytes, the length of a `ReadBuffer` of 255 bytes.
+pub const MAX_LEN: usize = 258;
+
+/// Asserts that a command buffer matches a hex string.
+#[macro_export]
+macro_rules! assert_spi_bytes {
+    ($actual:expr, $hex:expr $(,)?) => {
+        $crate::test_utils::assert_bytes(&$actual[..], $hex)
+    };
+}
+
+/// Writes the bytes of a hex string to the start of a buffer.
+#[macro_export]
+macro_rules! fill_rx_hex {
+    ($buf:expr, $hex:expr $(,)?) => {
+        $crate::test_utils::fill_hex(&mut $buf[..], $hex)
+    };
+}
+
+/// Parses `hex` into `buf`, returning the number of bytes.
+///
+/// ## Panics
+/// If `hex` contains a character other than hex digits and whitespace, an odd number of
+/// digits or more bytes than `buf`.
+#[track_caller]
+#[allow(clippy::panic)]
+pub fn parse_hex(hex: &str, buf: &mut [u8]) -> usize {
+    let mut digits = hex.chars().filter(|c| !c.is_whitespace()).map(|c| {
+        c.to_digit(16)
+            .unwrap_or_else(|| panic!("invalid hex digit {c:?} in {hex:?}")) as u8
+    });
+    let (mut len, capacity) = (0, buf.len());
+    while let Some(high) = digits.next() {
+        let low = digits
+            .next()
+            .unwrap_or_else(|| panic!("odd number of hex digits in {hex:?}"));
+        *buf.get_mut(len)
+            .unwrap_or_else(|| panic!("{hex:?} is longer than {capacity} bytes")) = high << 4 | low;
+        len += 1;
+    }
+    len
+}
+
+/// Writes the bytes of `hex` to the start of `buf`, see [`fill_rx_hex!`](crate::fill_rx_hex).
+///
+/// ## Panics
+/// If `hex` is invalid or longer than `buf`.
+#[track_caller]
+pub fn fill_hex(buf: &mut [u8], hex: &str) {
+    parse_hex(hex, buf);
+}
+
+/// Asserts that `actual` matches `hex`, see [`assert_spi_bytes!`](crate::assert_spi_bytes).
+///
+/// ## Panics
+/// If the bytes differ, or `hex` is invalid or longer than [`MAX_LEN`].
+#[track_caller]
+#[allow(clippy::indexing_slicing, clippy::panic)]
+pub fn assert_bytes(actual: &[u8], hex: &str) {
+    let mut buf = [0; MAX_LEN];
+    let len = parse_hex(hex, &mut buf);
+    let expected = &buf[..len];
+    if let Some(index) = first_difference(expected, actual) {
+        panic!(
+            "SPI bytes differ at byte {index}\nexpected: {}\n  actual: {}\n          {}",
+            Hex(expected),
+            Hex(actual),
+            Markers(expected, actual)
+        );
+    }
+}
+
+fn first_difference(expected: &[u8], actual: &[u8]) -> Option<usize> {
+    (0..expected.len().max(actual.len())).find(|&i| expected.get(i) != actual.get(i))
+}
+
+/// `^^` under each differing byte of [`Hex`] renderings.
+struct Markers<'a>(&'a [u8], &'a [u8]);
+impl fmt::Display for Markers<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let len = self.0.len().max(self.1.len());
+        for i in 0..len {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            if self.0.get(i) != self.1.get(i) {
+                f.write_str("^^")?;
+            } else {
+                f.write_str("  ")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex() {
+        let mut buf = [0; 4];
+        assert_eq!(parse_hex("A2 a4\n00 02", &mut buf), 4);
+        assert_eq!(buf, [0xA2, 0xA4, 0x00, 0x02]);
+        assert_eq!(parse_hex("", &mut buf), 0);
+        assert_eq!(
+            first_difference(&[0x8E, 0x16], &[0x8E, 0x16, 0x04]),
+            Some(2)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "SPI bytes differ at byte 1")]
+    fn test_assert_bytes_mismatch() {
+        assert_spi_bytes!([0x8E, 0x0E, 0x04], "8E 16 04");
+    }
+}
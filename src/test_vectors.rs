@@ -0,0 +1,264 @@
+//! Golden MOSI byte sequences derived from the datasheet, for conformance tests of drivers and
+//! executors: send the commands of [`VECTORS`] through the SPI path under test, capture the
+//! bytes clocked out and compare them with [`check`], [`check_all`] or [`assert_vector`].
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::{
+//!     commands::{SetStandby, StdbyConfig},
+//!     test_vectors::{self, Mismatch},
+//! };
+//!
+//! let set_standby = SetStandby::new(StdbyConfig::StdbyRc);
+//! let vector = test_vectors::find("SetStandby(StdbyRc)").unwrap();
+//! assert_eq!(test_vectors::check(vector, &set_standby.tx_buf), Ok(()));
+//! assert_eq!(
+//!     test_vectors::check(vector, &[0x80, 0x01]),
+//!     Err(Mismatch { name: "SetStandby(StdbyRc)", index: 1, expected: Some(0x00), actual: Some(0x01) })
+//! );
+//!
+//! // Captured bytes of each vector sent through the SPI path under test
+//! assert_eq!(test_vectors::check_all(|vector| vector.tx), Ok(()));
+//! ```
+
+/// # Vector
+/// A command and the bytes it must clock out on MOSI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Vector {
+    /// Command and its parameters, e.g. `SetRfFrequency(868 MHz)`.
+    pub name: &'static str,
+    /// Bytes on MOSI, opcode first.
+    pub tx: &'static [u8],
+}
+
+/// Captured bytes differing from a vector, at the first differing `index`. `expected` or
+/// `actual` is `None` past the end of the vector or the capture.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub name: &'static str,
+    pub index: usize,
+    pub expected: Option<u8>,
+    pub actual: Option<u8>,
+}
+
+/// Vectors of the commands of a typical LoRa TX/RX setup, with the parameters of the datasheet
+/// examples.
+pub const VECTORS: &[Vector] = &[
+    Vector {
+        name: "SetSleep(warm start)",
+        tx: &[0x84, 0x04],
+    },
+    Vector {
+        name: "SetStandby(StdbyRc)",
+        tx: &[0x80, 0x00],
+    },
+    Vector {
+        name: "SetStandby(StdbyXosc)",
+        tx: &[0x80, 0x01],
+    },
+    Vector {
+        name: "SetRegulatorMode(DC-DC)",
+        tx: &[0x96, 0x01],
+    },
+    Vector {
+        name: "SetDio3AsTcxoCtrl(1.8 V, 5 ms)",
+        tx: &[0x97, 0x02, 0x00, 0x01, 0x40],
+    },
+    Vector {
+        name: "Calibrate(all blocks)",
+        tx: &[0x89, 0x7F],
+    },
+    Vector {
+        name: "CalibrateImage(863-870 MHz)",
+        tx: &[0x98, 0xD7, 0xDB],
+    },
+    Vector {
+        name: "CalibrateImage(902-928 MHz)",
+        tx: &[0x98, 0xE1, 0xE9],
+    },
+    Vector {
+        name: "SetDio2AsRfSwitchCtrl(enabled)",
+        tx: &[0x9D, 0x01],
+    },
+    Vector {
+        name: "SetPacketType(Lora)",
+        tx: &[0x8A, 0x01],
+    },
+    Vector {
+        name: "SetRfFrequency(868 MHz)",
+        tx: &[0x86, 0x36, 0x40, 0x00, 0x00],
+    },
+    Vector {
+        name: "SetRfFrequency(915 MHz)",
+        tx: &[0x86, 0x39, 0x30, 0x00, 0x00],
+    },
+    Vector {
+        name: "SetPaConfig(SX1262, +22 dBm)",
+        tx: &[0x95, 0x04, 0x07, 0x00, 0x01],
+    },
+    Vector {
+        name: "SetTxParams(+22 dBm, 200 us)",
+        tx: &[0x8E, 0x16, 0x04],
+    },
+    Vector {
+        name: "SetBufferBaseAddress(0, 0)",
+        tx: &[0x8F, 0x00, 0x00],
+    },
+    Vector {
+        name: "SetModulationParams(SF7, 125 kHz, CR 4/5)",
+        tx: &[0x8B, 0x07, 0x04, 0x01, 0x00],
+    },
+    Vector {
+        name: "SetPacketParams(preamble 8, variable header, 255 bytes, CRC on)",
+        tx: &[0x8C, 0x00, 0x08, 0x00, 0xFF, 0x01, 0x00],
+    },
+    Vector {
+        name: "WriteRegister(LoRa sync word MSB, public)",
+        tx: &[0x0D, 0x07, 0x40, 0x34],
+    },
+    Vector {
+        name: "SetDioIrqParams(TxDone, RxDone, Timeout on DIO1)",
+        tx: &[0x08, 0x02, 0x03, 0x02, 0x03, 0x00, 0x00, 0x00, 0x00],
+    },
+    Vector {
+        name: "WriteBuffer(0, [1, 2, 3])",
+        tx: &[0x0E, 0x00, 0x01, 0x02, 0x03],
+    },
+    Vector {
+        name: "SetTx(no timeout)",
+        tx: &[0x83, 0x00, 0x00, 0x00],
+    },
+    Vector {
+        name: "SetRx(continuous)",
+        tx: &[0x82, 0xFF, 0xFF, 0xFF],
+    },
+    Vector {
+        name: "GetIrqStatus",
+        tx: &[0x12, 0x00, 0x00, 0x00],
+    },
+    Vector {
+        name: "ClearIrqStatus(all)",
+        tx: &[0x02, 0x43, 0xFF],
+    },
+    Vector {
+        name: "GetRxBufferStatus",
+        tx: &[0x13, 0x00, 0x00, 0x00],
+    },
+    Vector {
+        name: "ReadBuffer(0, 3 bytes)",
+        tx: &[0x1E, 0x00, 0x00, 0x00, 0x00, 0x00],
+    },
+    Vector {
+        name: "GetStatus",
+        tx: &[0xC0, 0x00],
+    },
+    Vector {
+        name: "GetDeviceErrors",
+        tx: &[0x17, 0x00, 0x00, 0x00],
+    },
+];
+
+/// Vector named `name`.
+pub fn find(name: &str) -> Option<&'static Vector> {
+    VECTORS.iter().find(|vector| vector.name == name)
+}
+
+/// Compares captured bytes with a vector.
+pub fn check(vector: &Vector, actual: &[u8]) -> Result<(), Mismatch> {
+    let len = vector.tx.len().max(actual.len());
+    for index in 0..len {
+        let (expected, actual) = (vector.tx.get(index).copied(), actual.get(index).copied());
+        if expected != actual {
+            return Err(Mismatch {
+                name: vector.name,
+                index,
+                expected,
+                actual,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Compares the bytes captured by `capture` for each vector, stopping at the first mismatch.
+pub fn check_all<C: AsRef<[u8]>>(mut capture: impl FnMut(&Vector) -> C) -> Result<(), Mismatch> {
+    VECTORS
+        .iter()
+        .try_for_each(|vector| check(vector, capture(vector).as_ref()))
+}
+
+/// Asserts that captured bytes match a vector.
+///
+/// ## Panics
+/// Panics with the name of the vector and the first differing byte on a mismatch.
+#[track_caller]
+#[allow(clippy::panic)]
+pub fn assert_vector(vector: &Vector, actual: &[u8]) {
+    if let Err(mismatch) = check(vector, actual) {
+        panic!(
+            "{} differs at byte {}: expected {:02X?}, got {:02X?}",
+            mismatch.name, mismatch.index, mismatch.expected, mismatch.actual
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::*;
+
+    #[test]
+    fn test_vectors_match_commands() {
+        let dio1_irqs = Irq::TX_DONE | Irq::RX_DONE | Irq::TIMEOUT;
+        let tx_bufs: [&[u8]; 28] = [
+            &SetSleep::new(true).tx_buf,
+            &SetStandby::new(StdbyConfig::StdbyRc).tx_buf,
+            &SetStandby::new(StdbyConfig::StdbyXosc).tx_buf,
+            &SetRegulatorMode::new(true).tx_buf,
+            &SetDio3AsTcxoCtrl::new(TcxoVoltage::V1_8, 320).tx_buf,
+            &Calibrate::new(CalibrationSetting::from_bits(0x7F)).tx_buf,
+            &CalibrateImage::new(0xD7, 0xDB).tx_buf,
+            &CalibrateImage::new(0xE1, 0xE9).tx_buf,
+            &SetDio2AsRfSwitchCtrl::new(true).tx_buf,
+            &SetPacketType::new(PacketType::Lora).tx_buf,
+            &SetRfFrequency::from_hz(868_000_000).tx_buf,
+            &SetRfFrequency::from_hz(915_000_000).tx_buf,
+            &SetPaConfig::new(0x04, 0x07, 0x00).tx_buf,
+            &SetTxParams::new(22, RampTime::Ramp200U).tx_buf,
+            &SetBufferBaseAddress::new(0, 0).tx_buf,
+            &SetModulationParamsLora::new(Sf::Sf7, Bw::Bw125, Cr::Cr4_5, false).tx_buf,
+            &SetPacketParams::new(8, HeaderType::VariableLength, 255, true, InvertIq::Standard)
+                .tx_buf,
+            &WriteRegister::at_address(0x0740, 0x34).tx_buf,
+            &SetDioIrqParams::new(dio1_irqs, dio1_irqs, Irq::new(), Irq::new()).tx_buf,
+            &WriteBuffer::<5>::from_slice(0, &[1, 2, 3]).tx_buf,
+            &SetTx::new(0).tx_buf,
+            &SetRx::new(0xFF_FFFF).tx_buf,
+            &GetIrqStatus::new().tx_buf,
+            &ClearIrqStatus::new(Irq::ALL).tx_buf,
+            &GetRxBufferStatus::new().tx_buf,
+            &ReadBuffer::<6>::new(0).tx_buf,
+            &GetStatus::new().tx_buf,
+            &GetDeviceErrors::new().tx_buf,
+        ];
+        assert_eq!(tx_bufs.len(), VECTORS.len());
+        for (vector, tx_buf) in VECTORS.iter().zip(tx_bufs) {
+            assert_vector(vector, tx_buf);
+        }
+    }
+
+    #[test]
+    fn test_check_length_mismatch() {
+        let vector = find("GetStatus").unwrap();
+        assert_eq!(
+            check(vector, &[0xC0]),
+            Err(Mismatch {
+                name: "GetStatus",
+                index: 1,
+                expected: Some(0x00),
+                actual: None
+            })
+        );
+        assert!(check(vector, &[0xC0, 0x00, 0x00]).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+//! Timestamping of descriptors at submission and completion, for measuring TX and RX times and
+//! SPI latency in time-synchronized protocols.
+//!
+//! Executors call a [`TransferHook`] when they submit a descriptor and when its transfer has
+//! completed, e.g. from the SPI/DMA transfer complete interrupt. [`TimestampHook`] records the
+//! time given by a user clock, e.g. a monotonic timer, at both points, as the [`TransferTimes`]
+//! of an [`AttributedDescriptor`].
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::{
+//!     commands::SetTx,
+//!     timestamp::{TimestampHook, TransferHook},
+//! };
+//!
+//! let mut ticks = 0u32;
+//! let mut hook: TimestampHook<u32, _, 4> = TimestampHook::new(|| {
+//!     ticks += 10;
+//!     ticks
+//! });
+//!
+//! let set_tx = SetTx::new(0);
+//! hook.submitted(&set_tx.descriptor());
+//! hook.completed(&set_tx.descriptor());
+//!
+//! let record = hook.records().next().unwrap();
+//! assert_eq!(record.descriptor, set_tx.descriptor());
+//! let times = record.attributes;
+//! assert_eq!((times.submitted, times.completed), (10, Some(20)));
+//! assert_eq!(times.latency(), Some(10));
+//! ```
+
+use super::attributes::AttributedDescriptor;
+use super::commands::SpiDescriptor;
+use core::ops::Sub;
+
+/// # TransferHook
+/// Called by executors around each transfer. Both methods default to doing nothing.
+pub trait TransferHook {
+    /// `descriptor` is submitted to the SPI/DMA.
+    #[inline]
+    fn submitted(&mut self, descriptor: &SpiDescriptor) {
+        let _ = descriptor;
+    }
+    /// The transfer of `descriptor` has completed.
+    #[inline]
+    fn completed(&mut self, descriptor: &SpiDescriptor) {
+        let _ = descriptor;
+    }
+}
+/// No hook.
+impl TransferHook for () {}
+
+/// Submission and completion times of a transfer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransferTimes<T> {
+    pub submitted: T,
+    /// `None` until the transfer has completed.
+    pub completed: Option<T>,
+}
+impl<T: Copy + Sub> TransferTimes<T> {
+    /// Time from submission to completion.
+    #[inline]
+    pub fn latency(&self) -> Option<T::Output> {
+        self.completed.map(|completed| completed - self.submitted)
+    }
+}
+
+/// # TimestampHook
+/// Records the times of the last `N` submitted descriptors with `clock`.
+pub struct TimestampHook<T, F, const N: usize> {
+    clock: F,
+    records: [Option<AttributedDescriptor<TransferTimes<T>>>; N],
+    next: usize,
+}
+impl<T: Copy, F: FnMut() -> T, const N: usize> TimestampHook<T, F, N> {
+    const MIN_SIZE: () = assert!(N >= 1, "N must be at least 1");
+
+    #[inline]
+    pub fn new(clock: F) -> Self {
+        let () = Self::MIN_SIZE;
+        Self {
+            clock,
+            records: core::array::from_fn(|_| None),
+            next: 0,
+        }
+    }
+    /// Recorded times, oldest first.
+    pub fn records(&self) -> impl Iterator<Item = &AttributedDescriptor<TransferTimes<T>>> {
+        let (newer, older) = self.records.split_at(self.next);
+        older.iter().chain(newer).flatten()
+    }
+    /// Forgets the recorded times.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.records = core::array::from_fn(|_| None);
+        self.next = 0;
+    }
+}
+impl<T: Copy, F: FnMut() -> T, const N: usize> TransferHook for TimestampHook<T, F, N> {
+    /// Records the submission time, replacing the oldest record when all `N` are used.
+    #[allow(clippy::indexing_slicing)]
+    fn submitted(&mut self, descriptor: &SpiDescriptor) {
+        let times = TransferTimes {
+            submitted: (self.clock)(),
+            completed: None,
+        };
+        self.records[self.next] = Some(AttributedDescriptor::new(*descriptor, times));
+        self.next = (self.next + 1) % N;
+    }
+    /// Records the completion time of the oldest pending submission of `descriptor`, as
+    /// transfers complete in submission order.
+    fn completed(&mut self, descriptor: &SpiDescriptor) {
+        let now = (self.clock)();
+        let (newer, older) = self.records.split_at_mut(self.next);
+        let pending = older.iter_mut().chain(newer).flatten().find(|record| {
+            record.descriptor == *descriptor && record.attributes.completed.is_none()
+        });
+        if let Some(record) = pending {
+            record.attributes.completed = Some(now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{GetIrqStatus, SetStandby, StdbyConfig};
+
+    #[test]
+    fn test_timestamp_hook_wraps_and_matches_oldest() {
+        let mut ticks = 0u32;
+        let mut hook: TimestampHook<u32, _, 2> = TimestampHook::new(|| {
+            ticks += 1;
+            ticks
+        });
+        let set_standby = SetStandby::new(StdbyConfig::StdbyRc);
+        let get_irq_status = GetIrqStatus::new();
+        hook.submitted(&set_standby.descriptor());
+        hook.submitted(&get_irq_status.descriptor());
+        hook.submitted(&get_irq_status.descriptor());
+        hook.completed(&get_irq_status.descriptor());
+
+        let mut records = hook.records();
+        let first = records.next().unwrap().attributes;
+        assert_eq!((first.submitted, first.completed), (2, Some(4)));
+        let second = records.next().unwrap().attributes;
+        assert_eq!((second.submitted, second.completed), (3, None));
+        assert!(records.next().is_none());
+        drop(records);
+
+        hook.completed(&get_irq_status.descriptor());
+        let second = hook.records().nth(1).unwrap();
+        assert_eq!(second.attributes.completed, Some(5));
+    }
+}
@@ -0,0 +1,124 @@
+//! Integer conversions between time, LoRa symbols and timeout steps.
+
+use super::commands::{Bw, Cr, HeaderType, Sf};
+
+/// LoRa symbol duration in microseconds, `2^SF / BW`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{commands::{Bw, Sf}, timing};
+///
+/// assert_eq!(timing::symbol_duration_us(Sf::Sf7, Bw::Bw125), 1024);
+/// assert_eq!(timing::symbol_duration_us(Sf::Sf12, Bw::Bw125), 32_768);
+/// ```
+#[inline]
+pub const fn symbol_duration_us(sf: Sf, bw: Bw) -> u32 {
+    (1_000_000 * sf.chips_per_symbol() as u64 / bw.hz() as u64) as u32
+}
+
+/// Number of symbols covering at least `ms` milliseconds, e.g. for `SetLoraSymbNumTimeout`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{commands::{Bw, Sf}, timing};
+///
+/// assert_eq!(timing::ms_to_symbols(10, Sf::Sf7, Bw::Bw125), 10);
+/// assert_eq!(timing::ms_to_symbols(100, Sf::Sf12, Bw::Bw125), 4);
+/// ```
+#[inline]
+pub const fn ms_to_symbols(ms: u32, sf: Sf, bw: Bw) -> u32 {
+    (ms as u64 * 1000).div_ceil(symbol_duration_us(sf, bw) as u64) as u32
+}
+
+/// Duration of `symbols` symbols in milliseconds, rounded up.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{commands::{Bw, Sf}, timing};
+///
+/// assert_eq!(timing::symbols_to_ms(8, Sf::Sf7, Bw::Bw125), 9);
+/// assert_eq!(timing::symbols_to_ms(8, Sf::Sf12, Bw::Bw125), 263);
+/// ```
+#[inline]
+pub const fn symbols_to_ms(symbols: u32, sf: Sf, bw: Bw) -> u32 {
+    (symbols as u64 * symbol_duration_us(sf, bw) as u64).div_ceil(1000) as u32
+}
+
+/// `SetRx`/`SetTx` timeout in steps of 15.625 us for `ms` milliseconds.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{commands::SetRx, timing};
+///
+/// let set_rx: SetRx = SetRx::new(timing::ms_to_timeout(1000));
+/// assert_eq!(set_rx.tx_buf, [0x82, 0x00, 0xFA, 0x00]);
+/// ```
+#[inline]
+pub const fn ms_to_timeout(ms: u32) -> u32 {
+    ms * 64
+}
+
+/// `SetRx`/`SetTx` timeout in steps of 15.625 us for `symbols` symbols, rounded up.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{commands::{Bw, Sf}, timing};
+///
+/// assert_eq!(timing::symbols_to_timeout(8, Sf::Sf7, Bw::Bw125), 525);
+/// ```
+#[inline]
+pub const fn symbols_to_timeout(symbols: u32, sf: Sf, bw: Bw) -> u32 {
+    (symbols as u64 * symbol_duration_us(sf, bw) as u64 * 64).div_ceil(1000) as u32
+}
+
+/// LoRa time on air in microseconds of a packet with `payload_length` bytes, per the SX1261/2
+/// datasheet.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{commands::{Bw, Cr, HeaderType, Sf}, timing};
+///
+/// let toa = timing::time_on_air_us(
+///     Sf::Sf7,
+///     Bw::Bw125,
+///     Cr::Cr4_5,
+///     false,
+///     8,
+///     HeaderType::VariableLength,
+///     10,
+///     true,
+/// );
+/// assert_eq!(toa, 41_216);
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub const fn time_on_air_us(
+    sf: Sf,
+    bw: Bw,
+    cr: Cr,
+    low_data_rate_optimize: bool,
+    preamble_length: u16,
+    header_type: HeaderType,
+    payload_length: u8,
+    crc: bool,
+) -> u32 {
+    let sf_bits = sf.factor() as i32;
+    let header_bits = match header_type {
+        HeaderType::VariableLength => 20,
+        HeaderType::FixedLength => 0,
+    };
+    // SF5 and SF6 have a longer sync and no extra payload bits in the first block
+    let (sync_quarters, extra_bits) = match sf {
+        Sf::Sf5 | Sf::Sf6 => (25, 0),
+        _ => (17, 8),
+    };
+    let bits = 8 * payload_length as i32 + 16 * crc as i32 - 4 * sf_bits + extra_bits + header_bits;
+    let bits_per_block = 4 * (sf_bits - 2 * low_data_rate_optimize as i32);
+    let blocks = if bits > 0 {
+        (bits + bits_per_block - 1) / bits_per_block
+    } else {
+        0
+    };
+    let payload_symbols = 8 + blocks as u64 * cr.denominator() as u64;
+    let quarters = 4 * preamble_length as u64 + sync_quarters + 4 * payload_symbols;
+    (quarters * 1_000_000 * sf.chips_per_symbol() as u64 / (4 * bw.hz() as u64)) as u32
+}
@@ -0,0 +1,816 @@
+//! Transmit and receive workflows bundling the commands of a single packet exchange.
+#![allow(clippy::new_without_default)]
+
+use super::commands::{
+    Bw, CapacityExceeded, ClearIrqStatus, Cr, GetPacketStatusLora, GetRxBufferStatus, HeaderType,
+    InvertIq, Irq, ReadBuffer, SetBufferBaseAddress, SetLoraSymbNumTimeout,
+    SetModulationParamsLora, SetPacketParams, SetRfFrequency, SetRx, SetRxDutyCycle, SetTx, Sf,
+    SpiDescriptor, WriteBuffer, transfer_length,
+};
+use super::timing;
+
+/// # TxTransaction
+/// Writes a payload to the data buffer, sets the payload length in the packet parameters and
+/// starts the transmission. Call `prepare()` for each packet, then transfer `descriptors()` in
+/// order.
+///
+/// #### Type Parameter `N`
+/// `N` = maximum payload length + 2, at most 257 as payloads are at most 255 bytes long
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{
+///     commands::{HeaderType, InvertIq, SetPacketParams},
+///     transactions::TxTransaction,
+/// };
+///
+/// let mut tx: TxTransaction<66> = TxTransaction::new(
+///     0x00,
+///     SetPacketParams::new(8, HeaderType::VariableLength, 0, true, InvertIq::Standard),
+/// );
+/// tx.prepare(b"hello", 0);
+/// assert_eq!(tx.write_buffer.tx_buf[..7], [0x0E, 0x00, b'h', b'e', b'l', b'l', b'o']);
+/// assert_eq!(tx.set_packet_params.tx_buf, [0x8C, 0, 8, 0, 5, 1, 0]);
+/// assert_eq!(tx.set_tx.tx_buf, [0x83, 0, 0, 0]);
+///
+/// let descriptors = tx.descriptors();
+/// assert_eq!(descriptors[0].transfer_length, 7);
+/// assert_eq!(descriptors[1].transfer_length, 7);
+/// assert_eq!(descriptors[2].transfer_length, 4);
+/// ```
+///
+/// Buffers for payloads longer than 255 bytes fail to compile:
+/// ```compile_fail
+/// use sx126x_spi_buffers::{
+///     commands::{HeaderType, InvertIq, SetPacketParams},
+///     transactions::TxTransaction,
+/// };
+///
+/// let tx: TxTransaction<258> = TxTransaction::new(
+///     0x00,
+///     SetPacketParams::new(8, HeaderType::VariableLength, 0, true, InvertIq::Standard),
+/// );
+/// ```
+pub struct TxTransaction<const N: usize> {
+    pub write_buffer: WriteBuffer<N>,
+    pub set_packet_params: SetPacketParams,
+    pub set_tx: SetTx,
+    payload_length: u8,
+}
+impl<const N: usize> TxTransaction<N> {
+    const MAX_SIZE: () = assert!(N <= 257, "N must be at most 257");
+
+    /// `offset` is the TX base address, `set_packet_params` the packet parameters to which the
+    /// payload length is applied.
+    #[inline]
+    pub const fn new(offset: u8, set_packet_params: SetPacketParams) -> Self {
+        let () = Self::MAX_SIZE;
+        Self {
+            write_buffer: WriteBuffer::with_offset(offset),
+            set_packet_params,
+            set_tx: SetTx::new(0),
+            payload_length: 0,
+        }
+    }
+    /// Copies `payload` into the write buffer and sets the payload length and TX timeout.
+    ///
+    /// ## Panics
+    /// If `payload` is longer than `N - 2` bytes.
+    #[inline]
+    #[allow(clippy::panic)]
+    pub const fn prepare(&mut self, payload: &[u8], timeout: u32) {
+        if self.try_prepare(payload, timeout).is_err() {
+            panic!("payload exceeds buffer capacity");
+        }
+    }
+    /// Like `prepare()`, rejecting a `payload` longer than `N - 2` bytes.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn try_prepare(
+        &mut self,
+        payload: &[u8],
+        timeout: u32,
+    ) -> Result<(), CapacityExceeded> {
+        if payload.len() + 2 > N {
+            return Err(CapacityExceeded {
+                len: payload.len(),
+                capacity: N.saturating_sub(2),
+            });
+        }
+        let mut i: usize = 0;
+        while i < payload.len() {
+            self.write_buffer.tx_buf[i + 2] = payload[i];
+            i += 1;
+        }
+        // At most `N - 2` <= 255 bytes
+        self.payload_length = payload.len() as u8;
+        self.set_packet_params.tx_buf[4] = self.payload_length;
+        self.set_tx = SetTx::new(timeout);
+        Ok(())
+    }
+    /// Descriptors for `WriteBuffer`, `SetPacketParams` and `SetTx`. Only the prepared payload
+    /// is written.
+    #[inline]
+    pub const fn descriptors(&self) -> [SpiDescriptor; 3] {
+        [
+            SpiDescriptor {
+                transfer_length: transfer_length(self.payload_length as usize + 2),
+                ..self.write_buffer.descriptor()
+            },
+            self.set_packet_params.descriptor(),
+            self.set_tx.descriptor(),
+        ]
+    }
+}
+
+/// # TxBatch
+/// Packs several frames back to back into the radio data buffer with a single `WriteBuffer`,
+/// then transmits them one by one.
+///
+/// 1. `push()` each frame, then transfer `write_descriptor()`.
+/// 2. For each frame, transfer `frame_descriptors()` and wait for `TxDone`.
+///
+/// #### Type Parameters
+/// - `N` = total length of the frames + 2, at most 258
+/// - `F` = maximum number of frames
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{
+///     commands::{HeaderType, InvertIq, SetPacketParams},
+///     transactions::TxBatch,
+/// };
+///
+/// let mut batch: TxBatch<66, 4> = TxBatch::new(
+///     &SetPacketParams::new(8, HeaderType::VariableLength, 0, true, InvertIq::Standard),
+///     0x80,
+///     0,
+/// );
+/// assert_eq!(batch.push(b"abc"), Some(0));
+/// assert_eq!(batch.push(b"de"), Some(1));
+/// assert_eq!(batch.write_buffer.tx_buf[..7], [0x0E, 0x00, b'a', b'b', b'c', b'd', b'e']);
+/// assert_eq!(batch.write_descriptor().transfer_length, 7);
+///
+/// assert_eq!(batch.set_buffer_base_addresses[1].tx_buf, [0x8F, 3, 0x80]);
+/// assert_eq!(batch.set_packet_params[1].tx_buf, [0x8C, 0, 8, 0, 2, 1, 0]);
+/// assert_eq!(batch.frame_descriptors(1)[2].transfer_length, 4);
+/// assert!(batch.try_frame_descriptors(4).is_none());
+/// ```
+pub struct TxBatch<const N: usize, const F: usize> {
+    pub write_buffer: WriteBuffer<N>,
+    pub set_buffer_base_addresses: [SetBufferBaseAddress; F],
+    pub set_packet_params: [SetPacketParams; F],
+    pub set_tx: SetTx,
+    rx_base_address: u8,
+    len: usize,
+    frames: usize,
+}
+impl<const N: usize, const F: usize> TxBatch<N, F> {
+    const MAX_SIZE: () = assert!(N >= 2 && N - 2 <= 256, "frames exceed the data buffer");
+
+    /// `set_packet_params` is the template for the packet parameters of each frame,
+    /// `rx_base_address` the RX base address kept while transmitting.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn new(
+        set_packet_params: &SetPacketParams,
+        rx_base_address: u8,
+        timeout: u32,
+    ) -> Self {
+        let () = Self::MAX_SIZE;
+        let mut batch = Self {
+            write_buffer: WriteBuffer::with_offset(0),
+            set_buffer_base_addresses: [const { SetBufferBaseAddress::new(0, 0) }; F],
+            set_packet_params: [const {
+                SetPacketParams {
+                    tx_buf: [0; 7],
+                    rx_buf: [0; 7],
+                }
+            }; F],
+            set_tx: SetTx::new(timeout),
+            rx_base_address,
+            len: 0,
+            frames: 0,
+        };
+        let mut i: usize = 0;
+        while i < F {
+            batch.set_packet_params[i].tx_buf = set_packet_params.tx_buf;
+            i += 1;
+        }
+        batch
+    }
+    /// Appends a frame and returns its index, or `None` if the frame doesn't fit.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn push(&mut self, frame: &[u8]) -> Option<usize> {
+        if self.frames == F || self.len + frame.len() > N - 2 || frame.len() > u8::MAX as usize {
+            return None;
+        }
+        let mut i: usize = 0;
+        while i < frame.len() {
+            self.write_buffer.tx_buf[self.len + 2 + i] = frame[i];
+            i += 1;
+        }
+        let index = self.frames;
+        self.set_buffer_base_addresses[index] =
+            SetBufferBaseAddress::new(self.len as u8, self.rx_base_address);
+        self.set_packet_params[index].tx_buf[4] = frame.len() as u8;
+        self.len += frame.len();
+        self.frames += 1;
+        Some(index)
+    }
+    /// Number of frames in the batch.
+    #[inline]
+    pub const fn frames(&self) -> usize {
+        self.frames
+    }
+    #[inline]
+    pub const fn clear(&mut self) {
+        self.len = 0;
+        self.frames = 0;
+    }
+    /// Descriptor for `WriteBuffer`, writing all frames.
+    #[inline]
+    pub const fn write_descriptor(&self) -> SpiDescriptor {
+        SpiDescriptor {
+            transfer_length: transfer_length(self.len + 2),
+            ..self.write_buffer.descriptor()
+        }
+    }
+    /// Descriptors for `SetBufferBaseAddress`, `SetPacketParams` and `SetTx` of `frame`.
+    ///
+    /// ## Panics
+    /// If `frame` is not less than `F`.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn frame_descriptors(&self, frame: usize) -> [SpiDescriptor; 3] {
+        [
+            self.set_buffer_base_addresses[frame].descriptor(),
+            self.set_packet_params[frame].descriptor(),
+            self.set_tx.descriptor(),
+        ]
+    }
+    /// Descriptors for `SetBufferBaseAddress`, `SetPacketParams` and `SetTx` of `frame`, or
+    /// `None` if `frame` is not less than `F`.
+    #[inline]
+    pub const fn try_frame_descriptors(&self, frame: usize) -> Option<[SpiDescriptor; 3]> {
+        if frame < F {
+            Some(self.frame_descriptors(frame))
+        } else {
+            None
+        }
+    }
+}
+
+/// # RxTransaction
+/// Starts a reception and, once `RxDone` is raised, reads the payload and packet status and
+/// clears the RX IRQs.
+///
+/// 1. Transfer `set_rx.descriptor()`.
+/// 2. On `RxDone`, transfer `status_descriptors()` and call `update()`.
+/// 3. Transfer `read_descriptors()`, then get the result from `packet()`.
+///
+/// #### Type Parameter `N`
+/// `N` = maximum payload length + 3. Longer payloads are truncated.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::transactions::{RxPacket, RxPacketMeta, RxTransaction};
+///
+/// let mut rx: RxTransaction<67> = RxTransaction::new(0);
+/// assert_eq!(rx.set_rx.tx_buf, [0x82, 0, 0, 0]);
+/// assert_eq!(RxTransaction::<67>::continuous().set_rx.tx_buf, [0x82, 0xFF, 0xFF, 0xFF]);
+///
+/// rx.get_rx_buffer_status.rx_buf[2..4].copy_from_slice(&[5, 0x80]);
+/// rx.get_packet_status.rx_buf[2..5].copy_from_slice(&[184, 0b0010_1000, 162]);
+/// rx.update();
+/// assert_eq!(rx.read_buffer.tx_buf[1], 0x80);
+/// assert_eq!(rx.read_descriptors()[0].transfer_length, 8);
+///
+/// rx.read_buffer.rx_buf[3..8].copy_from_slice(b"hello");
+/// assert_eq!(
+///     rx.packet(),
+///     RxPacket {
+///         payload: b"hello",
+///         meta: RxPacketMeta {
+///             payload_length: 5,
+///             rx_start_buffer_pointer: 0x80,
+///             rssi_pkt: -92,
+///             snr_pkt: 10,
+///             signal_rssi_pkt: -81,
+///         },
+///     }
+/// );
+/// ```
+pub struct RxTransaction<const N: usize> {
+    pub set_rx: SetRx,
+    pub get_rx_buffer_status: GetRxBufferStatus,
+    pub get_packet_status: GetPacketStatusLora,
+    pub read_buffer: ReadBuffer<N>,
+    pub clear_irq_status: ClearIrqStatus,
+    payload_length: u8,
+}
+impl<const N: usize> RxTransaction<N> {
+    /// Single reception with `timeout`. A timeout of 0 waits until a packet is received.
+    #[inline]
+    pub const fn new(timeout: u32) -> Self {
+        Self {
+            set_rx: SetRx::new(timeout),
+            get_rx_buffer_status: GetRxBufferStatus::new(),
+            get_packet_status: GetPacketStatusLora::new(),
+            read_buffer: ReadBuffer::new(0),
+            clear_irq_status: ClearIrqStatus::new(
+                Irq::new()
+                    .with_rx_done(true)
+                    .with_preamble_detected(true)
+                    .with_sync_word_valid(true)
+                    .with_header_valid(true)
+                    .with_header_err(true)
+                    .with_crc_err(true)
+                    .with_timeout(true),
+            ),
+            payload_length: 0,
+        }
+    }
+    /// Continuous reception - the device stays in RX after each packet.
+    #[inline]
+    pub const fn continuous() -> Self {
+        Self::new(0xFF_FFFF)
+    }
+    /// Descriptors for `GetRxBufferStatus` and `GetPacketStatusLora`.
+    #[inline]
+    pub const fn status_descriptors(&self) -> [SpiDescriptor; 2] {
+        [
+            self.get_rx_buffer_status.descriptor(),
+            self.get_packet_status.descriptor(),
+        ]
+    }
+    /// Prepares `read_buffer` from the buffer status.
+    #[inline]
+    pub const fn update(&mut self) {
+        let payload_length = self.get_rx_buffer_status.payload_length_rx();
+        self.payload_length = if payload_length as usize > N - 3 {
+            (N - 3) as u8
+        } else {
+            payload_length
+        };
+        self.read_buffer.tx_buf[1] = self.get_rx_buffer_status.rx_start_buffer_pointer();
+    }
+    /// Descriptors for `ReadBuffer` and `ClearIrqStatus`. Only the received payload is read.
+    #[inline]
+    pub const fn read_descriptors(&self) -> [SpiDescriptor; 2] {
+        [
+            SpiDescriptor {
+                transfer_length: transfer_length(self.payload_length as usize + 3),
+                ..self.read_buffer.descriptor()
+            },
+            self.clear_irq_status.descriptor(),
+        ]
+    }
+    #[inline]
+    pub const fn meta(&self) -> RxPacketMeta {
+        RxPacketMeta::new(&self.get_rx_buffer_status, &self.get_packet_status)
+    }
+    #[inline]
+    pub fn packet(&self) -> RxPacket<'_> {
+        RxPacket {
+            payload: &self.read_buffer.rx_buf[3..self.payload_length as usize + 3],
+            meta: self.meta(),
+        }
+    }
+}
+/// Payload and metadata of a received packet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RxPacket<'a> {
+    pub payload: &'a [u8],
+    pub meta: RxPacketMeta,
+}
+
+/// # RxPacketMeta
+/// Buffer status and packet status of a received LoRa packet.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{
+///     commands::{GetPacketStatusLora, GetRxBufferStatus},
+///     transactions::RxPacketMeta,
+/// };
+///
+/// let mut get_rx_buffer_status: GetRxBufferStatus = GetRxBufferStatus::new();
+/// let mut get_packet_status: GetPacketStatusLora = GetPacketStatusLora::new();
+/// get_rx_buffer_status.rx_buf[2..4].copy_from_slice(&[16, 8]);
+/// get_packet_status.rx_buf[2..5].copy_from_slice(&[184, 0b1111_1100, 162]);
+///
+/// let meta = RxPacketMeta::new(&get_rx_buffer_status, &get_packet_status);
+/// assert_eq!(meta.payload_length, 16);
+/// assert_eq!(meta.rx_start_buffer_pointer, 8);
+/// assert_eq!(meta.rssi_pkt, -92);
+/// assert_eq!(meta.snr_pkt, -1);
+/// assert_eq!(meta.signal_rssi_pkt, -81);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RxPacketMeta {
+    pub payload_length: u8,
+    pub rx_start_buffer_pointer: u8,
+    pub rssi_pkt: i8,
+    pub snr_pkt: i8,
+    pub signal_rssi_pkt: i8,
+}
+impl RxPacketMeta {
+    #[inline]
+    pub const fn new(
+        get_rx_buffer_status: &GetRxBufferStatus,
+        get_packet_status: &GetPacketStatusLora,
+    ) -> Self {
+        Self {
+            payload_length: get_rx_buffer_status.payload_length_rx(),
+            rx_start_buffer_pointer: get_rx_buffer_status.rx_start_buffer_pointer(),
+            rssi_pkt: get_packet_status.rssi_pkt(),
+            snr_pkt: get_packet_status.snr_pkt(),
+            signal_rssi_pkt: get_packet_status.signal_rssi_pkt(),
+        }
+    }
+}
+
+/// # PingPongRx
+/// Receives alternately into the lower and upper half of the radio data buffer and into two
+/// `ReadBuffer`s, so the payload of one packet can be processed while the next one is received
+/// and read.
+///
+/// 1. Transfer `arm_descriptor()`, then start RX.
+/// 2. On `RxDone`, transfer `status_descriptor()` and call `update()`.
+/// 3. Transfer `read_descriptors()` - reads the payload and arms the other half - and restart
+///    RX. `payload()` holds the payload until the next `update()` but one.
+///
+/// #### Type Parameter `N`
+/// `N` = maximum payload length + 3, at most 131. Longer payloads are truncated.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::transactions::PingPongRx;
+///
+/// let mut rx: PingPongRx<67> = PingPongRx::new();
+/// assert_eq!(rx.set_buffer_base_addresses[0].tx_buf, [0x8F, 0x00, 0x00]);
+/// assert_eq!(rx.set_buffer_base_addresses[1].tx_buf, [0x8F, 0x80, 0x80]);
+///
+/// rx.get_rx_buffer_status.rx_buf[2..4].copy_from_slice(&[2, 0x00]);
+/// rx.update();
+/// assert_eq!(rx.read_descriptors()[0].transfer_length, 5);
+/// rx.read_buffers[0].rx_buf[3..5].copy_from_slice(b"hi");
+/// assert_eq!(rx.payload(), b"hi");
+///
+/// rx.get_rx_buffer_status.rx_buf[2..4].copy_from_slice(&[3, 0x80]);
+/// rx.update();
+/// assert_eq!(rx.read_buffers[1].tx_buf[1], 0x80);
+/// rx.read_buffers[1].rx_buf[3..6].copy_from_slice(b"hey");
+/// assert_eq!(rx.payload(), b"hey");
+/// ```
+pub struct PingPongRx<const N: usize> {
+    pub get_rx_buffer_status: GetRxBufferStatus,
+    pub read_buffers: [ReadBuffer<N>; 2],
+    pub set_buffer_base_addresses: [SetBufferBaseAddress; 2],
+    payload_lengths: [u8; 2],
+    current: usize,
+}
+impl<const N: usize> PingPongRx<N> {
+    const MAX_SIZE: () = assert!(
+        N >= 3 && N - 3 <= 0x80,
+        "payload exceeds half of the data buffer"
+    );
+
+    #[inline]
+    pub const fn new() -> Self {
+        let () = Self::MAX_SIZE;
+        Self {
+            get_rx_buffer_status: GetRxBufferStatus::new(),
+            read_buffers: [ReadBuffer::new(0x00), ReadBuffer::new(0x80)],
+            set_buffer_base_addresses: [
+                SetBufferBaseAddress::new(0x00, 0x00),
+                SetBufferBaseAddress::new(0x80, 0x80),
+            ],
+            payload_lengths: [0; 2],
+            current: 0,
+        }
+    }
+    /// Descriptor for `SetBufferBaseAddress` of the half receiving the next packet.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn arm_descriptor(&self) -> SpiDescriptor {
+        self.set_buffer_base_addresses[self.current].descriptor()
+    }
+    #[inline]
+    pub const fn status_descriptor(&self) -> SpiDescriptor {
+        self.get_rx_buffer_status.descriptor()
+    }
+    /// Prepares the `ReadBuffer` of the received packet and switches to the other half.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn update(&mut self) {
+        let payload_length = self.get_rx_buffer_status.payload_length_rx();
+        self.payload_lengths[self.current] = if payload_length as usize > N - 3 {
+            (N - 3) as u8
+        } else {
+            payload_length
+        };
+        self.read_buffers[self.current].tx_buf[1] =
+            self.get_rx_buffer_status.rx_start_buffer_pointer();
+        self.current ^= 1;
+    }
+    /// Descriptors for `ReadBuffer` of the received packet and `SetBufferBaseAddress` of the
+    /// other half.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub const fn read_descriptors(&self) -> [SpiDescriptor; 2] {
+        let received = self.current ^ 1;
+        [
+            SpiDescriptor {
+                transfer_length: transfer_length(self.payload_lengths[received] as usize + 3),
+                ..self.read_buffers[received].descriptor()
+            },
+            self.arm_descriptor(),
+        ]
+    }
+    /// Payload of the most recently received packet.
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub fn payload(&self) -> &[u8] {
+        let received = self.current ^ 1;
+        &self.read_buffers[received].rx_buf[3..self.payload_lengths[received] as usize + 3]
+    }
+}
+
+/// # RxWindow
+/// A Class A style receive window opened a fixed delay after `TxDone`, sized so that a preamble
+/// is still detected with the worst-case clock drift of the delay.
+///
+/// The window is `symbols()` symbols long, set with `SetLoraSymbNumTimeout` and a single `SetRx`
+/// without timeout. Transfer `descriptors()` from standby so that the RX starts `start_us()`
+/// microseconds after `TxDone`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{commands::{Bw, Cr, Sf}, transactions::RxWindow};
+///
+/// static RX1: RxWindow = RxWindow::new(868_100_000, Sf::Sf7, Bw::Bw125, Cr::Cr4_5, 1000, 100);
+/// assert_eq!(RX1.symbols(), 6);
+/// assert_eq!(RX1.start_us(), 1_001_024);
+/// assert_eq!(RX1.set_lora_symb_num_timeout.tx_buf, [0xA0, 6]);
+/// assert_eq!(RX1.set_rx.tx_buf, [0x82, 0, 0, 0]);
+///
+/// # #[cfg(not(feature = "llcc68"))] {
+/// static RX2: RxWindow = RxWindow::new(869_525_000, Sf::Sf12, Bw::Bw125, Cr::Cr4_5, 2000, 100);
+/// assert_eq!(RX2.symbols(), 6);
+/// assert_eq!(RX2.start_us(), 2_032_768);
+/// assert_eq!(RX2.set_modulation_params.tx_buf, [0x8B, 0x0C, 0x04, 0x01, 1]);
+/// assert_eq!(RX2.descriptors().len(), 4);
+/// # }
+/// ```
+pub struct RxWindow {
+    pub set_rf_frequency: SetRfFrequency,
+    pub set_modulation_params: SetModulationParamsLora,
+    pub set_lora_symb_num_timeout: SetLoraSymbNumTimeout,
+    pub set_rx: SetRx,
+    start_us: u32,
+}
+impl RxWindow {
+    /// Minimum number of symbols for the preamble to be detected.
+    pub const MIN_RX_SYMBOLS: u32 = 6;
+
+    /// Window on `freq_hz` opened `delay_ms` after `TxDone`, with a clock drift of `drift_ppm`.
+    ///
+    /// ## Panics
+    /// With the `llcc68` feature, if the LLCC68 does not support `sf` at `bw`.
+    #[inline]
+    pub const fn new(freq_hz: u32, sf: Sf, bw: Bw, cr: Cr, delay_ms: u32, drift_ppm: u32) -> Self {
+        let symbol_us = timing::symbol_duration_us(sf, bw) as u64;
+        let delay_us = delay_ms as u64 * 1000;
+        let rx_error_us = delay_us * drift_ppm as u64 / 1_000_000;
+        // Preamble symbols which may be missed on either side of the nominal start.
+        let mut symbols = ((2 * Self::MIN_RX_SYMBOLS as u64 - 8) * symbol_us + 2 * rx_error_us)
+            .div_ceil(symbol_us);
+        if symbols < Self::MIN_RX_SYMBOLS as u64 {
+            symbols = Self::MIN_RX_SYMBOLS as u64;
+        }
+        if symbols > u8::MAX as u64 {
+            symbols = u8::MAX as u64;
+        }
+        // Centre the window on the end of the first 4 preamble symbols.
+        let start_us = (delay_us + 4 * symbol_us).saturating_sub(symbols * symbol_us / 2);
+        Self {
+            set_rf_frequency: SetRfFrequency::from_hz(freq_hz),
+            set_modulation_params: SetModulationParamsLora::new_auto_ldro(sf, bw, cr),
+            set_lora_symb_num_timeout: SetLoraSymbNumTimeout::new(symbols as u8),
+            set_rx: SetRx::new(0),
+            start_us: start_us as u32,
+        }
+    }
+    /// Window length in symbols.
+    #[inline]
+    pub const fn symbols(&self) -> u8 {
+        self.set_lora_symb_num_timeout.tx_buf[1]
+    }
+    /// Time from `TxDone` to the start of RX, in microseconds.
+    #[inline]
+    pub const fn start_us(&self) -> u32 {
+        self.start_us
+    }
+    /// Descriptors for `SetRfFrequency`, `SetModulationParamsLora`, `SetLoraSymbNumTimeout` and
+    /// `SetRx`.
+    #[inline]
+    pub const fn descriptors(&self) -> [SpiDescriptor; 4] {
+        [
+            self.set_rf_frequency.descriptor(),
+            self.set_modulation_params.descriptor(),
+            self.set_lora_symb_num_timeout.descriptor(),
+            self.set_rx.descriptor(),
+        ]
+    }
+}
+
+/// # DutyCycledRx
+/// Wake-on-radio with `SetRxDutyCycle`: the receiver alternates between listening for
+/// `RX_SYMBOLS` symbols and sleeping, and the transmitter sends a preamble long enough to span a
+/// whole sleep period and two listen periods, so that one listen period always falls within it.
+///
+/// Both sides transfer `set_packet_params`, carrying the long preamble. The receiver then
+/// transfers `set_rx_duty_cycle` from standby.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{
+///     commands::{Bw, HeaderType, InvertIq, Sf},
+///     transactions::DutyCycledRx,
+/// };
+///
+/// static WOR: DutyCycledRx = DutyCycledRx::new(
+///     Sf::Sf7,
+///     Bw::Bw125,
+///     100,
+///     HeaderType::VariableLength,
+///     255,
+///     true,
+///     InvertIq::Standard,
+/// );
+/// assert_eq!(WOR.preamble_length(), 106);
+/// assert_eq!(WOR.set_packet_params.tx_buf, [0x8C, 0, 106, 0, 255, 1, 0]);
+/// assert_eq!(WOR.set_rx_duty_cycle.tx_buf, [0x94, 0, 0x02, 0x0D, 0, 0x16, 0xF3]);
+/// assert_eq!(WOR.rx_descriptors().len(), 2);
+/// ```
+pub struct DutyCycledRx {
+    pub set_packet_params: SetPacketParams,
+    pub set_rx_duty_cycle: SetRxDutyCycle,
+}
+impl DutyCycledRx {
+    /// Number of symbols listened for in each period.
+    pub const RX_SYMBOLS: u32 = 8;
+
+    /// Duty cycle waking up at least every `wake_latency_ms`.
+    #[inline]
+    pub const fn new(
+        sf: Sf,
+        bw: Bw,
+        wake_latency_ms: u32,
+        header_type: HeaderType,
+        payload_length: u8,
+        crc_type: bool,
+        invert_iq: InvertIq,
+    ) -> Self {
+        let rx_period = timing::symbols_to_timeout(Self::RX_SYMBOLS, sf, bw);
+        let sleep_period = timing::ms_to_timeout(wake_latency_ms).saturating_sub(rx_period);
+        // Timeout steps are 1000 / 64 us.
+        let mut preamble_length = ((sleep_period as u64 + 2 * rx_period as u64) * 1000)
+            .div_ceil(64 * timing::symbol_duration_us(sf, bw) as u64);
+        if preamble_length > u16::MAX as u64 {
+            preamble_length = u16::MAX as u64;
+        }
+        Self {
+            set_packet_params: SetPacketParams::new(
+                preamble_length as u16,
+                header_type,
+                payload_length,
+                crc_type,
+                invert_iq,
+            ),
+            set_rx_duty_cycle: SetRxDutyCycle::new(rx_period, sleep_period),
+        }
+    }
+    /// Preamble length in symbols.
+    #[inline]
+    pub const fn preamble_length(&self) -> u16 {
+        u16::from_be_bytes([
+            self.set_packet_params.tx_buf[1],
+            self.set_packet_params.tx_buf[2],
+        ])
+    }
+    /// Descriptors for `SetPacketParams` and `SetRxDutyCycle`.
+    #[inline]
+    pub const fn rx_descriptors(&self) -> [SpiDescriptor; 2] {
+        [
+            self.set_packet_params.descriptor(),
+            self.set_rx_duty_cycle.descriptor(),
+        ]
+    }
+}
+
+/// # Repeater
+/// Retransmits the last received packet from the radio data buffer, without reading it out: the
+/// TX base address is moved to where the packet was received. For the payload to be inspected
+/// or modified on the way, read it with a `ReadBuffer` instead and send it back with
+/// `ReadBuffer::write_descriptor()`.
+///
+/// After `RxDone`, transfer `get_rx_buffer_status`, call `update()` and transfer
+/// `descriptors()` from standby.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{
+///     commands::{HeaderType, InvertIq, SetPacketParams},
+///     transactions::Repeater,
+/// };
+///
+/// let mut repeater: Repeater = Repeater::new(
+///     0x00,
+///     SetPacketParams::new(8, HeaderType::VariableLength, 0, true, InvertIq::Standard),
+///     0,
+/// );
+/// repeater.get_rx_buffer_status.rx_buf[2..4].copy_from_slice(&[12, 0x40]);
+/// repeater.update();
+/// assert_eq!(repeater.set_buffer_base_address.tx_buf, [0x8F, 0x40, 0x00]);
+/// assert_eq!(repeater.set_packet_params.tx_buf[4], 12);
+/// assert_eq!(repeater.descriptors().len(), 3);
+/// ```
+pub struct Repeater {
+    pub get_rx_buffer_status: GetRxBufferStatus,
+    pub set_buffer_base_address: SetBufferBaseAddress,
+    pub set_packet_params: SetPacketParams,
+    pub set_tx: SetTx,
+    rx_base_address: u8,
+}
+impl Repeater {
+    /// `rx_base_address` is the RX base address to keep, `set_packet_params` the packet
+    /// parameters to which the payload length is applied.
+    #[inline]
+    pub const fn new(
+        rx_base_address: u8,
+        set_packet_params: SetPacketParams,
+        timeout: u32,
+    ) -> Self {
+        Self {
+            get_rx_buffer_status: GetRxBufferStatus::new(),
+            set_buffer_base_address: SetBufferBaseAddress::new(0, rx_base_address),
+            set_packet_params,
+            set_tx: SetTx::new(timeout),
+            rx_base_address,
+        }
+    }
+    /// Points the TX base address and payload length at the received packet.
+    #[inline]
+    pub const fn update(&mut self) {
+        self.set_buffer_base_address = SetBufferBaseAddress::new(
+            self.get_rx_buffer_status.rx_start_buffer_pointer(),
+            self.rx_base_address,
+        );
+        self.set_packet_params.tx_buf[4] = self.get_rx_buffer_status.payload_length_rx();
+    }
+    /// Descriptors for `SetBufferBaseAddress`, `SetPacketParams` and `SetTx`.
+    #[inline]
+    pub const fn descriptors(&self) -> [SpiDescriptor; 3] {
+        [
+            self.set_buffer_base_address.descriptor(),
+            self.set_packet_params.descriptor(),
+            self.set_tx.descriptor(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic]
+    fn test_tx_payload_too_long() {
+        let mut tx: TxTransaction<6> = TxTransaction::new(
+            0,
+            SetPacketParams::new(8, HeaderType::VariableLength, 0, true, InvertIq::Standard),
+        );
+        tx.prepare(b"hello", 0);
+    }
+
+    #[test]
+    fn test_rx_payload_truncated() {
+        let mut rx: RxTransaction<7> = RxTransaction::new(0);
+        rx.get_rx_buffer_status.rx_buf[2] = 10;
+        rx.update();
+        assert_eq!(rx.read_descriptors()[0].transfer_length, 7);
+        assert_eq!(rx.packet().payload.len(), 4);
+    }
+
+    #[test]
+    fn test_rx_window_drift() {
+        let rx_window = RxWindow::new(868_100_000, Sf::Sf7, Bw::Bw125, Cr::Cr4_5, 5000, 1000);
+        assert_eq!(rx_window.symbols(), 14);
+        assert_eq!(rx_window.start_us(), 4_996_928);
+        assert_eq!(rx_window.set_modulation_params.tx_buf[4], 0);
+    }
+}
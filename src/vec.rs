@@ -0,0 +1,98 @@
+//! `Vec` backed payload buffers, for host tooling, gateways and tests where the payload length
+//! is only known at runtime.
+
+use super::commands::{SpiDescriptor, transfer_length};
+use alloc::{vec, vec::Vec};
+
+/// # VecWriteBuffer
+/// [`WriteBuffer`](super::commands::WriteBuffer) with a runtime payload length.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::vec::VecWriteBuffer;
+///
+/// let write_buffer = VecWriteBuffer::new(0x10, b"hello");
+/// assert_eq!(write_buffer.tx_buf, [0x0E, 0x10, b'h', b'e', b'l', b'l', b'o']);
+/// assert_eq!(write_buffer.rx_buf, [0; 7]);
+/// assert_eq!(write_buffer.data(), b"hello");
+/// assert_eq!(write_buffer.descriptor().transfer_length, 7);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VecWriteBuffer {
+    pub tx_buf: Vec<u8>,
+    pub rx_buf: Vec<u8>,
+}
+impl VecWriteBuffer {
+    const OPCODE: u8 = 0x0E;
+
+    pub fn new(offset: u8, data: &[u8]) -> Self {
+        let mut tx_buf = Vec::with_capacity(data.len() + 2);
+        tx_buf.extend_from_slice(&[Self::OPCODE, offset]);
+        tx_buf.extend_from_slice(data);
+        Self {
+            rx_buf: vec![0; tx_buf.len()],
+            tx_buf,
+        }
+    }
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub fn data(&self) -> &[u8] {
+        &self.tx_buf[2..]
+    }
+    #[inline]
+    pub fn descriptor(&self) -> SpiDescriptor {
+        SpiDescriptor {
+            tx_buf_ptr: self.tx_buf.as_ptr(),
+            rx_buf_ptr: self.rx_buf.as_ptr(),
+            transfer_length: transfer_length(self.tx_buf.len()),
+        }
+    }
+}
+
+/// # VecReadBuffer
+/// [`ReadBuffer`](super::commands::ReadBuffer) with a runtime payload length, e.g. the
+/// `payload_length_rx` of `GetRxBufferStatus`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::vec::VecReadBuffer;
+///
+/// let mut read_buffer = VecReadBuffer::new(0x17, 5);
+/// assert_eq!(read_buffer.tx_buf, [0x1E, 0x17, 0, 0, 0, 0, 0, 0]);
+/// assert_eq!(read_buffer.descriptor().transfer_length, 8);
+/// read_buffer.rx_buf[3..].copy_from_slice(b"hello");
+/// assert_eq!(read_buffer.data(), b"hello");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VecReadBuffer {
+    pub tx_buf: Vec<u8>,
+    pub rx_buf: Vec<u8>,
+}
+impl VecReadBuffer {
+    const OPCODE: u8 = 0x1E;
+
+    /// Reads `len` bytes starting at `offset`.
+    #[allow(clippy::indexing_slicing)]
+    pub fn new(offset: u8, len: usize) -> Self {
+        let mut tx_buf = vec![0; len + 3];
+        tx_buf[0] = Self::OPCODE;
+        tx_buf[1] = offset;
+        Self {
+            tx_buf,
+            rx_buf: vec![0; len + 3],
+        }
+    }
+    #[inline]
+    #[allow(clippy::indexing_slicing)]
+    pub fn data(&self) -> &[u8] {
+        &self.rx_buf[3..]
+    }
+    #[inline]
+    pub fn descriptor(&self) -> SpiDescriptor {
+        SpiDescriptor {
+            tx_buf_ptr: self.tx_buf.as_ptr(),
+            rx_buf_ptr: self.rx_buf.as_ptr(),
+            transfer_length: transfer_length(self.tx_buf.len()),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! Read-back verification of register writes, for detecting SPI signal integrity issues.
+#![allow(clippy::new_without_default)]
+
+use super::commands::{ReadRegister, ReadRegisters, SpiDescriptor, WriteRegister, WriteRegisters};
+use super::registers::Register;
+
+/// A register read back with a different value than written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub address: u16,
+    pub written: u8,
+    pub read: u8,
+}
+
+/// # VerifiedWrite
+/// Writes a register and reads it back. Transfer both `descriptors()` in order, then call
+/// `verify()`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers::LoraSyncWordMsb, verify::{Mismatch, VerifiedWrite}};
+///
+/// let mut verified_write: VerifiedWrite<LoraSyncWordMsb> = VerifiedWrite::new(LoraSyncWordMsb(0x34));
+/// assert_eq!(verified_write.write.tx_buf, [0x0D, 0x07, 0x40, 0x34]);
+/// assert_eq!(verified_write.read.tx_buf, [0x1D, 0x07, 0x40, 0, 0]);
+/// assert_eq!(verified_write.descriptors()[1].transfer_length, 5);
+///
+/// verified_write.read.rx_buf[4] = 0x34;
+/// assert_eq!(verified_write.verify(), Ok(()));
+/// verified_write.read.rx_buf[4] = 0x24;
+/// assert_eq!(
+///     verified_write.verify(),
+///     Err(Mismatch { address: 0x0740, written: 0x34, read: 0x24 })
+/// );
+/// ```
+pub struct VerifiedWrite<R> {
+    pub write: WriteRegister,
+    pub read: ReadRegister<R>,
+}
+impl<R: Register> VerifiedWrite<R> {
+    maybe_const! {
+        #[inline]
+        pub [const] fn new(register: R) -> Self
+        where
+            R: [const] Register,
+        {
+            Self {
+                write: WriteRegister::new(register),
+                read: ReadRegister::new(),
+            }
+        }
+    }
+    #[inline]
+    pub const fn descriptors(&self) -> [SpiDescriptor; 2] {
+        [self.write.descriptor(), self.read.descriptor()]
+    }
+    /// Compares the value read back with the value written.
+    #[inline]
+    pub const fn verify(&self) -> Result<(), Mismatch> {
+        let written = self.write.tx_buf[3];
+        let read = self.read.rx_buf[4];
+        if written == read {
+            Ok(())
+        } else {
+            Err(Mismatch {
+                address: R::ADDRESS,
+                written,
+                read,
+            })
+        }
+    }
+}
+
+/// # VerifiedWrites
+/// Writes contiguous registers with one `WriteRegisters<N>` and reads them back with one
+/// `ReadRegisters<M>`, where `M = N + 1`. Transfer both `descriptors()` in order, then call
+/// `verify()`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{
+///     commands::WriteRegisters,
+///     registers::LoraSyncWord,
+///     verify::{Mismatch, VerifiedWrites},
+/// };
+///
+/// let mut verified_writes: VerifiedWrites<5, 6> =
+///     VerifiedWrites::new(WriteRegisters::<5>::from_block(LoraSyncWord::PUBLIC));
+/// assert_eq!(verified_writes.read.tx_buf, [0x1D, 0x07, 0x40, 0, 0, 0]);
+///
+/// verified_writes.read.rx_buf[4..6].copy_from_slice(&[0x34, 0x44]);
+/// assert_eq!(verified_writes.verify(), Ok(()));
+/// verified_writes.read.rx_buf[5] = 0x24;
+/// assert_eq!(
+///     verified_writes.verify(),
+///     Err(Mismatch { address: 0x0741, written: 0x44, read: 0x24 })
+/// );
+/// ```
+pub struct VerifiedWrites<const N: usize, const M: usize> {
+    pub write: WriteRegisters<N>,
+    pub read: ReadRegisters<M>,
+}
+impl<const N: usize, const M: usize> VerifiedWrites<N, M> {
+    const READ_SIZE: () = assert!(M == N + 1, "M must be N + 1");
+
+    /// Reads back the registers written by `write`.
+    #[inline]
+    pub const fn new(write: WriteRegisters<N>) -> Self {
+        let () = Self::READ_SIZE;
+        let address = u16::from_be_bytes([write.tx_buf[1], write.tx_buf[2]]);
+        Self {
+            write,
+            read: ReadRegisters::<M>::at_address(address),
+        }
+    }
+    #[inline]
+    pub const fn descriptors(&self) -> [SpiDescriptor; 2] {
+        [self.write.descriptor(), self.read.descriptor()]
+    }
+    /// Compares the values read back with the values written, returning the first mismatch.
+    #[allow(clippy::indexing_slicing)]
+    pub const fn verify(&self) -> Result<(), Mismatch> {
+        let address = u16::from_be_bytes([self.write.tx_buf[1], self.write.tx_buf[2]]);
+        let mut i: usize = 3;
+        while i < N {
+            let written = self.write.tx_buf[i];
+            let read = self.read.rx_buf[i + 1];
+            if written != read {
+                return Err(Mismatch {
+                    address: address + (i - 3) as u16,
+                    written,
+                    read,
+                });
+            }
+            i += 1;
+        }
+        Ok(())
+    }
+}